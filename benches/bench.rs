@@ -0,0 +1,166 @@
+//! Benchmarks that build on every toolchain that CI tests, since the `test`
+//! crate's `Bencher` is only available on nightly. Run them with `cargo bench`.
+
+extern crate gimli;
+
+use gimli::parser::{parse_abbreviations, CompilationUnitsIter};
+use gimli::{AbbreviationTag, AttributeName, LittleEndian};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const NUM_ABBREVS: u64 = 100;
+const NUM_ENTRIES: u64 = 10_000;
+
+/// The least time to run each benchmark for. The number of iterations doubles
+/// until a run takes at least this long, which also warms the benchmark up.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+
+/// A benchmark, which times its code with the given `Bencher`.
+type Bench = fn(&mut Bencher);
+
+/// A minimal stand-in for the `test` crate's `Bencher`.
+struct Bencher {
+    name: &'static str,
+}
+
+impl Bencher {
+    /// Run `f` repeatedly, and print the average time that it took.
+    fn iter<F>(&mut self, mut f: F)
+        where F: FnMut()
+    {
+        let mut iterations = 1u64;
+        loop {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                f();
+            }
+            let elapsed = start.elapsed();
+            if elapsed >= BENCH_TIME {
+                let ns_per_iter = elapsed.as_nanos() / iterations as u128;
+                println!("{:40} {:>12} ns/iter", self.name, ns_per_iter);
+                return;
+            }
+            iterations *= 2;
+        }
+    }
+}
+
+/// Push an unsigned LEB128 encoded integer onto `buf`.
+fn push_uleb(buf: &mut Vec<u8>, mut val: u64) {
+    loop {
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        if val == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+/// Build a `.debug_abbrev` section whose abbreviations are numbered
+/// `1..NUM_ABBREVS + 1`, the way that compilers typically number them.
+///
+/// Abbreviation 1 is a `DW_TAG_compile_unit` with children, and the rest are
+/// `DW_TAG_variable`s without children, each with a `DW_AT_name` string, a
+/// `DW_AT_decl_line` data2 and a varying number of `DW_AT_const_value` data4s.
+fn debug_abbrev() -> Vec<u8> {
+    let mut buf = Vec::new();
+    for code in 1..NUM_ABBREVS + 1 {
+        push_uleb(&mut buf, code);
+        if code == 1 {
+            buf.extend_from_slice(&[0x11, 0x01]);
+        } else {
+            buf.extend_from_slice(&[0x34, 0x00]);
+        }
+
+        buf.extend_from_slice(&[0x03, 0x08, 0x3b, 0x05]);
+        for _ in 0..code % 4 {
+            buf.extend_from_slice(&[0x1c, 0x06]);
+        }
+        buf.extend_from_slice(&[0x00, 0x00]);
+    }
+    buf.push(0x00);
+    buf
+}
+
+/// Build a `.debug_info` section with a single compilation unit containing
+/// `NUM_ENTRIES` children, using the abbreviations from `debug_abbrev`.
+fn debug_info() -> Vec<u8> {
+    let mut entries = Vec::new();
+    for i in 0..NUM_ENTRIES + 1 {
+        let code = if i == 0 { 1 } else { 2 + i % (NUM_ABBREVS - 1) };
+        push_uleb(&mut entries, code);
+        entries.extend_from_slice(b"name\0");
+        entries.extend_from_slice(&[0x2a, 0x00]);
+        for _ in 0..code % 4 {
+            entries.extend_from_slice(&[0x01, 0x02, 0x03, 0x04]);
+        }
+    }
+    entries.push(0x00);
+
+    let unit_length = entries.len() as u32 + 7;
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&[unit_length as u8,
+                            (unit_length >> 8) as u8,
+                            (unit_length >> 16) as u8,
+                            (unit_length >> 24) as u8]);
+    buf.extend_from_slice(&[0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08]);
+    buf.extend_from_slice(&entries);
+    buf
+}
+
+fn bench_parsing_debug_abbrev(b: &mut Bencher) {
+    let debug_abbrev = debug_abbrev();
+
+    b.iter(|| {
+        let abbrevs = parse_abbreviations(&debug_abbrev).unwrap().1;
+        black_box(&abbrevs);
+    });
+}
+
+fn bench_looking_up_abbreviations(b: &mut Bencher) {
+    let abbrevs = parse_abbreviations(&debug_abbrev()).unwrap().1;
+
+    b.iter(|| {
+        for code in 1..NUM_ABBREVS + 1 {
+            let abbrev = abbrevs.get(code).unwrap();
+            black_box(abbrev.attributes());
+        }
+    });
+}
+
+fn bench_iterating_debug_info_entries(b: &mut Bencher) {
+    let abbrevs = parse_abbreviations(&debug_abbrev()).unwrap().1;
+    let debug_info = debug_info();
+
+    b.iter(|| {
+        for unit in CompilationUnitsIter::new(&debug_info, LittleEndian) {
+            let unit = unit.unwrap();
+            let mut cursor = unit.entries(&abbrevs);
+            while let Some((_, entry)) = cursor.next_dfs().unwrap() {
+                black_box(entry.tag() == AbbreviationTag::Variable);
+                black_box(entry.attr(AttributeName::DeclLine));
+            }
+        }
+    });
+}
+
+fn main() {
+    let benches: [(&'static str, Bench); 3] = [
+        ("bench_parsing_debug_abbrev", bench_parsing_debug_abbrev),
+        ("bench_looking_up_abbreviations", bench_looking_up_abbreviations),
+        ("bench_iterating_debug_info_entries", bench_iterating_debug_info_entries),
+    ];
+
+    // `cargo bench` passes `--bench`, and any other argument filters by name.
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    for &(name, bench) in benches.iter() {
+        if filters.is_empty() || filters.iter().any(|filter| name.contains(filter.as_str())) {
+            bench(&mut Bencher { name });
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! Functions for parsing the `.debug_aranges` section.
+
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use nom::{Err, ErrorKind, IResult};
+use parser::{Error, Format, into_result, parse_address, parse_initial_length, parse_offset,
+             parse_u16, parse_u8, ParseResult, take};
+use std::cmp;
+
+/// The `.debug_aranges` section, which maps address ranges to the compilation
+/// units in `.debug_info` that describe them.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugAranges<'input, Endian>
+    where Endian: Endianity
+{
+    debug_aranges_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugAranges<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugAranges` instance from the data in the
+    /// `.debug_aranges` section, which is encoded in the given byte order.
+    pub fn new(debug_aranges_section: &'input [u8],
+               endian: Endian)
+               -> DebugAranges<'input, Endian> {
+        DebugAranges {
+            debug_aranges_section,
+            endian,
+        }
+    }
+
+    /// Iterate over the sets of address ranges in this section, one for each
+    /// compilation unit that has any.
+    pub fn sets(&self) -> ArangeSetsIter<'input, Endian> {
+        ArangeSetsIter {
+            input: self.debug_aranges_section,
+            offset: 0,
+            endian: self.endian,
+        }
+    }
+}
+
+/// The header of a set of address ranges in the `.debug_aranges` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArangeHeader {
+    offset: u64,
+    unit_length: u64,
+    format: Format,
+    version: u16,
+    debug_info_offset: u64,
+    address_size: u8,
+    segment_selector_size: u8,
+}
+
+impl ArangeHeader {
+    /// Get the offset of this header from the start of the `.debug_aranges`
+    /// section.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the length of this set, not including its initial length field.
+    pub fn unit_length(&self) -> u64 {
+        self.unit_length
+    }
+
+    /// Return true if this set uses the 64-bit DWARF format, false if it uses
+    /// the 32-bit format.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Get the version of this set's encoding.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get the offset of the compilation unit's header in the `.debug_info`
+    /// section, which can be used to parse it with
+    /// `parse_compilation_unit_header`.
+    pub fn debug_info_offset(&self) -> u64 {
+        self.debug_info_offset
+    }
+
+    /// Get the size of addresses (in bytes) in this set.
+    pub fn address_size(&self) -> u8 {
+        self.address_size
+    }
+
+    /// Get the size of segment selectors (in bytes) in this set. This is 0
+    /// for targets with a flat address space.
+    pub fn segment_selector_size(&self) -> u8 {
+        self.segment_selector_size
+    }
+}
+
+/// An address range in a set of address ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArangeEntry {
+    segment: u64,
+    address: u64,
+    length: u64,
+}
+
+impl ArangeEntry {
+    /// Construct a new `ArangeEntry`.
+    pub fn new(segment: u64, address: u64, length: u64) -> ArangeEntry {
+        ArangeEntry {
+            segment,
+            address,
+            length,
+        }
+    }
+
+    /// Get the segment selector of this range, or 0 if the set does not have
+    /// segment selectors.
+    pub fn segment(&self) -> u64 {
+        self.segment
+    }
+
+    /// Get the start address of this range.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Get the length of this range, in bytes.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+}
+
+/// A set of address ranges that belong to a single compilation unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArangeSet {
+    header: ArangeHeader,
+    entries: Vec<ArangeEntry>,
+}
+
+impl ArangeSet {
+    /// Get this set's header.
+    pub fn header(&self) -> &ArangeHeader {
+        &self.header
+    }
+
+    /// Get this set's address ranges, not including the terminating entry.
+    pub fn entries(&self) -> &[ArangeEntry] {
+        &self.entries[..]
+    }
+}
+
+/// Parse a segment selector or an address of the given size. Sizes of 0 mean
+/// the value is not present.
+fn parse_sized<Endian>(input: &[u8], size: u8, endian: Endian) -> ParseResult<&[u8], u64>
+    where Endian: Endianity
+{
+    if size == 0 {
+        IResult::Done(input, 0)
+    } else {
+        parse_address(input, size, endian)
+    }
+}
+
+/// Parse a set of address ranges, which is at the given offset in the
+/// `.debug_aranges` section.
+fn parse_arange_set<Endian>(input: &[u8],
+                            offset: u64,
+                            endian: Endian)
+                            -> ParseResult<&[u8], ArangeSet>
+    where Endian: Endianity
+{
+    let (rest, (unit_length, format)) = try_parse!(input, call!(parse_initial_length, endian));
+    let (after_unit, unit) = try_parse!(rest, call!(take, unit_length));
+
+    let (rest, version) = try_parse!(unit, call!(parse_u16, endian));
+    if version != 2 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+
+    let (rest, debug_info_offset) = try_parse!(rest, call!(parse_offset, format, endian));
+    let (rest, address_size) = try_parse!(rest, parse_u8);
+    let (rest, segment_selector_size) = try_parse!(rest, parse_u8);
+    match address_size {
+        1 | 2 | 4 | 8 => {},
+        _ => return IResult::Error(Err::Position(
+            ErrorKind::Custom(Error::UnsupportedAddressSize), input)),
+    }
+    match segment_selector_size {
+        0 | 1 | 2 | 4 | 8 => {},
+        _ => return IResult::Error(Err::Position(
+            ErrorKind::Custom(Error::UnsupportedSegmentSize), input)),
+    }
+
+    // The first tuple is aligned to a multiple of the tuple size from the
+    // start of the set, and the header is padded to get there.
+    let tuple_size = segment_selector_size as u64 + 2 * address_size as u64;
+    let header_size = (input.len() - rest.len()) as u64;
+    let padding = (tuple_size - header_size % tuple_size) % tuple_size;
+    let (mut rest, _) = try_parse!(rest, call!(take, padding));
+
+    let mut entries = Vec::new();
+    while !rest.is_empty() {
+        let (rest1, segment) = try_parse!(rest, call!(parse_sized, segment_selector_size, endian));
+        let (rest1, address) = try_parse!(rest1, call!(parse_address, address_size, endian));
+        let (rest1, length) = try_parse!(rest1, call!(parse_address, address_size, endian));
+        rest = rest1;
+        if segment == 0 && address == 0 && length == 0 {
+            break;
+        }
+        entries.push(ArangeEntry::new(segment, address, length));
+    }
+
+    IResult::Done(after_unit,
+                  ArangeSet {
+                      header: ArangeHeader {
+                          offset,
+                          unit_length,
+                          format,
+                          version,
+                          debug_info_offset,
+                          address_size,
+                          segment_selector_size,
+                      },
+                      entries,
+                  })
+}
+
+/// An iterator over the sets of address ranges in a `.debug_aranges` section.
+#[derive(Debug, Clone)]
+pub struct ArangeSetsIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    offset: u64,
+    endian: Endian,
+}
+
+impl<'input, Endian> Iterator for ArangeSetsIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<ArangeSet, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match into_result(parse_arange_set(self.input, self.offset, self.endian)) {
+            Ok((rest, set)) => {
+                self.offset += (self.input.len() - rest.len()) as u64;
+                self.input = rest;
+                Some(Ok(set))
+            },
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+/// An address range that maps to a compilation unit, as stored by
+/// `ArangeTable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Arange {
+    start: u64,
+    end: u64,
+    debug_info_offset: u64,
+}
+
+/// A table for finding the compilation unit that covers an address, built
+/// from the address ranges in a `.debug_aranges` section.
+///
+/// The ranges are sorted by address, so that lookups are binary searches.
+#[derive(Debug, Clone)]
+pub struct ArangeTable {
+    aranges: Vec<Arange>,
+    // The largest end address of each range and all of the ranges before it,
+    // which bounds the search for overlapping ranges.
+    max_ends: Vec<u64>,
+}
+
+impl ArangeTable {
+    /// Build a table from every set of address ranges in the given section.
+    pub fn new<Endian>(debug_aranges: &DebugAranges<Endian>) -> Result<ArangeTable, Error>
+        where Endian: Endianity
+    {
+        let mut aranges = Vec::new();
+        for set in debug_aranges.sets() {
+            let set = set?;
+            let debug_info_offset = set.header().debug_info_offset();
+            aranges.extend(set.entries()
+                .iter()
+                .filter(|entry| entry.length() != 0)
+                .map(|entry| {
+                    Arange {
+                        start: entry.address(),
+                        end: entry.address().saturating_add(entry.length()),
+                        debug_info_offset,
+                    }
+                }));
+        }
+
+        aranges.sort_by_key(|arange| arange.start);
+        let max_ends = aranges.iter()
+            .scan(0, |max_end, arange: &Arange| {
+                *max_end = cmp::max(*max_end, arange.end);
+                Some(*max_end)
+            })
+            .collect();
+
+        Ok(ArangeTable {
+            aranges,
+            max_ends,
+        })
+    }
+
+    /// Find the offset in the `.debug_info` section of the header of the
+    /// compilation unit that covers the given address, if any.
+    pub fn find(&self, address: u64) -> Option<u64> {
+        let candidates = self.aranges.partition_point(|arange| arange.start <= address);
+        (0..candidates)
+            .rev()
+            .take_while(|&index| self.max_ends[index] > address)
+            .map(|index| &self.aranges[index])
+            .find(|arange| address < arange.end)
+            .map(|arange| arange.debug_info_offset)
+    }
+}
+
+#[cfg(test)]
+const TEST_DEBUG_ARANGES: [u8; 88] = [
+    // A 32-bit set with 4 byte addresses.
+    0x24, 0x00, 0x00, 0x00,                         // 32-bit unit length
+    0x02, 0x00,                                     // version 2
+    0x10, 0x00, 0x00, 0x00,                         // debug_info_offset
+    0x04,                                           // address size
+    0x00,                                           // segment selector size
+    0x00, 0x00, 0x00, 0x00,                         // padding
+    0x00, 0x10, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // 0x1000, length 0x20
+    0x00, 0x30, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, // 0x3000, length 0x10
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // terminator
+
+    // A 64-bit set with 8 byte addresses.
+    0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+    0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // unit length
+    0x02, 0x00,                                     // version 2
+    0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // debug_info_offset
+    0x08,                                           // address size
+    0x00,                                           // segment selector size
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // padding
+    0x20, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // 0x1020
+    0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // length 0x2000
+];
+
+#[test]
+fn test_parse_arange_sets() {
+    let debug_aranges = DebugAranges::new(&TEST_DEBUG_ARANGES, LittleEndian);
+    let sets = debug_aranges.sets()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the sets");
+    assert_eq!(sets.len(), 2);
+
+    let header = sets[0].header();
+    assert_eq!(header.offset(), 0);
+    assert_eq!(header.unit_length(), 0x24);
+    assert!(!header.is_dwarf64());
+    assert_eq!(header.version(), 2);
+    assert_eq!(header.debug_info_offset(), 0x10);
+    assert_eq!(header.address_size(), 4);
+    assert_eq!(header.segment_selector_size(), 0);
+    assert_eq!(sets[0].entries(),
+               &[ArangeEntry::new(0, 0x1000, 0x20), ArangeEntry::new(0, 0x3000, 0x10)]);
+
+    let header = sets[1].header();
+    assert_eq!(header.offset(), 0x28);
+    assert_eq!(header.unit_length(), 0x24);
+    assert!(header.is_dwarf64());
+    assert_eq!(header.debug_info_offset(), 0x80);
+    assert_eq!(header.address_size(), 8);
+    assert_eq!(sets[1].entries(), &[ArangeEntry::new(0, 0x1020, 0x2000)]);
+}
+
+#[test]
+fn test_parse_arange_set_segments() {
+    let buf = [
+        0x00, 0x00, 0x00, 0x20,                     // 32-bit unit length
+        0x00, 0x02,                                 // version 2
+        0x00, 0x00, 0x00, 0x00,                     // debug_info_offset
+        0x02,                                       // address size
+        0x02,                                       // segment selector size
+        0x00, 0x07, 0x12, 0x34, 0x00, 0x10,         // segment 7, 0x1234, length 0x10
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,         // terminator
+        0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,         // ignored after the terminator
+        0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+    ];
+
+    let mut sets = DebugAranges::new(&buf, BigEndian).sets();
+    let set = sets.next().expect("Should have a set").expect("Should parse the set");
+    assert_eq!(set.header().segment_selector_size(), 2);
+    assert_eq!(set.entries(), &[ArangeEntry::new(7, 0x1234, 0x10)]);
+    assert!(sets.next().is_none());
+}
+
+#[test]
+fn test_parse_arange_set_errors() {
+    let mut buf = TEST_DEBUG_ARANGES;
+    buf[4] = 0x03;
+    match DebugAranges::new(&buf, LittleEndian).sets().next() {
+        Some(Err(Error::UnknownDwarfVersion)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut buf = TEST_DEBUG_ARANGES;
+    buf[10] = 0x03;
+    match DebugAranges::new(&buf, LittleEndian).sets().next() {
+        Some(Err(Error::UnsupportedAddressSize)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut buf = TEST_DEBUG_ARANGES;
+    buf[11] = 0x03;
+    match DebugAranges::new(&buf, LittleEndian).sets().next() {
+        Some(Err(Error::UnsupportedSegmentSize)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut sets = DebugAranges::new(&TEST_DEBUG_ARANGES[..30], LittleEndian).sets();
+    match sets.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(sets.next().is_none());
+}
+
+#[test]
+fn test_arange_table_find() {
+    let debug_aranges = DebugAranges::new(&TEST_DEBUG_ARANGES, LittleEndian);
+    let table = ArangeTable::new(&debug_aranges).expect("Should build the table");
+
+    assert_eq!(table.find(0xfff), None);
+    assert_eq!(table.find(0x1000), Some(0x10));
+    assert_eq!(table.find(0x101f), Some(0x10));
+    // The range that starts closest to the address wins where ranges overlap.
+    assert_eq!(table.find(0x1020), Some(0x80));
+    assert_eq!(table.find(0x3000), Some(0x10));
+    assert_eq!(table.find(0x300f), Some(0x10));
+    assert_eq!(table.find(0x3010), Some(0x80));
+    assert_eq!(table.find(0x3020), None);
+}
@@ -0,0 +1,120 @@
+//! Functions for reading the `.debug_addr` section.
+
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::LittleEndian;
+use parser::{AttributeValue, Error, into_result, parse_address};
+
+/// The `.debug_addr` section, which holds the addresses that split DWARF
+/// units refer to by index.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugAddr<'input, Endian>
+    where Endian: Endianity
+{
+    debug_addr_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugAddr<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugAddr` instance from the data in the `.debug_addr`
+    /// section, which is encoded in the given byte order.
+    pub fn new(debug_addr_section: &'input [u8], endian: Endian) -> DebugAddr<'input, Endian> {
+        DebugAddr {
+            debug_addr_section,
+            endian,
+        }
+    }
+
+    /// Get the address with the given index in the unit's table of addresses,
+    /// which starts at `base`, the offset from the unit's `DW_AT_addr_base` or
+    /// `DW_AT_GNU_addr_base` attribute.
+    pub fn get_address(&self, address_size: u8, base: u64, index: u64) -> Result<u64, Error> {
+        let offset = index.checked_mul(address_size as u64)
+            .and_then(|offset| offset.checked_add(base))
+            .ok_or(Error::OffsetOutOfBounds)?;
+        if offset >= self.debug_addr_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let input = &self.debug_addr_section[offset as usize..];
+        let (_, address) = into_result(parse_address(input, address_size, self.endian))?;
+        Ok(address)
+    }
+
+    /// Get the address that an attribute value refers to: either the address
+    /// itself, or the address at the value's index in the unit's table of
+    /// addresses, which starts at `base`. Returns `None` if the value is not
+    /// an address.
+    pub fn address_value(&self,
+                         value: AttributeValue,
+                         address_size: u8,
+                         base: Option<u64>)
+                         -> Result<Option<u64>, Error> {
+        match value {
+            AttributeValue::Addr(address) => Ok(Some(address)),
+            AttributeValue::DebugAddrIndex(index) => {
+                let base = base.ok_or(Error::MissingAddrBase)?;
+                self.get_address(address_size, base, index).map(Some)
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+#[test]
+fn test_get_address() {
+    let buf = [
+        0xaa, 0xbb, 0xcc, 0xdd,                         // another unit's addresses
+        0x00, 0x10, 0x00, 0x00,
+        0x00, 0x20, 0x00, 0x00,
+        0x00, 0x30,                                     // truncated
+    ];
+    let debug_addr = DebugAddr::new(&buf, LittleEndian);
+
+    assert_eq!(debug_addr.get_address(4, 4, 0).expect("Should get the address"), 0x1000);
+    assert_eq!(debug_addr.get_address(4, 4, 1).expect("Should get the address"), 0x2000);
+    assert_eq!(debug_addr.get_address(4, 0, 2).expect("Should get the address"), 0x2000);
+
+    match debug_addr.get_address(4, 4, 2) {
+        Err(Error::UnexpectedEof) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_addr.get_address(4, 4, 3) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_addr.get_address(8, 0, u64::MAX) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_address_value() {
+    let buf = [
+        0x00, 0x10, 0x00, 0x00,
+        0x00, 0x20, 0x00, 0x00,
+    ];
+    let debug_addr = DebugAddr::new(&buf, LittleEndian);
+
+    let address = debug_addr.address_value(AttributeValue::Addr(0x3000), 4, None)
+        .expect("Should get the address");
+    assert_eq!(address, Some(0x3000));
+
+    let address = debug_addr.address_value(AttributeValue::DebugAddrIndex(1), 4, Some(0))
+        .expect("Should get the address");
+    assert_eq!(address, Some(0x2000));
+
+    let address = debug_addr.address_value(AttributeValue::Data4(0x10), 4, Some(0))
+        .expect("Should not fail");
+    assert_eq!(address, None);
+
+    match debug_addr.address_value(AttributeValue::DebugAddrIndex(1), 4, None) {
+        Err(Error::MissingAddrBase) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
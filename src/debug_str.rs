@@ -0,0 +1,63 @@
+//! Functions for reading the `.debug_str` section.
+
+use parser::Error;
+
+/// The `.debug_str` section, which holds the strings referenced by
+/// `DW_FORM_strp` attribute values.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugStr<'input> {
+    debug_str_section: &'input [u8],
+}
+
+impl<'input> DebugStr<'input> {
+    /// Construct a new `DebugStr` instance from the data in the `.debug_str`
+    /// section.
+    pub fn new(debug_str_section: &'input [u8]) -> DebugStr<'input> {
+        DebugStr { debug_str_section }
+    }
+
+    /// Get the null-terminated string at the given offset from the start of
+    /// the section, without its terminator.
+    ///
+    /// The offset may have come from either a 32- or a 64-bit DWARF unit.
+    /// Returns `Error::OffsetOutOfBounds` if the offset is past the end of the
+    /// section, and `Error::UnterminatedString` if there is no null terminator
+    /// between the offset and the end of the section.
+    pub fn get_str(&self, offset: u64) -> Result<&'input [u8], Error> {
+        if offset >= self.debug_str_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let bytes = &self.debug_str_section[offset as usize..];
+        match bytes.iter().position(|&b| b == 0) {
+            Some(len) => Ok(&bytes[..len]),
+            None => Err(Error::UnterminatedString),
+        }
+    }
+}
+
+#[test]
+fn test_get_str() {
+    let buf = b"\0foo\0bar\0unterminated";
+    let debug_str = DebugStr::new(buf);
+
+    assert_eq!(debug_str.get_str(0).unwrap(), b"");
+    assert_eq!(debug_str.get_str(1).unwrap(), b"foo");
+    assert_eq!(debug_str.get_str(3).unwrap(), b"o");
+    assert_eq!(debug_str.get_str(5).unwrap(), b"bar");
+
+    match debug_str.get_str(9) {
+        Err(Error::UnterminatedString) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_str.get_str(buf.len() as u64) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_str.get_str(0x1_0000_0001) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
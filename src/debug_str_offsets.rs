@@ -0,0 +1,392 @@
+//! Functions for reading the `.debug_str_offsets` section.
+
+use debug_str::DebugStr;
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use nom::{Err, ErrorKind, IResult};
+use parser::{AttributeValue, CompilationUnit, Error, Format, into_result, parse_initial_length,
+             parse_offset, parse_u16, ParseResult, take};
+#[cfg(test)]
+use parser::{CompilationUnitsIter, parse_abbreviations_at};
+#[cfg(test)]
+use types::AttributeName;
+
+/// The `.debug_str_offsets` section, which holds the offsets in the
+/// `.debug_str` section of the strings that `DW_FORM_strx` and
+/// `DW_FORM_GNU_str_index` attribute values refer to by index.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugStrOffsets<'input, Endian>
+    where Endian: Endianity
+{
+    debug_str_offsets_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugStrOffsets<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugStrOffsets` instance from the data in the
+    /// `.debug_str_offsets` section, which is encoded in the given byte order.
+    pub fn new(debug_str_offsets_section: &'input [u8],
+               endian: Endian)
+               -> DebugStrOffsets<'input, Endian> {
+        DebugStrOffsets {
+            debug_str_offsets_section,
+            endian,
+        }
+    }
+
+    /// Parse the DWARF 5 contribution whose header is at the given offset.
+    ///
+    /// The section of a GNU split DWARF object file has no headers, and holds
+    /// a single table of offsets starting at offset 0 instead.
+    pub fn contribution(&self,
+                        offset: u64)
+                        -> Result<StrOffsetsContribution<'input, Endian>, Error> {
+        if offset >= self.debug_str_offsets_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let input = &self.debug_str_offsets_section[offset as usize..];
+        let (_, contribution) = into_result(parse_contribution(input, offset, self.endian))?;
+        Ok(contribution)
+    }
+
+    /// Get the offset in the `.debug_str` section of the string with the given
+    /// index in the unit's table of offsets, which starts at `base`, the value
+    /// from `CompilationUnit::str_offsets_base`.
+    ///
+    /// For DWARF 5 units, the table is the entries of the contribution whose
+    /// header comes just before `base`, and the index must be within it.
+    pub fn get_str_offset(&self,
+                          unit: &CompilationUnit<Endian>,
+                          base: u64,
+                          index: u64)
+                          -> Result<u64, Error> {
+        let format = if unit.is_dwarf64() {
+            Format::Dwarf64
+        } else {
+            Format::Dwarf32
+        };
+        if unit.header().version() >= 5 {
+            let header_size = format.initial_length_size() as u64 + 4;
+            let offset = base.checked_sub(header_size).ok_or(Error::OffsetOutOfBounds)?;
+            return self.contribution(offset)?.get(index);
+        }
+
+        let offset = index.checked_mul(format.offset_size() as u64)
+            .and_then(|offset| offset.checked_add(base))
+            .ok_or(Error::OffsetOutOfBounds)?;
+        if offset >= self.debug_str_offsets_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let input = &self.debug_str_offsets_section[offset as usize..];
+        let (_, str_offset) = into_result(parse_offset(input, format, self.endian))?;
+        Ok(str_offset)
+    }
+
+    /// Get the null-terminated string with the given index in the unit's table
+    /// of offsets, which starts at `base`, without its terminator.
+    pub fn get_str(&self,
+                   unit: &CompilationUnit<Endian>,
+                   base: u64,
+                   index: u64,
+                   debug_str: &DebugStr<'input>)
+                   -> Result<&'input [u8], Error> {
+        debug_str.get_str(self.get_str_offset(unit, base, index)?)
+    }
+
+    /// Get the string of a string-class attribute value of the given unit,
+    /// without its null terminator, looking up `DW_FORM_strx*` and
+    /// `DW_FORM_GNU_str_index` values through this section and `DW_FORM_strp`
+    /// values in the given `.debug_str` section.
+    ///
+    /// Returns `Ok(None)` if the value is not a string, or if it is a string
+    /// that lives in a section other than `.debug_str`, and an error if the
+    /// string can not be read from these sections.
+    pub fn string_value(&self,
+                        value: AttributeValue<'input>,
+                        unit: &CompilationUnit<Endian>,
+                        base: u64,
+                        debug_str: &DebugStr<'input>)
+                        -> Result<Option<&'input [u8]>, Error> {
+        match value {
+            AttributeValue::DebugStrOffsetsIndex(index) => {
+                self.get_str(unit, base, index, debug_str).map(Some)
+            },
+            _ => value.string_value(debug_str),
+        }
+    }
+}
+
+/// A unit's contribution to the `.debug_str_offsets` section: a header and a
+/// table of offsets in the `.debug_str` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrOffsetsContribution<'input, Endian>
+    where Endian: Endianity
+{
+    offset: u64,
+    unit_length: u64,
+    format: Format,
+    version: u16,
+    entries: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> StrOffsetsContribution<'input, Endian>
+    where Endian: Endianity
+{
+    /// Get the offset of this contribution's header from the start of the
+    /// `.debug_str_offsets` section.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the length of this contribution, not including the length field
+    /// itself.
+    pub fn unit_length(&self) -> u64 {
+        self.unit_length
+    }
+
+    /// Return true if this contribution's offsets are 64 bits, false if they
+    /// are 32 bits.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Get the version of this contribution's encoding.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get the offset of this contribution's first entry from the start of the
+    /// `.debug_str_offsets` section, which is what the `DW_AT_str_offsets_base`
+    /// attributes of the units that use it refer to.
+    pub fn entries_offset(&self) -> u64 {
+        self.offset + self.format.initial_length_size() as u64 + 4
+    }
+
+    /// Get the number of entries in this contribution.
+    pub fn len(&self) -> u64 {
+        (self.entries.len() / self.format.offset_size() as usize) as u64
+    }
+
+    /// Return true if this contribution has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the offset in the `.debug_str` section of the entry with the given
+    /// index.
+    pub fn get(&self, index: u64) -> Result<u64, Error> {
+        if index >= self.len() {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let offset_size = self.format.offset_size() as usize;
+        let input = &self.entries[index as usize * offset_size..];
+        let (_, str_offset) = into_result(parse_offset(input, self.format, self.endian))?;
+        Ok(str_offset)
+    }
+}
+
+/// Parse a unit's contribution to the `.debug_str_offsets` section, whose
+/// header is at the given offset.
+fn parse_contribution<'input, Endian>
+    (input: &'input [u8],
+     offset: u64,
+     endian: Endian)
+     -> ParseResult<&'input [u8], StrOffsetsContribution<'input, Endian>>
+    where Endian: Endianity
+{
+    let (rest, (unit_length, format)) = try_parse!(input, call!(parse_initial_length, endian));
+    let (rest, data) = try_parse!(rest, call!(take, unit_length));
+
+    let (data, version) = try_parse!(data, call!(parse_u16, endian));
+    if version != 5 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+
+    // The version is followed by two bytes of padding.
+    let (entries, _) = try_parse!(data, call!(parse_u16, endian));
+
+    IResult::Done(rest,
+                  StrOffsetsContribution {
+                      offset,
+                      unit_length,
+                      format,
+                      version,
+                      entries,
+                      endian,
+                  })
+}
+
+#[test]
+fn test_contribution_32() {
+    let buf = [
+        0xaa, 0xbb,                                     // another contribution
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x00, 0x00,                                     // padding
+        0x01, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+        0xcc,                                           // the next contribution
+    ];
+    let debug_str_offsets = DebugStrOffsets::new(&buf, LittleEndian);
+    let contribution = debug_str_offsets.contribution(2).expect("Should parse the contribution");
+
+    assert_eq!(contribution.offset(), 2);
+    assert_eq!(contribution.unit_length(), 0x0c);
+    assert!(!contribution.is_dwarf64());
+    assert_eq!(contribution.version(), 5);
+    assert_eq!(contribution.entries_offset(), 10);
+    assert_eq!(contribution.len(), 2);
+    assert!(!contribution.is_empty());
+    assert_eq!(contribution.get(0).expect("Should get the entry"), 1);
+    assert_eq!(contribution.get(1).expect("Should get the entry"), 5);
+
+    match contribution.get(2) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_str_offsets.contribution(buf.len() as u64) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match debug_str_offsets.contribution(0) {
+        Err(Error::UnexpectedEof) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_contribution_64() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // 64-bit unit length
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c,
+        0x00, 0x05,                                     // version 5
+        0x00, 0x00,                                     // padding
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
+    ];
+    let debug_str_offsets = DebugStrOffsets::new(&buf, BigEndian);
+    let contribution = debug_str_offsets.contribution(0).expect("Should parse the contribution");
+
+    assert!(contribution.is_dwarf64());
+    assert_eq!(contribution.unit_length(), 0x0c);
+    assert_eq!(contribution.entries_offset(), 16);
+    assert_eq!(contribution.len(), 1);
+    assert_eq!(contribution.get(0).expect("Should get the entry"), 0x1_0000_0000);
+}
+
+#[test]
+fn test_contribution_unknown_version() {
+    let buf = [
+        0x04, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00,                                     // padding
+    ];
+    match DebugStrOffsets::new(&buf, LittleEndian).contribution(0) {
+        Err(Error::UnknownDwarfVersion) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_string_value() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x00,                               // code 1, DW_TAG_compile_unit
+        0x03, 0x25,                                     // DW_AT_name, DW_FORM_strx1
+        0x72, 0x17,                                     // DW_AT_str_offsets_base,
+                                                        // DW_FORM_sec_offset
+        0x00, 0x00,
+        0x00,
+    ];
+    let debug_info = [
+        0x0e, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x01,                                           // DW_UT_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x01,                                           // abbreviation code
+        0x01,                                           // DW_AT_name
+        0x08, 0x00, 0x00, 0x00,                         // DW_AT_str_offsets_base
+    ];
+    let debug_str_offsets = [
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x00, 0x00,                                     // padding
+        0x01, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+        0x08, 0x00, 0x00, 0x00,                         // the next contribution
+        0x05, 0x00,
+        0x00, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+    ];
+    let debug_str = DebugStr::new(b"\0foo\0bar\0");
+    let debug_str_offsets = DebugStrOffsets::new(&debug_str_offsets, LittleEndian);
+
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let base = unit.str_offsets_base(&abbrevs).expect("Should read the base");
+    assert_eq!(base, 8);
+
+    let mut entries = unit.entries(&abbrevs);
+    entries.next_entry().expect("Should parse the entry");
+    let name = entries.current()
+        .and_then(|entry| entry.attr_value(AttributeName::Name))
+        .expect("Should have a name");
+    assert_eq!(name, AttributeValue::DebugStrOffsetsIndex(1));
+    let string_value = |value| {
+        debug_str_offsets.string_value(value, &unit, base, &debug_str)
+            .expect("Should get the string value")
+    };
+    assert_eq!(string_value(name), Some(&b"bar"[..]));
+    assert_eq!(string_value(AttributeValue::DebugStrOffsetsIndex(0)), Some(&b"foo"[..]));
+    assert_eq!(string_value(AttributeValue::DebugStrRef(5)), Some(&b"bar"[..]));
+    assert_eq!(string_value(AttributeValue::Udata(1)), None);
+
+    // The index is past the end of the unit's contribution, even though the
+    // section goes on.
+    match debug_str_offsets.string_value(AttributeValue::DebugStrOffsetsIndex(2),
+                                         &unit,
+                                         base,
+                                         &debug_str) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    match debug_str_offsets.get_str_offset(&unit, base, u64::MAX) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    // There is no contribution header before the base.
+    match debug_str_offsets.get_str_offset(&unit, 0, 3) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    match debug_str_offsets.get_str(&unit, base, 0, &DebugStr::new(b"\0foo")) {
+        Err(Error::UnterminatedString) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    // A GNU split DWARF unit's table has no header, and starts at offset 0.
+    let debug_info_v4 = [
+        0x07, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+    ];
+    let unit = CompilationUnitsIter::new(&debug_info_v4, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    assert_eq!(debug_str_offsets.get_str_offset(&unit, 0, 3).expect("Should get the offset"),
+               5);
+}
@@ -0,0 +1,138 @@
+//! Types for reading fixed size integers in either byte order.
+//!
+//! The DWARF sections of an object file are encoded in the target's byte
+//! order. Parsing is generic over an `Endianity`: use `LittleEndian` or
+//! `BigEndian` when the byte order is known at compile time, and
+//! `RunTimeEndian` when it is only known once the object file has been read.
+
+use std::fmt::Debug;
+
+/// A trait describing the byte order that fixed size integers are read in.
+pub trait Endianity: Debug + Default + Clone + Copy + PartialEq + Eq {
+    /// Return true if this is big endian byte order, false otherwise.
+    fn is_big_endian(self) -> bool;
+
+    /// Return true if this is little endian byte order, false otherwise.
+    fn is_little_endian(self) -> bool {
+        !self.is_big_endian()
+    }
+
+    /// Read an unsigned 16 bit integer from the front of the given buffer.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the buffer is shorter than 2 bytes.
+    fn read_u16(self, buf: &[u8]) -> u16 {
+        let bytes = [buf[0], buf[1]];
+        if self.is_big_endian() {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    /// Read an unsigned 32 bit integer from the front of the given buffer.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the buffer is shorter than 4 bytes.
+    fn read_u32(self, buf: &[u8]) -> u32 {
+        let bytes = [buf[0], buf[1], buf[2], buf[3]];
+        if self.is_big_endian() {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    /// Read an unsigned 64 bit integer from the front of the given buffer.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the buffer is shorter than 8 bytes.
+    fn read_u64(self, buf: &[u8]) -> u64 {
+        let bytes = [buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7]];
+        if self.is_big_endian() {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        }
+    }
+}
+
+/// Little endian byte order, known at compile time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LittleEndian;
+
+impl Endianity for LittleEndian {
+    #[inline]
+    fn is_big_endian(self) -> bool {
+        false
+    }
+}
+
+/// Big endian byte order, known at compile time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BigEndian;
+
+impl Endianity for BigEndian {
+    #[inline]
+    fn is_big_endian(self) -> bool {
+        true
+    }
+}
+
+/// The native byte order of the host.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+/// The native byte order of the host.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+/// A byte order that is chosen at run time, for example from the header of
+/// the object file being debugged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RunTimeEndian {
+    /// Little endian byte order.
+    Little,
+    /// Big endian byte order.
+    Big,
+}
+
+impl Default for RunTimeEndian {
+    #[cfg(target_endian = "little")]
+    fn default() -> RunTimeEndian {
+        RunTimeEndian::Little
+    }
+
+    #[cfg(target_endian = "big")]
+    fn default() -> RunTimeEndian {
+        RunTimeEndian::Big
+    }
+}
+
+impl Endianity for RunTimeEndian {
+    #[inline]
+    fn is_big_endian(self) -> bool {
+        self == RunTimeEndian::Big
+    }
+}
+
+#[test]
+fn test_read_little_endian() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    assert_eq!(LittleEndian.read_u16(&buf), 0x0201);
+    assert_eq!(LittleEndian.read_u32(&buf), 0x04030201);
+    assert_eq!(LittleEndian.read_u64(&buf), 0x0807060504030201);
+    assert_eq!(RunTimeEndian::Little.read_u32(&buf), 0x04030201);
+}
+
+#[test]
+fn test_read_big_endian() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    assert_eq!(BigEndian.read_u16(&buf), 0x0102);
+    assert_eq!(BigEndian.read_u32(&buf), 0x01020304);
+    assert_eq!(BigEndian.read_u64(&buf), 0x0102030405060708);
+    assert_eq!(RunTimeEndian::Big.read_u32(&buf), 0x01020304);
+}
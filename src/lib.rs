@@ -1,7 +1,6 @@
 //! A parser for the DWARF debugging information format.
 //!
-//! This library targets the fourth edition of the standard (the most recent, at
-//! time of writing).
+//! This library targets versions 2 through 5 of the standard.
 //!
 //! TODO FITZGEN: example usage here!
 
@@ -10,6 +9,36 @@
 extern crate leb128;
 #[macro_use] extern crate nom;
 
+mod endianity;
+pub use endianity::*;
+
+mod aranges;
+pub use aranges::*;
+
+mod debug_addr;
+pub use debug_addr::*;
+
+mod debug_str;
+pub use debug_str::*;
+
+mod debug_str_offsets;
+pub use debug_str_offsets::*;
+
+mod line;
+pub use line::*;
+
+mod loc;
+pub use loc::*;
+
+mod macros;
+pub use macros::*;
+
+mod pubnames;
+pub use pubnames::*;
+
+mod ranges;
+pub use ranges::*;
+
 pub mod parser;
 mod types;
 pub use types::*;
@@ -0,0 +1,2213 @@
+//! Functions for parsing the line number information in the `.debug_line`
+//! section.
+
+use debug_addr::DebugAddr;
+use debug_str::DebugStr;
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::LittleEndian;
+use nom::{Err, ErrorKind, IResult};
+use parser::{AbbreviationsCache, AttributeValue, CompilationUnit, CompilationUnitsIter, Error,
+             Format, into_result, parse_address, parse_attribute_form, parse_attribute_value,
+             parse_initial_length, parse_null_terminated_string, parse_offset, parse_signed_leb,
+             parse_u16, parse_u8, parse_unsigned_leb, ParseResult, take};
+#[cfg(test)]
+use parser::parse_abbreviations_at;
+use ranges::DebugRanges;
+use std::cmp;
+use std::collections::HashMap;
+use std::mem;
+use types::{AbbreviationTag, Abbreviations, AttributeForm, AttributeName, ExtendedOpcode,
+            LineNumberContentType, StandardOpcode};
+
+/// The `.debug_line` section, which holds a line number program for each
+/// compilation unit that has a `DW_AT_stmt_list` attribute.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugLine<'input, Endian>
+    where Endian: Endianity
+{
+    debug_line_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugLine<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugLine` instance from the data in the `.debug_line`
+    /// section, which is encoded in the given byte order.
+    pub fn new(debug_line_section: &'input [u8], endian: Endian) -> DebugLine<'input, Endian> {
+        DebugLine {
+            debug_line_section,
+            endian,
+        }
+    }
+
+    /// Parse the header of the line number program at the given offset, which
+    /// is usually the value of a compilation unit's `DW_AT_stmt_list`
+    /// attribute.
+    ///
+    /// The `address_size` is the size of addresses in that compilation unit.
+    /// DWARF 5 line number program headers record their own address size,
+    /// which is used instead.
+    pub fn header(&self,
+                  offset: u64,
+                  address_size: u8)
+                  -> Result<LineNumberProgramHeader<'input, Endian>, Error> {
+        if offset >= self.debug_line_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let input = &self.debug_line_section[offset as usize..];
+        let (_, header) = into_result(parse_line_number_program_header(input,
+                                                                       offset,
+                                                                       address_size,
+                                                                       self.endian))?;
+        Ok(header)
+    }
+
+    /// Parse the header of the given compilation unit's line number program,
+    /// which its root entry's `DW_AT_stmt_list` attribute refers to.
+    ///
+    /// Returns `Ok(None)` if the unit does not have line number information.
+    pub fn unit_header(&self,
+                       unit: &CompilationUnit<'input, Endian>,
+                       abbrevs: &Abbreviations)
+                       -> Result<Option<LineNumberProgramHeader<'input, Endian>>, Error> {
+        let mut entries = unit.entries(abbrevs);
+        entries.next_entry()?;
+        let stmt_list = entries.current()
+            .and_then(|entry| entry.attr_value(AttributeName::StmtList));
+        let offset = match stmt_list {
+            Some(AttributeValue::SecOffset(offset)) => offset,
+            Some(value) => match value.udata_value() {
+                Some(offset) => offset,
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        self.header(offset, unit.header().address_size()).map(Some)
+    }
+}
+
+/// The format of one of the fields in the entries of a DWARF 5 line number
+/// program header's directory or file name table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEntryFormat {
+    content_type: LineNumberContentType,
+    form: AttributeForm,
+}
+
+impl FileEntryFormat {
+    /// Construct a new `FileEntryFormat`.
+    pub fn new(content_type: LineNumberContentType, form: AttributeForm) -> FileEntryFormat {
+        FileEntryFormat {
+            content_type,
+            form,
+        }
+    }
+
+    /// Get the `DW_LNCT_*` content type of this field.
+    pub fn content_type(&self) -> LineNumberContentType {
+        self.content_type
+    }
+
+    /// Get the form that this field is encoded with.
+    pub fn form(&self) -> AttributeForm {
+        self.form
+    }
+}
+
+/// An entry in a line number program header's file name table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEntry<'input> {
+    path_name: AttributeValue<'input>,
+    directory_index: u64,
+    timestamp: u64,
+    size: u64,
+    md5: Option<[u8; 16]>,
+}
+
+impl<'input> FileEntry<'input> {
+    /// Construct a new `FileEntry`.
+    pub fn new(path_name: AttributeValue<'input>,
+               directory_index: u64,
+               timestamp: u64,
+               size: u64,
+               md5: Option<[u8; 16]>)
+               -> FileEntry<'input> {
+        FileEntry {
+            path_name,
+            directory_index,
+            timestamp,
+            size,
+            md5,
+        }
+    }
+
+    /// Get the file's path name. This is a string-class attribute value, which
+    /// is `AttributeValue::String` before DWARF 5, and may refer to the
+    /// `.debug_str` or `.debug_line_str` sections in DWARF 5.
+    pub fn path_name(&self) -> AttributeValue<'input> {
+        self.path_name
+    }
+
+    /// Get the index of the file's directory in the header's include
+    /// directories, as used by `LineNumberProgramHeader::directory`.
+    pub fn directory_index(&self) -> u64 {
+        self.directory_index
+    }
+
+    /// Get the file's implementation defined modification timestamp, or 0 if
+    /// it is not available.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Get the file's size in bytes, or 0 if it is not available.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get the file's MD5 checksum, if the header has one.
+    pub fn md5(&self) -> Option<&[u8; 16]> {
+        self.md5.as_ref()
+    }
+}
+
+/// The header of a line number program in the `.debug_line` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineNumberProgramHeader<'input, Endian>
+    where Endian: Endianity
+{
+    offset: u64,
+    unit_length: u64,
+    format: Format,
+    version: u16,
+    address_size: u8,
+    segment_selector_size: u8,
+    header_length: u64,
+    minimum_instruction_length: u8,
+    maximum_operations_per_instruction: u8,
+    default_is_stmt: bool,
+    line_base: i8,
+    line_range: u8,
+    opcode_base: u8,
+    standard_opcode_lengths: &'input [u8],
+    directory_entry_format: Vec<FileEntryFormat>,
+    include_directories: Vec<AttributeValue<'input>>,
+    file_name_entry_format: Vec<FileEntryFormat>,
+    file_names: Vec<FileEntry<'input>>,
+    program: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> LineNumberProgramHeader<'input, Endian>
+    where Endian: Endianity
+{
+    /// Get the offset of this header from the start of the `.debug_line`
+    /// section.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the length of this line number program, not including its initial
+    /// length field.
+    pub fn unit_length(&self) -> u64 {
+        self.unit_length
+    }
+
+    /// Return true if this line number program uses the 64-bit DWARF format,
+    /// false if it uses the 32-bit format.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Get the version of this line number program's encoding, which is
+    /// independent of the DWARF version of its compilation unit.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get the size of addresses (in bytes) in this line number program.
+    pub fn address_size(&self) -> u8 {
+        self.address_size
+    }
+
+    /// Get the size of segment selectors (in bytes) in this line number
+    /// program. This is always 0 before DWARF 5.
+    pub fn segment_selector_size(&self) -> u8 {
+        self.segment_selector_size
+    }
+
+    /// Get the number of bytes from the end of the `header_length` field to
+    /// the first opcode of the line number program.
+    pub fn header_length(&self) -> u64 {
+        self.header_length
+    }
+
+    /// Get the size (in bytes) of the smallest target machine instruction.
+    /// Address advancing opcodes are multiplied by this value.
+    pub fn minimum_instruction_length(&self) -> u8 {
+        self.minimum_instruction_length
+    }
+
+    /// Get the maximum number of operations in a VLIW instruction. This is
+    /// always 1 for non-VLIW architectures, and before DWARF 4.
+    pub fn maximum_operations_per_instruction(&self) -> u8 {
+        self.maximum_operations_per_instruction
+    }
+
+    /// Get the initial value of the `is_stmt` register.
+    pub fn default_is_stmt(&self) -> bool {
+        self.default_is_stmt
+    }
+
+    /// Get the smallest line advance that a special opcode can encode.
+    pub fn line_base(&self) -> i8 {
+        self.line_base
+    }
+
+    /// Get the number of distinct line advances that special opcodes can
+    /// encode.
+    pub fn line_range(&self) -> u8 {
+        self.line_range
+    }
+
+    /// Get the number assigned to the first special opcode.
+    pub fn opcode_base(&self) -> u8 {
+        self.opcode_base
+    }
+
+    /// Get the number of LEB128 operands of each standard opcode. The first
+    /// element is for opcode 1, since opcode 0 introduces extended opcodes.
+    pub fn standard_opcode_lengths(&self) -> &'input [u8] {
+        self.standard_opcode_lengths
+    }
+
+    /// Get the format of the entries in the include directories table. This
+    /// is empty before DWARF 5.
+    pub fn directory_entry_format(&self) -> &[FileEntryFormat] {
+        &self.directory_entry_format[..]
+    }
+
+    /// Get the include directories table, as string-class attribute values.
+    ///
+    /// Before DWARF 5, the compilation directory is implicitly directory 0 and
+    /// is not in this table. From DWARF 5, the compilation directory is the
+    /// first entry.
+    pub fn include_directories(&self) -> &[AttributeValue<'input>] {
+        &self.include_directories[..]
+    }
+
+    /// Get the include directory with the given index, as used by
+    /// `FileEntry::directory_index`.
+    ///
+    /// Returns `None` for directory 0 before DWARF 5, since that is the
+    /// compilation directory from the unit's `DW_AT_comp_dir` attribute.
+    pub fn directory(&self, index: u64) -> Option<AttributeValue<'input>> {
+        let index = if self.version < 5 {
+            index.checked_sub(1)?
+        } else {
+            index
+        };
+        self.include_directories.get(index as usize).cloned()
+    }
+
+    /// Get the format of the entries in the file name table. This is empty
+    /// before DWARF 5.
+    pub fn file_name_entry_format(&self) -> &[FileEntryFormat] {
+        &self.file_name_entry_format[..]
+    }
+
+    /// Get the file name table.
+    pub fn file_names(&self) -> &[FileEntry<'input>] {
+        &self.file_names[..]
+    }
+
+    /// Get the file with the given index, as used by the line number
+    /// program's `file` register.
+    ///
+    /// Files are numbered from 1 before DWARF 5, and from 0 since.
+    pub fn file(&self, index: u64) -> Option<&FileEntry<'input>> {
+        let index = if self.version < 5 {
+            index.checked_sub(1)?
+        } else {
+            index
+        };
+        self.file_names.get(index as usize)
+    }
+
+    /// Run the line number program, iterating over the rows of its table.
+    pub fn rows(&self) -> LineNumberRows<'_, 'input, Endian> {
+        LineNumberRows::new(self)
+    }
+}
+
+/// Parse the directory or file name entry format of a DWARF 5 header.
+fn parse_file_entry_formats(input: &[u8]) -> ParseResult<&[u8], Vec<FileEntryFormat>> {
+    let (mut rest, count) = try_parse!(input, parse_u8);
+    let mut formats = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (rest1, content_type) = try_parse!(rest, parse_unsigned_leb);
+        let (rest1, form) = try_parse!(rest1, parse_attribute_form);
+        formats.push(FileEntryFormat::new(LineNumberContentType(content_type), form));
+        rest = rest1;
+    }
+    IResult::Done(rest, formats)
+}
+
+/// Parse the entries of a DWARF 5 directory or file name table, described by
+/// the given formats.
+fn parse_file_entries<'input, Endian>(input: &'input [u8],
+                                      formats: &[FileEntryFormat],
+                                      address_size: u8,
+                                      format: Format,
+                                      endian: Endian)
+                                      -> ParseResult<&'input [u8], Vec<FileEntry<'input>>>
+    where Endian: Endianity
+{
+    let (mut rest, count) = try_parse!(input, parse_unsigned_leb);
+    if count != 0 && !formats.iter().any(|f| f.content_type() == LineNumberContentType::Path) {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::MissingFileEntryFormatPath),
+                                            input));
+    }
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let entry_input = rest;
+        let mut entry = FileEntry::new(AttributeValue::String(&[]), 0, 0, 0, None);
+        for f in formats {
+            let (rest1, value) = try_parse!(rest,
+                                            call!(parse_attribute_value,
+                                                  f.form(),
+                                                  5,
+                                                  address_size,
+                                                  format,
+                                                  endian));
+            rest = rest1;
+
+            match (f.content_type(), value) {
+                (LineNumberContentType::Path, _) => entry.path_name = value,
+                (LineNumberContentType::DirectoryIndex, _) =>
+                    entry.directory_index = value.udata_value().unwrap_or(0),
+                (LineNumberContentType::Timestamp, _) =>
+                    entry.timestamp = value.udata_value().unwrap_or(0),
+                (LineNumberContentType::Size, _) =>
+                    entry.size = value.udata_value().unwrap_or(0),
+                (LineNumberContentType::Md5, AttributeValue::Data16(data)) => {
+                    let mut md5 = [0; 16];
+                    md5.copy_from_slice(data);
+                    entry.md5 = Some(md5);
+                },
+                // Skip vendor defined content, and content in unexpected forms.
+                _ => {},
+            }
+        }
+        if rest.len() == entry_input.len() {
+            return IResult::Error(Err::Position(ErrorKind::Custom(Error::EmptyFileEntryFormat),
+                                                entry_input));
+        }
+        entries.push(entry);
+    }
+    IResult::Done(rest, entries)
+}
+
+#[test]
+fn test_parse_file_entries_empty_format() {
+    let formats = [FileEntryFormat::new(LineNumberContentType::Path, AttributeForm::FlagPresent)];
+
+    match parse_file_entries(&[0x00, 0x01], &formats, 8, Format::Dwarf32, LittleEndian) {
+        IResult::Done(rest, entries) => {
+            assert_eq!(rest, &[0x01]);
+            assert!(entries.is_empty());
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    // A huge count of entries that consume no input must not run forever.
+    let input = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    match parse_file_entries(&input, &formats, 8, Format::Dwarf32, LittleEndian) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::EmptyFileEntryFormat), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_file_entries_missing_path() {
+    let formats = [FileEntryFormat::new(LineNumberContentType::Size, AttributeForm::Udata)];
+
+    // An empty table does not need any formats.
+    for formats in &[&formats[..], &[]] {
+        match parse_file_entries(&[0x00], formats, 8, Format::Dwarf32, LittleEndian) {
+            IResult::Done(rest, entries) => {
+                assert!(rest.is_empty());
+                assert!(entries.is_empty());
+            },
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+    }
+
+    match parse_file_entries(&[0x01, 0x02], &formats, 8, Format::Dwarf32, LittleEndian) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::MissingFileEntryFormatPath),
+                                     _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+/// Parse the include directories table of a header from before DWARF 5: a
+/// sequence of strings terminated by an empty string.
+fn parse_include_directories<'input>(mut input: &'input [u8])
+                                     -> ParseResult<&'input [u8], Vec<AttributeValue<'input>>> {
+    let mut directories = Vec::new();
+    loop {
+        let (rest, directory) = try_parse!(input, parse_null_terminated_string);
+        input = rest;
+        if directory.is_empty() {
+            return IResult::Done(input, directories);
+        }
+        directories.push(AttributeValue::String(directory));
+    }
+}
+
+/// Parse the file name table of a header from before DWARF 5. It is a sequence
+/// of entries terminated by an entry with an empty path name.
+fn parse_file_names<'input>(mut input: &'input [u8])
+                             -> ParseResult<&'input [u8], Vec<FileEntry<'input>>> {
+    let mut files = Vec::new();
+    loop {
+        let (rest, path_name) = try_parse!(input, parse_null_terminated_string);
+        if path_name.is_empty() {
+            return IResult::Done(rest, files);
+        }
+
+        let (rest, directory_index) = try_parse!(rest, parse_unsigned_leb);
+        let (rest, timestamp) = try_parse!(rest, parse_unsigned_leb);
+        let (rest, size) = try_parse!(rest, parse_unsigned_leb);
+        files.push(FileEntry::new(AttributeValue::String(path_name),
+                                  directory_index,
+                                  timestamp,
+                                  size,
+                                  None));
+        input = rest;
+    }
+}
+
+/// Parse a line number program header, which is at the given offset in the
+/// `.debug_line` section.
+fn parse_line_number_program_header<'input, Endian>
+    (input: &'input [u8],
+     offset: u64,
+     address_size: u8,
+     endian: Endian)
+     -> ParseResult<&'input [u8], LineNumberProgramHeader<'input, Endian>>
+    where Endian: Endianity
+{
+    let (rest, (unit_length, format)) = try_parse!(input, call!(parse_initial_length, endian));
+    let (after_unit, unit) = try_parse!(rest, call!(take, unit_length));
+
+    let (rest, version) = try_parse!(unit, call!(parse_u16, endian));
+    if !(2..=5).contains(&version) {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+    if version == 2 && format == Format::Dwarf64 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnsupportedDwarf64Version),
+                                            input));
+    }
+
+    let (rest, address_size, segment_selector_size) = if version >= 5 {
+        let (rest, address_size) = try_parse!(rest, parse_u8);
+        let (rest, segment_selector_size) = try_parse!(rest, parse_u8);
+        (rest, address_size, segment_selector_size)
+    } else {
+        (rest, address_size, 0)
+    };
+
+    let (rest, header_length) = try_parse!(rest, call!(parse_offset, format, endian));
+    let (program, rest) = try_parse!(rest, call!(take, header_length));
+
+    let (rest, minimum_instruction_length) = try_parse!(rest, parse_u8);
+    let (rest, maximum_operations_per_instruction) = if version >= 4 {
+        try_parse!(rest, parse_u8)
+    } else {
+        (rest, 1)
+    };
+    if maximum_operations_per_instruction == 0 {
+        return IResult::Error(Err::Position(
+            ErrorKind::Custom(Error::MaximumOperationsPerInstructionZero), input));
+    }
+
+    let (rest, default_is_stmt) = try_parse!(rest, parse_u8);
+    let (rest, line_base) = try_parse!(rest, parse_u8);
+    let (rest, line_range) = try_parse!(rest, parse_u8);
+    if line_range == 0 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::LineRangeZero), input));
+    }
+
+    let (rest, opcode_base) = try_parse!(rest, parse_u8);
+    if opcode_base == 0 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::OpcodeBaseZero), input));
+    }
+    let (rest, standard_opcode_lengths) = try_parse!(rest, call!(take, opcode_base as u64 - 1));
+
+    let (directory_entry_format, include_directories, file_name_entry_format, file_names) =
+        if version >= 5 {
+            let (rest, directory_entry_format) = try_parse!(rest, parse_file_entry_formats);
+            let (rest, directories) = try_parse!(rest,
+                                                 call!(parse_file_entries,
+                                                       &directory_entry_format,
+                                                       address_size,
+                                                       format,
+                                                       endian));
+            let (rest, file_name_entry_format) = try_parse!(rest, parse_file_entry_formats);
+            let (_, file_names) = try_parse!(rest,
+                                             call!(parse_file_entries,
+                                                   &file_name_entry_format,
+                                                   address_size,
+                                                   format,
+                                                   endian));
+            let directories = directories.iter().map(|dir| dir.path_name()).collect();
+            (directory_entry_format, directories, file_name_entry_format, file_names)
+        } else {
+            let (rest, directories) = try_parse!(rest, parse_include_directories);
+            let (_, file_names) = try_parse!(rest, parse_file_names);
+            (Vec::new(), directories, Vec::new(), file_names)
+        };
+
+    IResult::Done(after_unit,
+                  LineNumberProgramHeader {
+                      offset,
+                      unit_length,
+                      format,
+                      version,
+                      address_size,
+                      segment_selector_size,
+                      header_length,
+                      minimum_instruction_length,
+                      maximum_operations_per_instruction,
+                      default_is_stmt: default_is_stmt != 0,
+                      line_base: line_base as i8,
+                      line_range,
+                      opcode_base,
+                      standard_opcode_lengths,
+                      directory_entry_format,
+                      include_directories,
+                      file_name_entry_format,
+                      file_names,
+                      program,
+                      endian,
+                  })
+}
+
+#[cfg(test)]
+pub(crate) const TEST_DEBUG_LINE_V4: [u8; 65] = [
+    0x3d, 0x00, 0x00, 0x00,             // 32-bit unit length
+    0x04, 0x00,                         // version 4
+    0x34, 0x00, 0x00, 0x00,             // header length
+    0x01,                               // minimum instruction length
+    0x01,                               // maximum operations per instruction
+    0x01,                               // default is_stmt
+    0xfb,                               // line base
+    0x0e,                               // line range
+    0x0d,                               // opcode base
+    0x00, 0x01, 0x01, 0x01, 0x01, 0x00, // standard opcode lengths
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x01,
+
+    // Include directories.
+    b'/', b'i', b'n', b'c', 0x00,
+    b'/', b'u', b's', b'r', b'/', b'i', b'n', b'c', 0x00,
+    0x00,
+
+    // File names.
+    b'f', b'o', b'o', b'.', b'c', 0x00, 0x00, 0x00, 0x00,
+    b'b', b'a', b'r', b'.', b'h', 0x00, 0x01, 0x12, 0x34,
+    0x00,
+
+    // Program.
+    0x00, 0x01, 0x01,
+];
+
+#[test]
+fn test_parse_debug_line_header_v4() {
+    let debug_line = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian);
+    let header = debug_line.header(0, 4).expect("Should parse the header");
+
+    assert_eq!(header.offset(), 0);
+    assert_eq!(header.unit_length(), 0x3d);
+    assert!(!header.is_dwarf64());
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.address_size(), 4);
+    assert_eq!(header.header_length(), 0x34);
+    assert_eq!(header.minimum_instruction_length(), 1);
+    assert_eq!(header.maximum_operations_per_instruction(), 1);
+    assert!(header.default_is_stmt());
+    assert_eq!(header.line_base(), -5);
+    assert_eq!(header.line_range(), 14);
+    assert_eq!(header.opcode_base(), 13);
+    assert_eq!(header.standard_opcode_lengths(),
+               &[0x00, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01]);
+    assert!(header.directory_entry_format().is_empty());
+    assert!(header.file_name_entry_format().is_empty());
+
+    assert_eq!(header.include_directories(),
+               &[AttributeValue::String(b"/inc"), AttributeValue::String(b"/usr/inc")]);
+    assert_eq!(header.directory(0), None);
+    assert_eq!(header.directory(2), Some(AttributeValue::String(b"/usr/inc")));
+    assert_eq!(header.directory(3), None);
+
+    assert_eq!(header.file_names(),
+               &[FileEntry::new(AttributeValue::String(b"foo.c"), 0, 0, 0, None),
+                 FileEntry::new(AttributeValue::String(b"bar.h"), 1, 0x12, 0x34, None)]);
+    assert_eq!(header.file(0), None);
+    assert_eq!(header.file(1).map(|file| file.path_name()),
+               Some(AttributeValue::String(b"foo.c")));
+    assert_eq!(header.file(2).map(|file| file.directory_index()), Some(1));
+    assert_eq!(header.program, &[0x00, 0x01, 0x01]);
+}
+
+#[test]
+fn test_parse_debug_line_header_v5() {
+    let buf = [
+        0x5b, 0x00, 0x00, 0x00,             // 32-bit unit length
+        0x05, 0x00,                         // version 5
+        0x08,                               // address size
+        0x00,                               // segment selector size
+        0x50, 0x00, 0x00, 0x00,             // header length
+        0x01,                               // minimum instruction length
+        0x01,                               // maximum operations per instruction
+        0x01,                               // default is_stmt
+        0xfb,                               // line base
+        0x0e,                               // line range
+        0x0d,                               // opcode base
+        0x00, 0x01, 0x01, 0x01, 0x01, 0x00, // standard opcode lengths
+        0x00, 0x00, 0x01, 0x00, 0x00, 0x01,
+
+        // Directory entry format: DW_LNCT_path as DW_FORM_line_strp.
+        0x01, 0x01, 0x1f,
+        // Directories.
+        0x02,
+        0x00, 0x00, 0x00, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+
+        // File name entry format: DW_LNCT_path as DW_FORM_string,
+        // DW_LNCT_directory_index as DW_FORM_data1 and DW_LNCT_MD5 as
+        // DW_FORM_data16.
+        0x03, 0x01, 0x08, 0x02, 0x0b, 0x05, 0x1e,
+        // File names.
+        0x02,
+        b'a', b'.', b'c', 0x00,
+        0x00,
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+        0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        b'b', b'.', b'h', 0x00,
+        0x01,
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+        0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+
+        // Program.
+        0x00, 0x01, 0x01,
+    ];
+
+    let debug_line = DebugLine::new(&buf, LittleEndian);
+    let header = debug_line.header(0, 4).expect("Should parse the header");
+
+    assert_eq!(header.version(), 5);
+    assert_eq!(header.address_size(), 8);
+    assert_eq!(header.segment_selector_size(), 0);
+    assert_eq!(header.header_length(), 0x50);
+    assert_eq!(header.opcode_base(), 13);
+
+    assert_eq!(header.directory_entry_format(),
+               &[FileEntryFormat::new(LineNumberContentType::Path, AttributeForm::LineStrp)]);
+    assert_eq!(header.include_directories(),
+               &[AttributeValue::DebugLineStrRef(0), AttributeValue::DebugLineStrRef(5)]);
+    assert_eq!(header.directory(0), Some(AttributeValue::DebugLineStrRef(0)));
+
+    assert_eq!(header.file_name_entry_format(),
+               &[FileEntryFormat::new(LineNumberContentType::Path, AttributeForm::String),
+                 FileEntryFormat::new(LineNumberContentType::DirectoryIndex,
+                                      AttributeForm::Data1),
+                 FileEntryFormat::new(LineNumberContentType::Md5, AttributeForm::Data16)]);
+    assert_eq!(header.file_names().len(), 2);
+
+    let file = header.file(0).expect("Should have file 0");
+    assert_eq!(file.path_name(), AttributeValue::String(b"a.c"));
+    assert_eq!(file.directory_index(), 0);
+    assert_eq!(file.md5(),
+               Some(&[0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                      0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]));
+
+    let file = header.file(1).expect("Should have file 1");
+    assert_eq!(file.path_name(), AttributeValue::String(b"b.h"));
+    assert_eq!(file.directory_index(), 1);
+    assert_eq!(file.md5().map(|md5| md5[15]), Some(0xff));
+
+    assert_eq!(header.program, &[0x00, 0x01, 0x01]);
+}
+
+#[test]
+fn test_parse_debug_line_header_v5_empty_tables() {
+    let buf = [
+        0x15, 0x00, 0x00, 0x00,             // 32-bit unit length
+        0x05, 0x00,                         // version 5
+        0x08,                               // address size
+        0x00,                               // segment selector size
+        0x0a, 0x00, 0x00, 0x00,             // header length
+        0x01,                               // minimum instruction length
+        0x01,                               // maximum operations per instruction
+        0x01,                               // default is_stmt
+        0xfb,                               // line base
+        0x0e,                               // line range
+        0x01,                               // opcode base
+        0x00,                               // directory entry format count
+        0x00,                               // directories count
+        0x00,                               // file name entry format count
+        0x00,                               // file names count
+        0x00, 0x01, 0x01,                   // program
+    ];
+
+    let header = DebugLine::new(&buf, LittleEndian)
+        .header(0, 4)
+        .expect("Should parse the header");
+    assert!(header.include_directories().is_empty());
+    assert!(header.file_names().is_empty());
+    assert_eq!(header.program, &[0x00, 0x01, 0x01]);
+}
+
+#[test]
+fn test_parse_debug_line_header_v3_64() {
+    let buf = [
+        // Some other data before the header.
+        0xaa, 0xbb,
+
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x21, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // unit length
+        0x03, 0x00,                                     // version 3
+        0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // header length
+        0x04,                                           // minimum instruction length
+        0x00,                                           // default is_stmt
+        0xfd,                                           // line base
+        0x0c,                                           // line range
+        0x0a,                                           // opcode base
+        0x00, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, // standard opcode lengths
+        0x01,
+        0x00,                                           // no include directories
+        b'x', b'.', b'c', 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+
+    let debug_line = DebugLine::new(&buf, LittleEndian);
+    let header = debug_line.header(2, 8).expect("Should parse the header");
+
+    assert_eq!(header.offset(), 2);
+    assert!(header.is_dwarf64());
+    assert_eq!(header.version(), 3);
+    assert_eq!(header.address_size(), 8);
+    assert_eq!(header.minimum_instruction_length(), 4);
+    assert_eq!(header.maximum_operations_per_instruction(), 1);
+    assert!(!header.default_is_stmt());
+    assert_eq!(header.line_base(), -3);
+    assert_eq!(header.line_range(), 12);
+    assert_eq!(header.standard_opcode_lengths().len(), 9);
+    assert!(header.include_directories().is_empty());
+    assert_eq!(header.file_names(),
+               &[FileEntry::new(AttributeValue::String(b"x.c"), 0, 0, 0, None)]);
+    assert!(header.program.is_empty());
+}
+
+#[test]
+fn test_parse_debug_line_header_errors() {
+    let debug_line = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian);
+    match debug_line.header(TEST_DEBUG_LINE_V4.len() as u64, 4) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let debug_line = DebugLine::new(&TEST_DEBUG_LINE_V4[..40], LittleEndian);
+    match debug_line.header(0, 4) {
+        Err(Error::UnexpectedEof) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut buf = TEST_DEBUG_LINE_V4;
+    buf[4] = 0x06;
+    match DebugLine::new(&buf, LittleEndian).header(0, 4) {
+        Err(Error::UnknownDwarfVersion) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut buf = TEST_DEBUG_LINE_V4;
+    buf[14] = 0x00;
+    match DebugLine::new(&buf, LittleEndian).header(0, 4) {
+        Err(Error::LineRangeZero) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut buf = TEST_DEBUG_LINE_V4;
+    buf[11] = 0x00;
+    match DebugLine::new(&buf, LittleEndian).header(0, 4) {
+        Err(Error::MaximumOperationsPerInstructionZero) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+/// A row of the line number table, which is the state of the line number
+/// program's registers whenever it appends a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineNumberRow {
+    address: u64,
+    op_index: u64,
+    file: u64,
+    line: u64,
+    column: u64,
+    is_stmt: bool,
+    basic_block: bool,
+    end_sequence: bool,
+    prologue_end: bool,
+    epilogue_begin: bool,
+    isa: u64,
+    discriminator: u64,
+}
+
+impl LineNumberRow {
+    /// The state of the registers at the start of each sequence.
+    fn new(default_is_stmt: bool) -> LineNumberRow {
+        LineNumberRow {
+            address: 0,
+            op_index: 0,
+            file: 1,
+            line: 1,
+            column: 0,
+            is_stmt: default_is_stmt,
+            basic_block: false,
+            end_sequence: false,
+            prologue_end: false,
+            epilogue_begin: false,
+            isa: 0,
+            discriminator: 0,
+        }
+    }
+
+    /// Get the address of the machine instruction for this row.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Get the index of the operation within a VLIW instruction. This is
+    /// always 0 for non-VLIW architectures.
+    pub fn op_index(&self) -> u64 {
+        self.op_index
+    }
+
+    /// Get the index of this row's source file, as used by
+    /// `LineNumberRows::file`.
+    pub fn file(&self) -> u64 {
+        self.file
+    }
+
+    /// Get the source line number, starting from 1. This is 0 if the
+    /// instruction cannot be attributed to any source line.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Get the source column number, starting from 1. This is 0 for the left
+    /// edge of the line.
+    pub fn column(&self) -> u64 {
+        self.column
+    }
+
+    /// Return true if the instruction is a recommended breakpoint location.
+    pub fn is_stmt(&self) -> bool {
+        self.is_stmt
+    }
+
+    /// Return true if the instruction begins a basic block.
+    pub fn basic_block(&self) -> bool {
+        self.basic_block
+    }
+
+    /// Return true if the address is the first byte after the end of a
+    /// sequence of instructions. This row ends its sequence, and the other
+    /// registers are not meaningful.
+    pub fn end_sequence(&self) -> bool {
+        self.end_sequence
+    }
+
+    /// Return true if the address is where a breakpoint should be placed to
+    /// stop at the entry of a function.
+    pub fn prologue_end(&self) -> bool {
+        self.prologue_end
+    }
+
+    /// Return true if the address is where a breakpoint should be placed to
+    /// stop just before exiting a function.
+    pub fn epilogue_begin(&self) -> bool {
+        self.epilogue_begin
+    }
+
+    /// Get the instruction set architecture of the instruction.
+    pub fn isa(&self) -> u64 {
+        self.isa
+    }
+
+    /// Get the discriminator of the block that the instruction belongs to,
+    /// which distinguishes blocks that share a source position.
+    pub fn discriminator(&self) -> u64 {
+        self.discriminator
+    }
+}
+
+/// A decoded line number program opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode<'input> {
+    Special(u8),
+    Copy,
+    AdvancePc(u64),
+    AdvanceLine(i64),
+    SetFile(u64),
+    SetColumn(u64),
+    NegateStmt,
+    SetBasicBlock,
+    ConstAddPc,
+    FixedAdvancePc(u16),
+    SetPrologueEnd,
+    SetEpilogueBegin,
+    SetIsa(u64),
+    UnknownStandard,
+    EndSequence,
+    SetAddress(u64),
+    DefineFile(FileEntry<'input>),
+    SetDiscriminator(u64),
+    UnknownExtended,
+}
+
+/// Parse the operands of an extended opcode, which have already been split
+/// from the rest of the program by the opcode's length.
+fn parse_extended_opcode<'input, Endian>(input: &'input [u8],
+                                         endian: Endian)
+                                         -> ParseResult<&'input [u8], Opcode<'input>>
+    where Endian: Endianity
+{
+    let (operands, opcode) = match input.split_first() {
+        Some((&opcode, operands)) => (operands, ExtendedOpcode(opcode)),
+        None => return IResult::Done(input, Opcode::UnknownExtended),
+    };
+
+    match opcode {
+        ExtendedOpcode::EndSequence => IResult::Done(operands, Opcode::EndSequence),
+        ExtendedOpcode::SetAddress => {
+            // The address takes up all of the operands, so its size is not
+            // necessarily the unit's address size.
+            let address_size = match operands.len() {
+                len @ (1 | 2 | 4 | 8) => len as u8,
+                _ => {
+                    return IResult::Error(Err::Position(
+                        ErrorKind::Custom(Error::UnsupportedAddressSize), operands));
+                }
+            };
+            let (rest, address) = try_parse!(operands,
+                                             call!(parse_address, address_size, endian));
+            IResult::Done(rest, Opcode::SetAddress(address))
+        },
+        ExtendedOpcode::DefineFile => {
+            let (rest, path_name) = try_parse!(operands, parse_null_terminated_string);
+            let (rest, directory_index) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, timestamp) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, size) = try_parse!(rest, parse_unsigned_leb);
+            let file = FileEntry::new(AttributeValue::String(path_name),
+                                      directory_index,
+                                      timestamp,
+                                      size,
+                                      None);
+            IResult::Done(rest, Opcode::DefineFile(file))
+        },
+        ExtendedOpcode::SetDiscriminator => {
+            let (rest, discriminator) = try_parse!(operands, parse_unsigned_leb);
+            IResult::Done(rest, Opcode::SetDiscriminator(discriminator))
+        },
+        _ => IResult::Done(&[], Opcode::UnknownExtended),
+    }
+}
+
+/// Parse a single opcode, along with its operands.
+fn parse_opcode<'input, Endian>(input: &'input [u8],
+                                header: &LineNumberProgramHeader<'input, Endian>)
+                                -> ParseResult<&'input [u8], Opcode<'input>>
+    where Endian: Endianity
+{
+    let (rest, opcode) = try_parse!(input, parse_u8);
+    if opcode >= header.opcode_base {
+        return IResult::Done(rest, Opcode::Special(opcode));
+    }
+
+    if opcode == 0 {
+        let (rest, length) = try_parse!(rest, parse_unsigned_leb);
+        let (rest, operands) = try_parse!(rest, call!(take, length));
+        let (_, opcode) = try_parse!(operands, call!(parse_extended_opcode, header.endian));
+        return IResult::Done(rest, opcode);
+    }
+
+    match StandardOpcode(opcode) {
+        StandardOpcode::Copy => IResult::Done(rest, Opcode::Copy),
+        StandardOpcode::AdvancePc => {
+            let (rest, advance) = try_parse!(rest, parse_unsigned_leb);
+            IResult::Done(rest, Opcode::AdvancePc(advance))
+        },
+        StandardOpcode::AdvanceLine => {
+            let (rest, advance) = try_parse!(rest, parse_signed_leb);
+            IResult::Done(rest, Opcode::AdvanceLine(advance))
+        },
+        StandardOpcode::SetFile => {
+            let (rest, file) = try_parse!(rest, parse_unsigned_leb);
+            IResult::Done(rest, Opcode::SetFile(file))
+        },
+        StandardOpcode::SetColumn => {
+            let (rest, column) = try_parse!(rest, parse_unsigned_leb);
+            IResult::Done(rest, Opcode::SetColumn(column))
+        },
+        StandardOpcode::NegateStmt => IResult::Done(rest, Opcode::NegateStmt),
+        StandardOpcode::SetBasicBlock => IResult::Done(rest, Opcode::SetBasicBlock),
+        StandardOpcode::ConstAddPc => IResult::Done(rest, Opcode::ConstAddPc),
+        StandardOpcode::FixedAdvancePc => {
+            let (rest, advance) = try_parse!(rest, call!(parse_u16, header.endian));
+            IResult::Done(rest, Opcode::FixedAdvancePc(advance))
+        },
+        StandardOpcode::SetPrologueEnd => IResult::Done(rest, Opcode::SetPrologueEnd),
+        StandardOpcode::SetEpilogueBegin => IResult::Done(rest, Opcode::SetEpilogueBegin),
+        StandardOpcode::SetIsa => {
+            let (rest, isa) = try_parse!(rest, parse_unsigned_leb);
+            IResult::Done(rest, Opcode::SetIsa(isa))
+        },
+        _ => {
+            // The header tells us how many LEB128 operands each standard
+            // opcode has, so we can skip the ones that we don't know about.
+            let operands = header.standard_opcode_lengths[opcode as usize - 1];
+            let mut rest = rest;
+            for _ in 0..operands {
+                let (rest1, _) = try_parse!(rest, parse_unsigned_leb);
+                rest = rest1;
+            }
+            IResult::Done(rest, Opcode::UnknownStandard)
+        },
+    }
+}
+
+/// An iterator over the rows of a line number program's table, which runs the
+/// line number state machine.
+///
+/// The line number program may define files that are not in its header, so
+/// use `LineNumberRows::file` rather than `LineNumberProgramHeader::file` to
+/// look up the files of rows.
+#[derive(Debug, Clone)]
+pub struct LineNumberRows<'header, 'input, Endian>
+    where 'input: 'header,
+          Endian: Endianity + 'header
+{
+    header: &'header LineNumberProgramHeader<'input, Endian>,
+    input: &'input [u8],
+    row: LineNumberRow,
+    defined_files: Vec<FileEntry<'input>>,
+}
+
+impl<'header, 'input, Endian> LineNumberRows<'header, 'input, Endian>
+    where Endian: Endianity
+{
+    fn new(header: &'header LineNumberProgramHeader<'input, Endian>)
+           -> LineNumberRows<'header, 'input, Endian> {
+        LineNumberRows {
+            header,
+            input: header.program,
+            row: LineNumberRow::new(header.default_is_stmt),
+            defined_files: Vec::new(),
+        }
+    }
+
+    /// Get the file with the given index, as used by `LineNumberRow::file`.
+    /// This includes the files that the header lists, and any files that the
+    /// line number program has defined so far with `DW_LNE_define_file`.
+    pub fn file(&self, index: u64) -> Option<&FileEntry<'input>> {
+        lookup_file(self.header, &self.defined_files, index)
+    }
+
+    /// Advance the address and op_index registers by the given number of
+    /// operations.
+    fn advance(&mut self, operation_advance: u64) {
+        let minimum_instruction_length = self.header.minimum_instruction_length as u64;
+        let maximum_operations_per_instruction =
+            self.header.maximum_operations_per_instruction as u64;
+
+        if maximum_operations_per_instruction == 1 {
+            let advance = minimum_instruction_length.wrapping_mul(operation_advance);
+            self.row.address = self.row.address.wrapping_add(advance);
+        } else {
+            let op_index = self.row.op_index.wrapping_add(operation_advance);
+            let advance = minimum_instruction_length
+                .wrapping_mul(op_index / maximum_operations_per_instruction);
+            self.row.address = self.row.address.wrapping_add(advance);
+            self.row.op_index = op_index % maximum_operations_per_instruction;
+        }
+    }
+
+    /// Append a row to the table, and reset the registers that only apply to
+    /// a single row.
+    fn append_row(&mut self) -> LineNumberRow {
+        let row = self.row;
+        self.row.basic_block = false;
+        self.row.prologue_end = false;
+        self.row.epilogue_begin = false;
+        self.row.discriminator = 0;
+        row
+    }
+
+    /// Execute an opcode, and return the row that it appends to the table, if
+    /// any.
+    fn execute(&mut self, opcode: Opcode<'input>) -> Option<LineNumberRow> {
+        match opcode {
+            Opcode::Special(opcode) => {
+                let adjusted = opcode - self.header.opcode_base;
+                let line_range = self.header.line_range;
+                let line_advance = self.header.line_base as i64 + (adjusted % line_range) as i64;
+                self.row.line = self.row.line.wrapping_add(line_advance as u64);
+                self.advance((adjusted / line_range) as u64);
+                return Some(self.append_row());
+            },
+            Opcode::Copy => return Some(self.append_row()),
+            Opcode::AdvancePc(advance) => self.advance(advance),
+            Opcode::AdvanceLine(advance) =>
+                self.row.line = self.row.line.wrapping_add(advance as u64),
+            Opcode::SetFile(file) => self.row.file = file,
+            Opcode::SetColumn(column) => self.row.column = column,
+            Opcode::NegateStmt => self.row.is_stmt = !self.row.is_stmt,
+            Opcode::SetBasicBlock => self.row.basic_block = true,
+            Opcode::ConstAddPc => {
+                let adjusted = 255 - self.header.opcode_base;
+                self.advance((adjusted / self.header.line_range) as u64);
+            },
+            Opcode::FixedAdvancePc(advance) => {
+                self.row.address = self.row.address.wrapping_add(advance as u64);
+                self.row.op_index = 0;
+            },
+            Opcode::SetPrologueEnd => self.row.prologue_end = true,
+            Opcode::SetEpilogueBegin => self.row.epilogue_begin = true,
+            Opcode::SetIsa(isa) => self.row.isa = isa,
+            Opcode::EndSequence => {
+                self.row.end_sequence = true;
+                let row = self.row;
+                self.row = LineNumberRow::new(self.header.default_is_stmt);
+                return Some(row);
+            },
+            Opcode::SetAddress(address) => {
+                self.row.address = address;
+                self.row.op_index = 0;
+            },
+            Opcode::DefineFile(file) => self.defined_files.push(file),
+            Opcode::SetDiscriminator(discriminator) => self.row.discriminator = discriminator,
+            Opcode::UnknownStandard | Opcode::UnknownExtended => {},
+        }
+        None
+    }
+
+    fn next_row(&mut self) -> Result<Option<LineNumberRow>, Error> {
+        while !self.input.is_empty() {
+            let (rest, opcode) = into_result(parse_opcode(self.input, self.header))?;
+            self.input = rest;
+            if let Some(row) = self.execute(opcode) {
+                return Ok(Some(row));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'header, 'input, Endian> Iterator for LineNumberRows<'header, 'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<LineNumberRow, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_row() {
+            Ok(row) => row.map(Ok),
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+fn make_test_header<'input>(program: &'input [u8])
+                            -> LineNumberProgramHeader<'input, LittleEndian> {
+    let mut header = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian)
+        .header(0, 4)
+        .expect("Should parse the header");
+    header.program = program;
+    header
+}
+
+#[cfg(test)]
+fn row_registers(row: &LineNumberRow) -> (u64, u64, u64, u64, bool, bool, bool, bool, bool) {
+    (row.address(), row.file(), row.line(), row.column(), row.is_stmt(), row.basic_block(),
+     row.end_sequence(), row.prologue_end(), row.epilogue_begin())
+}
+
+#[test]
+fn test_line_number_rows() {
+    let program = [
+        0x00, 0x05, 0x02, 0x00, 0x10, 0x00, 0x00,   // DW_LNE_set_address 0x1000
+        0x0a,                                       // DW_LNS_set_prologue_end
+        0x01,                                       // DW_LNS_copy
+        0x2f,                                       // special: address += 2, line += 1
+        0x03, 0x7f,                                 // DW_LNS_advance_line -1
+        0x04, 0x02,                                 // DW_LNS_set_file 2
+        0x05, 0x05,                                 // DW_LNS_set_column 5
+        0x06,                                       // DW_LNS_negate_stmt
+        0x07,                                       // DW_LNS_set_basic_block
+        0x0b,                                       // DW_LNS_set_epilogue_begin
+        0x0c, 0x03,                                 // DW_LNS_set_isa 3
+        0x00, 0x02, 0x04, 0x04,                     // DW_LNE_set_discriminator 4
+        0x02, 0x04,                                 // DW_LNS_advance_pc 4
+        0x01,                                       // DW_LNS_copy
+        0x08,                                       // DW_LNS_const_add_pc
+        0x09, 0x00, 0x01,                           // DW_LNS_fixed_advance_pc 0x100
+        0x00, 0x03, 0x80, 0xaa, 0xbb,               // unknown extended opcode
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+        0x00, 0x08, 0x03, b'd', b'.', b'c', 0x00,   // DW_LNE_define_file
+        0x00, 0x00, 0x00,
+        0x04, 0x03,                                 // DW_LNS_set_file 3
+        0x01,                                       // DW_LNS_copy
+    ];
+    let header = make_test_header(&program);
+    let mut rows = header.rows();
+
+    let row = rows.next().expect("Should have a row").expect("Should parse a row");
+    assert_eq!(row_registers(&row), (0x1000, 1, 1, 0, true, false, false, true, false));
+    assert_eq!(rows.file(row.file()).map(|file| file.path_name()),
+               Some(AttributeValue::String(b"foo.c")));
+
+    let row = rows.next().expect("Should have a row").expect("Should parse a row");
+    assert_eq!(row_registers(&row), (0x1002, 1, 2, 0, true, false, false, false, false));
+
+    let row = rows.next().expect("Should have a row").expect("Should parse a row");
+    assert_eq!(row_registers(&row), (0x1006, 2, 1, 5, false, true, false, false, true));
+    assert_eq!(row.isa(), 3);
+    assert_eq!(row.discriminator(), 4);
+    assert_eq!(row.op_index(), 0);
+
+    let row = rows.next().expect("Should have a row").expect("Should parse a row");
+    assert_eq!(row_registers(&row), (0x1117, 2, 1, 5, false, false, true, false, false));
+    assert_eq!(row.isa(), 3);
+    assert_eq!(row.discriminator(), 0);
+
+    let row = rows.next().expect("Should have a row").expect("Should parse a row");
+    assert_eq!(row_registers(&row), (0, 3, 1, 0, true, false, false, false, false));
+    assert_eq!(row.isa(), 0);
+    assert_eq!(rows.file(row.file()).map(|file| file.path_name()),
+               Some(AttributeValue::String(b"d.c")));
+    assert!(rows.file(4).is_none());
+
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn test_line_number_rows_unknown_standard_opcode() {
+    let lengths = [0x00, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x02];
+    let program = [
+        0x0d, 0x81, 0x01, 0x05,                     // unknown opcode with 2 operands
+        0x0e,                                       // special: line += -5
+        0x03, 0x0a,                                 // DW_LNS_advance_line 10
+        0x01,                                       // DW_LNS_copy
+    ];
+    let mut header = make_test_header(&program);
+    header.opcode_base = 14;
+    header.standard_opcode_lengths = &lengths;
+
+    let rows = header.rows()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the rows");
+    assert_eq!(rows.iter().map(|row| row.line()).collect::<Vec<_>>(),
+               vec![1u64.wrapping_sub(5), 6]);
+}
+
+#[test]
+fn test_line_number_rows_vliw() {
+    let program = [
+        0x02, 0x04,                                 // DW_LNS_advance_pc 4
+        0x01,                                       // DW_LNS_copy
+        0x02, 0x02,                                 // DW_LNS_advance_pc 2
+        0x01,                                       // DW_LNS_copy
+    ];
+    let mut header = make_test_header(&program);
+    header.minimum_instruction_length = 4;
+    header.maximum_operations_per_instruction = 3;
+
+    let rows = header.rows()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the rows");
+    assert_eq!(rows.iter().map(|row| (row.address(), row.op_index())).collect::<Vec<_>>(),
+               vec![(4, 1), (8, 0)]);
+}
+
+#[test]
+fn test_line_number_rows_errors() {
+    let program = [0x00, 0x04, 0x02, 0x00, 0x10, 0x00];
+    let header = make_test_header(&program);
+    let mut rows = header.rows();
+    match rows.next() {
+        Some(Err(Error::UnsupportedAddressSize)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(rows.next().is_none());
+
+    // The operands' length must not be truncated to a supported address size.
+    let mut program = vec![0x00, 0x82, 0x02, 0x02];
+    program.extend_from_slice(&[0; 257]);
+    let header = make_test_header(&program);
+    match header.rows().next() {
+        Some(Err(Error::UnsupportedAddressSize)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let program = [0x02];
+    let header = make_test_header(&program);
+    match header.rows().next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+/// Look up a file by index in a line number program's header, or failing that
+/// in the files that the program defined with `DW_LNE_define_file`, which are
+/// numbered after the header's files.
+fn lookup_file<'a, 'input, Endian>(header: &'a LineNumberProgramHeader<'input, Endian>,
+                                   defined_files: &'a [FileEntry<'input>],
+                                   index: u64)
+                                   -> Option<&'a FileEntry<'input>>
+    where Endian: Endianity
+{
+    header.file(index).or_else(|| {
+        let first = if header.version < 5 { 1 } else { 0 };
+        let defined = header.file_names.len() as u64 + first;
+        index.checked_sub(defined).and_then(|index| defined_files.get(index as usize))
+    })
+}
+
+/// A line number program whose rows have been added to a `LineTable`.
+#[derive(Debug, Clone)]
+struct LineProgram<'input, Endian>
+    where Endian: Endianity
+{
+    header: LineNumberProgramHeader<'input, Endian>,
+    defined_files: Vec<FileEntry<'input>>,
+}
+
+/// A sequence of rows with contiguous, increasing addresses, as ended by a
+/// `DW_LNE_end_sequence` opcode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineSequence {
+    program: usize,
+    start: u64,
+    end: u64,
+    rows: Vec<LineNumberRow>,
+}
+
+impl LineSequence {
+    /// Get the address of the first instruction in this sequence.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Get the address of the first byte after the end of this sequence.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Get the rows of this sequence, in order of address. This does not
+    /// include the row that ends the sequence.
+    pub fn rows(&self) -> &[LineNumberRow] {
+        &self.rows[..]
+    }
+}
+
+/// The source location of an address, as found by `LineTable::find`.
+#[derive(Debug, Clone, Copy)]
+pub struct LineLocation<'table, 'input, Endian>
+    where 'input: 'table,
+          Endian: Endianity + 'table
+{
+    header: &'table LineNumberProgramHeader<'input, Endian>,
+    row: &'table LineNumberRow,
+    file: Option<&'table FileEntry<'input>>,
+}
+
+impl<'table, 'input, Endian> LineLocation<'table, 'input, Endian>
+    where Endian: Endianity
+{
+    /// Get the header of the line number program that the row came from,
+    /// which is needed to resolve the file's directory.
+    pub fn header(&self) -> &'table LineNumberProgramHeader<'input, Endian> {
+        self.header
+    }
+
+    /// Get the row that covers the address. Its line and column are the
+    /// address's source position.
+    pub fn row(&self) -> &'table LineNumberRow {
+        self.row
+    }
+
+    /// Get the row's source file, or `None` if the row's file index is not
+    /// valid.
+    pub fn file(&self) -> Option<&'table FileEntry<'input>> {
+        self.file
+    }
+}
+
+/// A table for looking up the source location of addresses, built from the
+/// rows of one or more line number programs.
+///
+/// The rows are grouped into sequences, which are sorted by address so that
+/// lookups are binary searches rather than reruns of the line number programs.
+#[derive(Debug, Clone)]
+pub struct LineTable<'input, Endian>
+    where Endian: Endianity
+{
+    programs: Vec<LineProgram<'input, Endian>>,
+    sequences: Vec<LineSequence>,
+    // The largest end address of each sequence and all of the sequences
+    // before it, which bounds the search for overlapping sequences.
+    max_ends: Vec<u64>,
+}
+
+impl<'input, Endian> LineTable<'input, Endian>
+    where Endian: Endianity
+{
+    /// Build a table from the rows of the given line number programs.
+    pub fn new<I>(headers: I) -> Result<LineTable<'input, Endian>, Error>
+        where I: IntoIterator<Item = LineNumberProgramHeader<'input, Endian>>
+    {
+        let mut programs = Vec::new();
+        let mut sequences = Vec::new();
+        for header in headers {
+            let mut rows = header.rows();
+            let mut sequence_rows = Vec::new();
+            while let Some(row) = rows.next_row()? {
+                if !row.end_sequence() {
+                    sequence_rows.push(row);
+                    continue;
+                }
+
+                let sequence_rows = mem::take(&mut sequence_rows);
+                // Skip empty sequences, such as those left behind for
+                // functions that the linker discarded.
+                match sequence_rows.first().map(|row| row.address()) {
+                    Some(start) if start < row.address() => {
+                        sequences.push(LineSequence {
+                            program: programs.len(),
+                            start,
+                            end: row.address(),
+                            rows: sequence_rows,
+                        });
+                    },
+                    _ => {},
+                }
+            }
+
+            let defined_files = rows.defined_files;
+            programs.push(LineProgram {
+                header,
+                defined_files,
+            });
+        }
+
+        sequences.sort_by_key(|sequence| sequence.start);
+        let max_ends = sequences.iter()
+            .scan(0, |max_end, sequence: &LineSequence| {
+                *max_end = cmp::max(*max_end, sequence.end);
+                Some(*max_end)
+            })
+            .collect();
+
+        Ok(LineTable {
+            programs,
+            sequences,
+            max_ends,
+        })
+    }
+
+    /// Build a table for a single compilation unit, from the line number
+    /// program that its `DW_AT_stmt_list` attribute refers to.
+    ///
+    /// The table is empty if the unit does not have line number information.
+    pub fn for_unit(debug_line: &DebugLine<'input, Endian>,
+                    unit: &CompilationUnit<'input, Endian>,
+                    abbrevs: &Abbreviations)
+                    -> Result<LineTable<'input, Endian>, Error> {
+        LineTable::new(debug_line.unit_header(unit, abbrevs)?)
+    }
+
+    /// Build a table for every compilation unit in the `.debug_info` section,
+    /// which covers the whole binary.
+    pub fn for_debug_info(debug_info: &'input [u8],
+                          debug_abbrev: &'input [u8],
+                          debug_line: &DebugLine<'input, Endian>)
+                          -> Result<LineTable<'input, Endian>, Error> {
+        let mut abbrevs_cache = AbbreviationsCache::new(debug_abbrev);
+        let mut headers = Vec::new();
+        for unit in CompilationUnitsIter::new(debug_info, debug_line.endian) {
+            let unit = unit?;
+            let abbrevs = abbrevs_cache.get_for_unit(unit.header())?;
+            headers.extend(debug_line.unit_header(&unit, &abbrevs)?);
+        }
+        LineTable::new(headers)
+    }
+
+    /// Get the sequences of this table, in order of their start address.
+    pub fn sequences(&self) -> &[LineSequence] {
+        &self.sequences[..]
+    }
+
+    /// Find the source location of the given address.
+    ///
+    /// If several sequences cover the address, the one that starts closest to
+    /// the address is used. Returns `None` if no sequence covers the address.
+    pub fn find(&self, address: u64) -> Option<LineLocation<'_, 'input, Endian>> {
+        let candidates = self.sequences.partition_point(|sequence| sequence.start <= address);
+        for index in (0..candidates).rev() {
+            if self.max_ends[index] <= address {
+                break;
+            }
+
+            let sequence = &self.sequences[index];
+            if address >= sequence.end {
+                continue;
+            }
+
+            let row = sequence.rows.partition_point(|row| row.address() <= address);
+            let row = &sequence.rows[row.checked_sub(1)?];
+            let program = &self.programs[sequence.program];
+            return Some(LineLocation {
+                header: &program.header,
+                row,
+                file: lookup_file(&program.header, &program.defined_files, row.file()),
+            });
+        }
+        None
+    }
+}
+
+#[test]
+fn test_line_table_find() {
+    let first = [
+        0x00, 0x05, 0x02, 0x00, 0x10, 0x00, 0x00,   // DW_LNE_set_address 0x1000
+        0x01,                                       // DW_LNS_copy
+        0x2f,                                       // special: address += 2, line += 1
+        0x2f,                                       // special: address += 2, line += 1
+        0x02, 0x04,                                 // DW_LNS_advance_pc 4
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+        0x00, 0x05, 0x02, 0x00, 0x20, 0x00, 0x00,   // DW_LNE_set_address 0x2000
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+    ];
+    let second = [
+        0x00, 0x05, 0x02, 0x00, 0x08, 0x00, 0x00,   // DW_LNE_set_address 0x800
+        0x04, 0x02,                                 // DW_LNS_set_file 2
+        0x03, 0x09,                                 // DW_LNS_advance_line 9
+        0x01,                                       // DW_LNS_copy
+        0x02, 0x10,                                 // DW_LNS_advance_pc 16
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+        0x00, 0x05, 0x02, 0x04, 0x10, 0x00, 0x00,   // DW_LNE_set_address 0x1004
+        0x03, 0x13,                                 // DW_LNS_advance_line 19
+        0x01,                                       // DW_LNS_copy
+        0x02, 0x01,                                 // DW_LNS_advance_pc 1
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+    ];
+    let table = LineTable::new(vec![make_test_header(&first), make_test_header(&second)])
+        .expect("Should build the table");
+
+    assert_eq!(table.sequences().iter().map(|s| (s.start(), s.end())).collect::<Vec<_>>(),
+               vec![(0x800, 0x810), (0x1000, 0x1008), (0x1004, 0x1005)]);
+
+    let line = |address| table.find(address).map(|location| location.row().line());
+    assert_eq!(line(0x7ff), None);
+    assert_eq!(line(0x800), Some(10));
+    assert_eq!(line(0x80f), Some(10));
+    assert_eq!(line(0x810), None);
+    assert_eq!(line(0x1000), Some(1));
+    assert_eq!(line(0x1003), Some(2));
+    // The overlapping sequence that starts closest to the address wins.
+    assert_eq!(line(0x1004), Some(20));
+    assert_eq!(line(0x1005), Some(3));
+    assert_eq!(line(0x1007), Some(3));
+    assert_eq!(line(0x1008), None);
+    // Empty sequences don't cover any addresses.
+    assert_eq!(line(0x2000), None);
+
+    let location = table.find(0x808).expect("Should find the address");
+    assert_eq!(location.row().address(), 0x800);
+    assert_eq!(location.file().map(|file| file.path_name()),
+               Some(AttributeValue::String(b"bar.h")));
+    assert_eq!(location.header().version(), 4);
+}
+
+#[test]
+fn test_line_table_for_unit() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x00,                           // code 1, DW_TAG_compile_unit, no children
+        0x10, 0x17,                                 // DW_AT_stmt_list, DW_FORM_sec_offset
+        0x00, 0x00,
+        0x00,
+    ];
+    let debug_info = [
+        0x0c, 0x00, 0x00, 0x00,                     // 32-bit unit length
+        0x04, 0x00,                                 // version 4
+        0x00, 0x00, 0x00, 0x00,                     // debug_abbrev_offset
+        0x04,                                       // address size
+        0x01,                                       // abbreviation code
+        0x00, 0x00, 0x00, 0x00,                     // DW_AT_stmt_list
+    ];
+    let mut debug_line = TEST_DEBUG_LINE_V4.to_vec();
+    debug_line[0] += 13;
+    debug_line.extend_from_slice(&[
+        0x00, 0x05, 0x02, 0x00, 0x10, 0x00, 0x00,   // DW_LNE_set_address 0x1000
+        0x01,                                       // DW_LNS_copy
+        0x02, 0x04,                                 // DW_LNS_advance_pc 4
+        0x00, 0x01, 0x01,                           // DW_LNE_end_sequence
+    ]);
+    let debug_line = DebugLine::new(&debug_line, LittleEndian);
+
+    let table = LineTable::for_debug_info(&debug_info, &debug_abbrev, &debug_line)
+        .expect("Should build the table");
+    assert_eq!(table.sequences().len(), 1);
+    assert_eq!(table.find(0x1003).map(|location| location.row().line()), Some(1));
+
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let table = LineTable::for_unit(&debug_line, &unit, &abbrevs)
+        .expect("Should build the table");
+    assert_eq!(table.sequences().len(), 1);
+}
+
+/// The address range of a function's code, either a subprogram or an inlined
+/// copy of one, as found by `function_ranges`. A function whose code is not
+/// contiguous has a `FunctionRange` for each of its ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionRange {
+    offset: u64,
+    tag: AbbreviationTag,
+    low_pc: u64,
+    high_pc: u64,
+}
+
+impl FunctionRange {
+    /// Construct a new `FunctionRange`.
+    pub fn new(offset: u64, tag: AbbreviationTag, low_pc: u64, high_pc: u64) -> FunctionRange {
+        FunctionRange {
+            offset,
+            tag,
+            low_pc,
+            high_pc,
+        }
+    }
+
+    /// Get the offset of the function's debugging information entry, as
+    /// returned by `DebuggingInformationEntry::offset`.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the tag of the function's debugging information entry, which is
+    /// either `DW_TAG_subprogram` or `DW_TAG_inlined_subroutine`.
+    pub fn tag(&self) -> AbbreviationTag {
+        self.tag
+    }
+
+    /// Return true if this is an inlined copy of a function.
+    pub fn is_inlined(&self) -> bool {
+        self.tag == AbbreviationTag::InlinedSubroutine
+    }
+
+    /// Get the address of the function's first instruction.
+    pub fn low_pc(&self) -> u64 {
+        self.low_pc
+    }
+
+    /// Get the address of the first byte after the end of the function.
+    pub fn high_pc(&self) -> u64 {
+        self.high_pc
+    }
+
+    fn contains(&self, address: u64) -> bool {
+        self.low_pc <= address && address < self.high_pc
+    }
+}
+
+/// Find the address ranges of the subprograms and inlined subroutines in the
+/// given compilation unit, from their `DW_AT_low_pc` and `DW_AT_high_pc`
+/// attributes, or their `DW_AT_ranges` range lists in `.debug_ranges`.
+///
+/// Each inlined copy and each instantiation of a template has its own range.
+/// A function whose code is split across a range list gets a `FunctionRange`
+/// for each of its ranges, all with the same offset. Addresses that are
+/// indices into the unit's table of addresses are resolved through
+/// `.debug_addr`. Entries without an address range are skipped, and entries
+/// whose addresses have forms that can not be read are an error.
+pub fn function_ranges<'input, Endian>(unit: &CompilationUnit<'input, Endian>,
+                                       abbrevs: &Abbreviations,
+                                       debug_addr: &DebugAddr<'input, Endian>,
+                                       debug_ranges: &DebugRanges<'input, Endian>)
+                                       -> Result<Vec<FunctionRange>, Error>
+    where Endian: Endianity
+{
+    let address_size = unit.header().address_size();
+    let addr_base = unit.addr_base(abbrevs)?;
+    let base_address = unit.base_address(abbrevs, debug_addr)?;
+
+    let mut functions = Vec::new();
+    let mut entries = unit.entries(abbrevs);
+    while let Some((_, entry)) = entries.next_dfs()? {
+        let tag = entry.tag();
+        if tag != AbbreviationTag::Subprogram && tag != AbbreviationTag::InlinedSubroutine {
+            continue;
+        }
+
+        if let Some(value) = entry.attr_value(AttributeName::Ranges) {
+            let offset = match value {
+                AttributeValue::SecOffset(offset) => offset,
+                value => value.udata_value().ok_or(Error::UnsupportedAttributeForm)?,
+            };
+            for range in debug_ranges.ranges(offset, unit.header(), base_address)? {
+                let range = range?;
+                functions.push(FunctionRange::new(entry.offset(), tag, range.begin(), range.end()));
+            }
+            continue;
+        }
+
+        let low_pc = match entry.attr_value(AttributeName::LowPc) {
+            Some(value) => {
+                debug_addr.address_value(value, address_size, addr_base)?
+                    .ok_or(Error::UnsupportedAttributeForm)?
+            },
+            None => continue,
+        };
+        // Since DWARF 4, a constant-class `DW_AT_high_pc` is an offset from
+        // `DW_AT_low_pc`.
+        let high_pc = match entry.attr_value(AttributeName::HighPc) {
+            Some(value) => match debug_addr.address_value(value, address_size, addr_base)? {
+                Some(address) => address,
+                None => {
+                    let offset = value.udata_value().ok_or(Error::UnsupportedAttributeForm)?;
+                    low_pc.wrapping_add(offset)
+                },
+            },
+            None => continue,
+        };
+        functions.push(FunctionRange::new(entry.offset(), tag, low_pc, high_pc));
+    }
+    Ok(functions)
+}
+
+#[test]
+fn test_function_ranges() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x01,                           // DW_TAG_compile_unit, children
+        0xb3, 0x42, 0x17, 0x11, 0x01, 0x00, 0x00,   // DW_AT_GNU_addr_base, DW_AT_low_pc addr
+        0x02, 0x2e, 0x01,                           // DW_TAG_subprogram, children
+        0x11, 0x01, 0x12, 0x06, 0x00, 0x00,         // DW_AT_low_pc addr, DW_AT_high_pc data4
+        0x03, 0x1d, 0x00,                           // DW_TAG_inlined_subroutine, no children
+        0x11, 0x01, 0x12, 0x01, 0x00, 0x00,         // DW_AT_low_pc addr, DW_AT_high_pc addr
+        0x04, 0x1d, 0x00,                           // DW_TAG_inlined_subroutine, no children
+        0x55, 0x17, 0x00, 0x00,                     // DW_AT_ranges sec_offset
+        0x05, 0x1d, 0x00,                           // DW_TAG_inlined_subroutine, no children
+        0x11, 0x81, 0x3e,                           // DW_AT_low_pc GNU_addr_index
+        0x12, 0x0b, 0x00, 0x00,                     // DW_AT_high_pc data1
+        0x06, 0x1d, 0x00,                           // DW_TAG_inlined_subroutine, no children
+        0x11, 0x0b, 0x12, 0x0b, 0x00, 0x00,         // DW_AT_low_pc data1, DW_AT_high_pc data1
+        0x00,
+    ];
+    let debug_info = [
+        0x2c, 0x00, 0x00, 0x00,                     // 32-bit unit length
+        0x04, 0x00,                                 // version 4
+        0x00, 0x00, 0x00, 0x00,                     // debug_abbrev_offset
+        0x04,                                       // address size
+        0x01,                                       // DW_TAG_compile_unit
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00,
+        0x02,                                       // DW_TAG_subprogram
+        0x00, 0x10, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00,
+        0x03,                                       // DW_TAG_inlined_subroutine
+        0x08, 0x10, 0x00, 0x00, 0x0c, 0x10, 0x00, 0x00,
+        0x04,                                       // DW_TAG_inlined_subroutine
+        0x00, 0x00, 0x00, 0x00,
+        0x05,                                       // DW_TAG_inlined_subroutine
+        0x00, 0x04,
+        0x00,
+        0x00,
+
+        0x15, 0x00, 0x00, 0x00,                     // 32-bit unit length
+        0x04, 0x00,                                 // version 4
+        0x00, 0x00, 0x00, 0x00,                     // debug_abbrev_offset
+        0x04,                                       // address size
+        0x01,                                       // DW_TAG_compile_unit
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00,
+        0x06,                                       // DW_TAG_inlined_subroutine
+        0x00, 0x04,
+        0x00,
+        0x00,
+    ];
+    let debug_ranges = [
+        0x10, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00,
+        0x18, 0x00, 0x00, 0x00, 0x1c, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    let debug_addr = [0x1c, 0x10, 0x00, 0x00];
+    let debug_ranges = DebugRanges::new(&debug_ranges, LittleEndian);
+    let debug_addr = DebugAddr::new(&debug_addr, LittleEndian);
+
+    let units: Vec<_> = CompilationUnitsIter::new(&debug_info, LittleEndian)
+        .map(|unit| unit.expect("Should parse the unit"))
+        .collect();
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let functions = function_ranges(&units[0], &abbrevs, &debug_addr, &debug_ranges)
+        .expect("Should find the functions");
+    let inlined = AbbreviationTag::InlinedSubroutine;
+    assert_eq!(functions,
+               vec![FunctionRange::new(0x14, AbbreviationTag::Subprogram, 0x1000, 0x1020),
+                    FunctionRange::new(0x1d, inlined, 0x1008, 0x100c),
+                    FunctionRange::new(0x26, inlined, 0x1010, 0x1014),
+                    FunctionRange::new(0x26, inlined, 0x1018, 0x101c),
+                    FunctionRange::new(0x2b, inlined, 0x101c, 0x1020)]);
+
+    match function_ranges(&units[1], &abbrevs, &debug_addr, &debug_ranges) {
+        Err(Error::UnsupportedAttributeForm) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+/// A place to put a breakpoint for a source line, as found by
+/// `LineTable::breakpoints`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineBreakpoint {
+    address: u64,
+    ranges: Vec<(u64, u64)>,
+    function: Option<FunctionRange>,
+}
+
+impl LineBreakpoint {
+    /// Get the address to put the breakpoint at.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Get the address ranges, as `(start, end)` pairs, that begin at a
+    /// recommended breakpoint location for the line.
+    pub fn ranges(&self) -> &[(u64, u64)] {
+        &self.ranges[..]
+    }
+
+    /// Get the innermost function that contains the ranges, if it is known.
+    pub fn function(&self) -> Option<&FunctionRange> {
+        self.function.as_ref()
+    }
+}
+
+/// Get the string of a string-class attribute value in a line number program
+/// header, which may live in the `.debug_str` or `.debug_line_str` sections.
+fn header_string<'input>(value: AttributeValue<'input>,
+                         debug_str: &DebugStr<'input>,
+                         debug_line_str: &DebugStr<'input>)
+                         -> Result<Option<&'input [u8]>, Error> {
+    match value {
+        AttributeValue::DebugLineStrRef(offset) => debug_line_str.get_str(offset).map(Some),
+        _ => value.string_value(debug_str),
+    }
+}
+
+/// Return true if `suffix` is made of the last components of `path`.
+fn is_path_suffix(path: &[u8], suffix: &[u8]) -> bool {
+    path.len() > suffix.len() && path.ends_with(suffix) &&
+    path[path.len() - suffix.len() - 1] == b'/'
+}
+
+/// Return true if the given file entry refers to the given path. Either path
+/// may be a suffix of the other, so that relative paths match absolute ones.
+pub(crate) fn file_matches<'input, Endian>(header: &LineNumberProgramHeader<'input, Endian>,
+                                           file: &FileEntry<'input>,
+                                           path: &[u8],
+                                           debug_str: &DebugStr<'input>,
+                                           debug_line_str: &DebugStr<'input>)
+                                           -> Result<bool, Error>
+    where Endian: Endianity
+{
+    let name = match header_string(file.path_name(), debug_str, debug_line_str)? {
+        Some(name) => name,
+        None => return Ok(false),
+    };
+
+    let mut full_path = Vec::new();
+    if !name.starts_with(b"/") {
+        if let Some(directory) = header.directory(file.directory_index()) {
+            if let Some(directory) = header_string(directory, debug_str, debug_line_str)? {
+                full_path.extend_from_slice(directory);
+                full_path.push(b'/');
+            }
+        }
+    }
+    full_path.extend_from_slice(name);
+
+    Ok(full_path == path || is_path_suffix(&full_path, path) ||
+       is_path_suffix(path, &full_path))
+}
+
+#[test]
+fn test_file_matches() {
+    let mut header = make_test_header(&[]);
+    header.version = 5;
+    header.include_directories = vec![AttributeValue::DebugLineStrRef(0),
+                                      AttributeValue::DebugLineStrRef(5)];
+    let debug_str = DebugStr::new(b"\0lib/a.c\0");
+    let debug_line_str = DebugStr::new(b"/src\0inc\0");
+    let matches = |file: &FileEntry, path: &[u8]| {
+        file_matches(&header, file, path, &debug_str, &debug_line_str)
+            .expect("Should read the file's path")
+    };
+
+    let file = FileEntry::new(AttributeValue::String(b"b.h"), 1, 0, 0, None);
+    assert!(matches(&file, b"b.h"));
+    assert!(matches(&file, b"inc/b.h"));
+    assert!(matches(&file, b"/src/inc/b.h"));
+    assert!(!matches(&file, b"/src/b.h"));
+    assert!(!matches(&file, b"nc/b.h"));
+
+    let file = FileEntry::new(AttributeValue::DebugStrRef(1), 0, 0, 0, None);
+    assert!(matches(&file, b"/src/lib/a.c"));
+    assert!(!matches(&file, b"inc/lib/a.c"));
+
+    // Absolute paths do not get the directory.
+    let file = FileEntry::new(AttributeValue::String(b"/usr/include/stdio.h"), 1, 0, 0, None);
+    assert!(matches(&file, b"include/stdio.h"));
+    assert!(!matches(&file, b"inc/usr/include/stdio.h"));
+
+    // Paths in the supplementary object file can not be matched.
+    let file = FileEntry::new(AttributeValue::DebugStrRefSup(1), 0, 0, 0, None);
+    assert!(!matches(&file, b"a.c"));
+
+    // The directory's path is not in `.debug_line_str`.
+    let file = FileEntry::new(AttributeValue::String(b"b.h"), 1, 0, 0, None);
+    match file_matches(&header, &file, b"b.h", &debug_str, &DebugStr::new(&[])) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+/// The result that a range of a breakpoint search is grouped into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BreakpointGroup {
+    Function(u64),
+    Sequence(usize),
+}
+
+impl<'input, Endian> LineTable<'input, Endian>
+    where Endian: Endianity
+{
+    /// Find the address to put a breakpoint at to stop at the entry of the
+    /// given function, after its prologue.
+    ///
+    /// This is the first row within the function that has `prologue_end` set,
+    /// or the function's `low_pc` if there is no such row.
+    pub fn function_entry(&self, function: &FunctionRange) -> u64 {
+        let start = self.max_ends.partition_point(|&end| end <= function.low_pc);
+        self.sequences[start..]
+            .iter()
+            .take_while(|sequence| sequence.start < function.high_pc)
+            .flat_map(|sequence| sequence.rows.iter())
+            .find(|row| row.prologue_end() && function.contains(row.address()))
+            .map_or(function.low_pc, |row| row.address())
+    }
+
+    /// Find the places to put breakpoints for the given source line.
+    ///
+    /// The ranges of addresses where rows with `is_stmt` set start the line
+    /// are grouped by the innermost of the given functions that contains them,
+    /// so that each inlined copy and each template instantiation gets its own
+    /// breakpoint. Function ranges with the same offset are the same function,
+    /// and share a breakpoint. Ranges outside of any function are grouped by sequence.
+    /// If the line is a function's first line, the breakpoint is put after
+    /// the function's prologue, as found by `function_entry`.
+    ///
+    /// Files match the path if either one is a suffix of the other. The
+    /// `debug_line_str` section has the same layout as `.debug_str`, and is
+    /// used to read DWARF 5 `DW_FORM_line_strp` paths. Returns an error if a
+    /// file's path can not be read from these sections.
+    pub fn breakpoints(&self,
+                       path: &[u8],
+                       line: u64,
+                       functions: &[FunctionRange],
+                       debug_str: &DebugStr<'input>,
+                       debug_line_str: &DebugStr<'input>)
+                       -> Result<Vec<LineBreakpoint>, Error> {
+        let mut breakpoints: Vec<LineBreakpoint> = Vec::new();
+        let mut groups = HashMap::new();
+        let mut matches = HashMap::new();
+
+        for (index, sequence) in self.sequences.iter().enumerate() {
+            let program = &self.programs[sequence.program];
+            let rows = &sequence.rows;
+            let mut i = 0;
+            while i < rows.len() {
+                let row = &rows[i];
+                i += 1;
+                if !row.is_stmt() || row.line() != line {
+                    continue;
+                }
+
+                let key = (sequence.program, row.file());
+                let is_match = match matches.get(&key) {
+                    Some(&is_match) => is_match,
+                    None => {
+                        let file = lookup_file(&program.header, &program.defined_files, row.file());
+                        let is_match = match file {
+                            Some(file) => {
+                                let header = &program.header;
+                                file_matches(header, file, path, debug_str, debug_line_str)?
+                            }
+                            None => false,
+                        };
+                        matches.insert(key, is_match);
+                        is_match
+                    }
+                };
+                if !is_match {
+                    continue;
+                }
+
+                // The range continues until the rows move to another line.
+                while i < rows.len() && rows[i].line() == line && rows[i].file() == row.file() {
+                    i += 1;
+                }
+                let end = rows.get(i).map_or(sequence.end, |row| row.address());
+                if row.address() >= end {
+                    continue;
+                }
+
+                let function = functions.iter()
+                    .filter(|function| function.contains(row.address()))
+                    .min_by_key(|function| function.high_pc - function.low_pc);
+                let group = match function {
+                    Some(function) => BreakpointGroup::Function(function.offset),
+                    None => BreakpointGroup::Sequence(index),
+                };
+
+                let breakpoint = *groups.entry(group).or_insert_with(|| {
+                    breakpoints.push(LineBreakpoint {
+                        address: row.address(),
+                        ranges: Vec::new(),
+                        function: function.copied(),
+                    });
+                    breakpoints.len() - 1
+                });
+                breakpoints[breakpoint].ranges.push((row.address(), end));
+            }
+        }
+
+        for breakpoint in &mut breakpoints {
+            if let Some(function) = breakpoint.function {
+                if breakpoint.address == function.low_pc {
+                    breakpoint.address = self.function_entry(&function);
+                }
+            }
+        }
+        breakpoints.sort_by_key(|breakpoint| breakpoint.address);
+        Ok(breakpoints)
+    }
+}
+
+#[cfg(test)]
+const TEST_BREAKPOINTS_PROGRAM: [u8; 46] = [
+    0x00, 0x05, 0x02, 0x00, 0x10, 0x00, 0x00,       // DW_LNE_set_address 0x1000
+    0x03, 0x09,                                     // DW_LNS_advance_line 9
+    0x01,                                           // DW_LNS_copy
+    0x0a,                                           // DW_LNS_set_prologue_end
+    0x4b,                                           // special: address += 4, line += 1
+    0x04, 0x02,                                     // DW_LNS_set_file 2
+    0x03, 0x78,                                     // DW_LNS_advance_line -8
+    0x02, 0x04,                                     // DW_LNS_advance_pc 4
+    0x01,                                           // DW_LNS_copy
+    0x04, 0x01,                                     // DW_LNS_set_file 1
+    0x03, 0x09,                                     // DW_LNS_advance_line 9
+    0x02, 0x04,                                     // DW_LNS_advance_pc 4
+    0x01,                                           // DW_LNS_copy
+    0x04, 0x02,                                     // DW_LNS_set_file 2
+    0x03, 0x77,                                     // DW_LNS_advance_line -9
+    0x02, 0x04,                                     // DW_LNS_advance_pc 4
+    0x01,                                           // DW_LNS_copy
+    0x04, 0x01,                                     // DW_LNS_set_file 1
+    0x03, 0x0a,                                     // DW_LNS_advance_line 10
+    0x06,                                           // DW_LNS_negate_stmt
+    0x02, 0x04,                                     // DW_LNS_advance_pc 4
+    0x01,                                           // DW_LNS_copy
+    0x02, 0x04,                                     // DW_LNS_advance_pc 4
+    0x00, 0x01, 0x01,                               // DW_LNE_end_sequence
+];
+
+#[test]
+fn test_line_table_breakpoints() {
+    let table = LineTable::new(vec![make_test_header(&TEST_BREAKPOINTS_PROGRAM)])
+        .expect("Should build the table");
+    let subprogram = FunctionRange::new(0x10, AbbreviationTag::Subprogram, 0x1000, 0x1018);
+    let inlined1 = FunctionRange::new(0x20, AbbreviationTag::InlinedSubroutine, 0x1008, 0x100c);
+    let inlined2 = FunctionRange::new(0x30, AbbreviationTag::InlinedSubroutine, 0x1010, 0x1014);
+    let functions = [subprogram, inlined1, inlined2];
+    let debug_str = DebugStr::new(&[]);
+    let breakpoints = |path: &[u8], line, functions: &[FunctionRange]| {
+        table.breakpoints(path, line, functions, &debug_str, &debug_str)
+            .expect("Should find breakpoints")
+    };
+
+    assert_eq!(table.function_entry(&subprogram), 0x1004);
+    assert_eq!(table.function_entry(&inlined1), 0x1008);
+
+    // The function's first line skips the prologue.
+    let found = breakpoints(b"foo.c", 10, &functions);
+    assert_eq!(found,
+               vec![LineBreakpoint {
+                        address: 0x1004,
+                        ranges: vec![(0x1000, 0x1004)],
+                        function: Some(subprogram),
+                    }]);
+
+    // Each inlined copy gets its own breakpoint.
+    for path in &[&b"bar.h"[..], b"inc/bar.h", b"/inc/bar.h"] {
+        let found = breakpoints(path, 3, &functions);
+        assert_eq!(found.iter()
+                       .map(|bp| (bp.address(), bp.function().map(|f| f.offset())))
+                       .collect::<Vec<_>>(),
+                   vec![(0x1008, Some(0x20)), (0x1010, Some(0x30))]);
+        assert!(found.iter().all(|bp| bp.function().is_some_and(|f| f.is_inlined())));
+    }
+    assert!(breakpoints(b"/usr/inc/bar.h", 3, &functions).is_empty());
+    assert!(breakpoints(b"nc/bar.h", 3, &functions).is_empty());
+
+    let found = breakpoints(b"/src/foo.c", 12, &[]);
+    assert_eq!(found,
+               vec![LineBreakpoint {
+                        address: 0x100c,
+                        ranges: vec![(0x100c, 0x1010)],
+                        function: None,
+                    }]);
+
+    // Rows without is_stmt are not breakpoint locations.
+    assert!(breakpoints(b"foo.c", 13, &functions).is_empty());
+
+    // The ranges of a function from a range list share a breakpoint.
+    let range1 = FunctionRange::new(0x40, AbbreviationTag::InlinedSubroutine, 0x1008, 0x100c);
+    let range2 = FunctionRange::new(0x40, AbbreviationTag::InlinedSubroutine, 0x1010, 0x1014);
+    let found = breakpoints(b"bar.h", 3, &[subprogram, range1, range2]);
+    assert_eq!(found,
+               vec![LineBreakpoint {
+                        address: 0x1008,
+                        ranges: vec![(0x1008, 0x100c), (0x1010, 0x1014)],
+                        function: Some(range1),
+                    }]);
+}
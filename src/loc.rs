@@ -0,0 +1,376 @@
+//! Functions for parsing the `.debug_loc` section.
+
+use debug_addr::DebugAddr;
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use parser::{Error, into_result, parse_address, parse_u16, parse_u32, parse_u8,
+             parse_unsigned_leb, take};
+use ranges::{max_address, Range};
+use types::{CompilationUnitHeader, LocationListEntryKind};
+
+/// The `.debug_loc` section, which holds the location lists that
+/// `DW_AT_location` and similar attributes refer to with `DW_FORM_sec_offset`.
+///
+/// This is also used for the `.debug_loc.dwo` section of pre-standard split
+/// DWARF, whose entries are read with `DebugLoc::dwo_locations`.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugLoc<'input, Endian>
+    where Endian: Endianity
+{
+    debug_loc_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugLoc<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugLoc` instance from the data in the `.debug_loc`
+    /// section, which is encoded in the given byte order.
+    pub fn new(debug_loc_section: &'input [u8], endian: Endian) -> DebugLoc<'input, Endian> {
+        DebugLoc {
+            debug_loc_section,
+            endian,
+        }
+    }
+
+    fn list(&self, offset: u64) -> Result<&'input [u8], Error> {
+        if offset >= self.debug_loc_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+        Ok(&self.debug_loc_section[offset as usize..])
+    }
+
+    /// Iterate over the location list at the given offset, which is usually
+    /// the value of an attribute in the given compilation unit.
+    ///
+    /// The `base_address` is the unit's base address, as returned by
+    /// `CompilationUnit::base_address`. Base address selection entries in the
+    /// list replace it.
+    pub fn locations(&self,
+                     offset: u64,
+                     header: &CompilationUnitHeader,
+                     base_address: u64)
+                     -> Result<LocationsIter<'input, Endian>, Error> {
+        Ok(LocationsIter {
+            input: self.list(offset)?,
+            address_size: header.address_size(),
+            base_address,
+            debug_addr: None,
+            endian: self.endian,
+        })
+    }
+
+    /// Iterate over the location list at the given offset in a `.debug_loc.dwo`
+    /// section, whose entries use the `DW_LLE_GNU_*` kinds.
+    ///
+    /// Their addresses are indices into the unit's table of addresses in the
+    /// `.debug_addr` section, which starts at the `addr_base` offset from the
+    /// skeleton unit's `DW_AT_GNU_addr_base` attribute.
+    pub fn dwo_locations(&self,
+                         offset: u64,
+                         header: &CompilationUnitHeader,
+                         debug_addr: &DebugAddr<'input, Endian>,
+                         addr_base: u64)
+                         -> Result<LocationsIter<'input, Endian>, Error> {
+        Ok(LocationsIter {
+            input: self.list(offset)?,
+            address_size: header.address_size(),
+            base_address: 0,
+            debug_addr: Some((*debug_addr, addr_base)),
+            endian: self.endian,
+        })
+    }
+}
+
+/// An entry in a location list: the DWARF expression that gives a value's
+/// location while the program counter is within a range of addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocationListEntry<'input> {
+    range: Range,
+    data: &'input [u8],
+}
+
+impl<'input> LocationListEntry<'input> {
+    /// Construct a new `LocationListEntry`.
+    pub fn new(range: Range, data: &'input [u8]) -> LocationListEntry<'input> {
+        LocationListEntry {
+            range,
+            data,
+        }
+    }
+
+    /// Get the absolute range of addresses where this entry applies.
+    pub fn range(&self) -> Range {
+        self.range
+    }
+
+    /// Get the bytes of this entry's DWARF expression.
+    pub fn data(&self) -> &'input [u8] {
+        self.data
+    }
+}
+
+/// An iterator over the entries of a location list.
+///
+/// Base address selection entries are applied rather than yielded, and
+/// entries with empty ranges are skipped, so every entry yielded has an
+/// absolute, non-empty range.
+#[derive(Debug, Clone)]
+pub struct LocationsIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    address_size: u8,
+    base_address: u64,
+    debug_addr: Option<(DebugAddr<'input, Endian>, u64)>,
+    endian: Endian,
+}
+
+/// The kind of a location list entry, and its addresses, before the entry's
+/// range is made absolute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RawLocationListEntry {
+    /// The end of the list.
+    EndOfList,
+    /// A base address selection entry, with the new base address.
+    BaseAddress(u64),
+    /// An entry with a range of addresses, which is followed by its DWARF
+    /// expression.
+    Range(Range),
+}
+
+impl<'input, Endian> LocationsIter<'input, Endian>
+    where Endian: Endianity
+{
+    /// Parse the next entry's kind and addresses. In `.debug_loc`, base
+    /// address selection entries are the ones whose `begin` is the largest
+    /// address.
+    fn parse_range(&mut self) -> Result<RawLocationListEntry, Error> {
+        let (rest, begin) = into_result(parse_address(self.input,
+                                                      self.address_size,
+                                                      self.endian))?;
+        let (rest, end) = into_result(parse_address(rest, self.address_size, self.endian))?;
+        self.input = rest;
+
+        if begin == 0 && end == 0 {
+            Ok(RawLocationListEntry::EndOfList)
+        } else if begin == max_address(self.address_size) {
+            Ok(RawLocationListEntry::BaseAddress(end))
+        } else {
+            Ok(RawLocationListEntry::Range(Range::new(begin, end)))
+        }
+    }
+
+    /// Parse the next entry's kind and addresses from a `.debug_loc.dwo` list,
+    /// whose entries say their kind explicitly.
+    fn parse_dwo_range(&mut self,
+                       debug_addr: &DebugAddr<'input, Endian>,
+                       addr_base: u64)
+                       -> Result<RawLocationListEntry, Error> {
+        let get_address = |index| debug_addr.get_address(self.address_size, addr_base, index);
+
+        let (rest, kind) = into_result(parse_u8(self.input))?;
+        let (rest, entry) = match LocationListEntryKind(kind) {
+            LocationListEntryKind::GnuEndOfListEntry => (rest, RawLocationListEntry::EndOfList),
+            LocationListEntryKind::GnuBaseAddressSelectionEntry => {
+                let (rest, index) = into_result(parse_unsigned_leb(rest))?;
+                (rest, RawLocationListEntry::BaseAddress(get_address(index)?))
+            },
+            LocationListEntryKind::GnuStartEndEntry => {
+                let (rest, begin) = into_result(parse_unsigned_leb(rest))?;
+                let (rest, end) = into_result(parse_unsigned_leb(rest))?;
+                let range = Range::new(get_address(begin)?, get_address(end)?);
+                (rest, RawLocationListEntry::Range(range))
+            },
+            LocationListEntryKind::GnuStartLengthEntry => {
+                let (rest, begin) = into_result(parse_unsigned_leb(rest))?;
+                let (rest, length) = into_result(parse_u32(rest, self.endian))?;
+                let begin = get_address(begin)?;
+                let range = Range::new(begin, begin.wrapping_add(length as u64));
+                (rest, RawLocationListEntry::Range(range))
+            },
+            _ => return Err(Error::UnknownLocationListEntry),
+        };
+        self.input = rest;
+        Ok(entry)
+    }
+
+    fn next_entry(&mut self) -> Result<Option<LocationListEntry<'input>>, Error> {
+        loop {
+            let entry = match self.debug_addr {
+                Some((debug_addr, addr_base)) => self.parse_dwo_range(&debug_addr, addr_base)?,
+                None => self.parse_range()?,
+            };
+            let range = match entry {
+                RawLocationListEntry::Range(range) => range,
+                RawLocationListEntry::BaseAddress(base_address) => {
+                    self.base_address = base_address;
+                    continue;
+                },
+                RawLocationListEntry::EndOfList => {
+                    self.input = &[];
+                    return Ok(None);
+                },
+            };
+
+            let (rest, length) = into_result(parse_u16(self.input, self.endian))?;
+            let (rest, data) = into_result(take(rest, length as u64))?;
+            self.input = rest;
+
+            if range.begin() == range.end() {
+                continue;
+            }
+
+            // The addresses of split DWARF entries are already absolute.
+            let range = if self.debug_addr.is_some() {
+                range
+            } else {
+                Range::new(self.base_address.wrapping_add(range.begin()),
+                           self.base_address.wrapping_add(range.end()))
+            };
+            return Ok(Some(LocationListEntry::new(range, data)));
+        }
+    }
+}
+
+impl<'input, Endian> Iterator for LocationsIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<LocationListEntry<'input>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match self.next_entry() {
+            Ok(entry) => entry.map(Ok),
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+#[test]
+fn test_locations() {
+    let buf = [
+        0xaa, 0xbb,                                     // another list's data
+        0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // 0x10..0x20
+        0x01, 0x00, 0x50,                               // DW_OP_reg0
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x10, 0x00, 0x00, // base address 0x1000
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // 0x0..0x8
+        0x02, 0x00, 0x91, 0x78,                         // DW_OP_fbreg -8
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // empty
+        0x01, 0x00, 0x51,                               // DW_OP_reg1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // end of list
+        0x30, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, // the next list
+    ];
+
+    let debug_loc = DebugLoc::new(&buf, LittleEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 4);
+    let entries = debug_loc.locations(2, &header, 0x100)
+        .expect("Should find the list")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the list");
+    assert_eq!(entries,
+               vec![LocationListEntry::new(Range::new(0x110, 0x120), &[0x50]),
+                    LocationListEntry::new(Range::new(0x1000, 0x1008), &[0x91, 0x78])]);
+}
+
+#[test]
+fn test_locations_64_big_endian() {
+    let buf = [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, // 0x10..0x20
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20,
+        0x00, 0x01, 0x50,                               // DW_OP_reg0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // end of list
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let debug_loc = DebugLoc::new(&buf, BigEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 8);
+    let mut entries = debug_loc.locations(0, &header, 0x12345678_00000000)
+        .expect("Should find the list");
+    assert_eq!(entries.next().map(|entry| entry.expect("Should parse the entry")),
+               Some(LocationListEntry::new(Range::new(0x12345678_00000010, 0x12345678_00000020),
+                                           &[0x50])));
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn test_dwo_locations() {
+    let debug_addr_buf = [
+        0xaa, 0xbb, 0xcc, 0xdd,                         // another unit's addresses
+        0x00, 0x10, 0x00, 0x00,                         // index 0: 0x1000
+        0x10, 0x10, 0x00, 0x00,                         // index 1: 0x1010
+        0x00, 0x20, 0x00, 0x00,                         // index 2: 0x2000
+        0xff, 0xff, 0xff, 0xff,                         // index 3: 0xffffffff
+    ];
+    let buf = [
+        0x02, 0x00, 0x01,                               // DW_LLE_GNU_start_end_entry
+        0x01, 0x00, 0x50,                               // DW_OP_reg0
+        0x02, 0x03, 0x03,                               // empty, at the largest address
+        0x01, 0x00, 0x53,                               // DW_OP_reg3
+        0x01, 0x02,                                     // DW_LLE_GNU_base_address_selection_entry
+        0x03, 0x02, 0x08, 0x00, 0x00, 0x00,             // DW_LLE_GNU_start_length_entry
+        0x01, 0x00, 0x51,                               // DW_OP_reg1
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00,             // empty
+        0x01, 0x00, 0x52,                               // DW_OP_reg2
+        0x00,                                           // DW_LLE_GNU_end_of_list_entry
+        0x04,                                           // the next list
+    ];
+
+    let debug_addr = DebugAddr::new(&debug_addr_buf, LittleEndian);
+    let debug_loc = DebugLoc::new(&buf, LittleEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 4);
+    let entries = debug_loc.dwo_locations(0, &header, &debug_addr, 4)
+        .expect("Should find the list")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the list");
+    assert_eq!(entries,
+               vec![LocationListEntry::new(Range::new(0x1000, 0x1010), &[0x50]),
+                    LocationListEntry::new(Range::new(0x2000, 0x2008), &[0x51])]);
+}
+
+#[test]
+fn test_locations_errors() {
+    let buf = [
+        0x04, 0x00, 0x01,                               // unknown entry kind
+        0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // 0x10..0x20
+        0x04, 0x00, 0x50,                               // truncated expression
+    ];
+
+    let debug_loc = DebugLoc::new(&buf, LittleEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 4);
+    match debug_loc.locations(buf.len() as u64, &header, 0) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut entries = debug_loc.locations(3, &header, 0).expect("Should find the list");
+    match entries.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(entries.next().is_none());
+
+    let debug_addr = DebugAddr::new(&[], LittleEndian);
+    let mut entries = debug_loc.dwo_locations(0, &header, &debug_addr, 0)
+        .expect("Should find the list");
+    match entries.next() {
+        Some(Err(Error::UnknownLocationListEntry)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    // DW_LLE_GNU_start_end_entry, with indices past the end of `.debug_addr`.
+    let debug_loc = DebugLoc::new(&[0x02, 0x00, 0x01], LittleEndian);
+    let mut entries = debug_loc.dwo_locations(0, &header, &debug_addr, 0)
+        .expect("Should find the list");
+    match entries.next() {
+        Some(Err(Error::OffsetOutOfBounds)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
@@ -0,0 +1,1278 @@
+//! Functions for parsing the `.debug_macinfo` section, and the `.debug_macro`
+//! section of DWARF 5 and of the GNU extension that preceded it, and for
+//! finding the macros that are defined at a source location.
+
+use debug_str::DebugStr;
+use debug_str_offsets::DebugStrOffsets;
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use line::{DebugLine, file_matches, LineNumberProgramHeader};
+#[cfg(test)]
+use line::TEST_DEBUG_LINE_V4;
+use nom::{Err, ErrorKind, IResult};
+use parser::{AttributeValue, CompilationUnit, Error, Format, into_result, parse_attribute_form,
+             parse_attribute_value, parse_null_terminated_string, parse_offset, parse_u16,
+             parse_u8, parse_unsigned_leb, ParseResult};
+#[cfg(test)]
+use parser::{CompilationUnitsIter, parse_abbreviations_at};
+use std::collections::{HashMap, HashSet};
+use types::{Abbreviations, AttributeForm, AttributeName, MacinfoType, MacroType};
+
+/// An entry in a list of macro definitions, from either the `.debug_macinfo`
+/// or the `.debug_macro` section.
+///
+/// Strings are string-class attribute values, since `.debug_macro` entries may
+/// refer to strings in the `.debug_str` section, in the supplementary object
+/// file's `.debug_str` section, or by index through `.debug_str_offsets`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroEntry<'input> {
+    /// A macro definition, from `DW_MACINFO_define` or one of the
+    /// `DW_MACRO_define*` entries.
+    Define {
+        /// The line of the definition, or 0 for definitions from the command
+        /// line or the compiler.
+        line: u64,
+        /// The macro's name, followed by its parameters and its body, as in
+        /// `NAME(x) body` or `NAME body`.
+        text: AttributeValue<'input>,
+    },
+
+    /// A macro undefinition, from `DW_MACINFO_undef` or one of the
+    /// `DW_MACRO_undef*` entries.
+    Undef {
+        /// The line of the undefinition.
+        line: u64,
+        /// The name of the macro.
+        name: AttributeValue<'input>,
+    },
+
+    /// The start of the definitions in an included source file.
+    StartFile {
+        /// The line of the `#include` directive, or 0 for the primary source
+        /// file.
+        line: u64,
+        /// The index of the file in the line number program header.
+        file: u64,
+    },
+
+    /// The end of the definitions in the innermost included source file.
+    EndFile,
+
+    /// The inclusion of the entries of another `.debug_macro` unit, from
+    /// `DW_MACRO_import`.
+    Import {
+        /// The offset of the other unit in the `.debug_macro` section.
+        offset: u64,
+    },
+
+    /// The inclusion of the entries of a `.debug_macro` unit in the
+    /// supplementary object file, from `DW_MACRO_import_sup`.
+    ImportSup {
+        /// The offset of the other unit in the supplementary object file's
+        /// `.debug_macro` section.
+        offset: u64,
+    },
+
+    /// A vendor extension from `DW_MACINFO_vendor_ext`.
+    VendorExt {
+        /// The vendor defined constant.
+        constant: u64,
+        /// The vendor defined string.
+        text: &'input [u8],
+    },
+
+    /// A vendor extension in the `.debug_macro` section, whose operands are
+    /// described by the unit's opcode operands table.
+    Unknown {
+        /// The entry's type.
+        macro_type: MacroType,
+        /// The entry's operands.
+        operands: Vec<AttributeValue<'input>>,
+    },
+}
+
+/// The `.debug_macinfo` section, which holds the macro definitions that
+/// `DW_AT_macro_info` attributes refer to before DWARF 5.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugMacinfo<'input> {
+    debug_macinfo_section: &'input [u8],
+}
+
+impl<'input> DebugMacinfo<'input> {
+    /// Construct a new `DebugMacinfo` instance from the data in the
+    /// `.debug_macinfo` section.
+    pub fn new(debug_macinfo_section: &'input [u8]) -> DebugMacinfo<'input> {
+        DebugMacinfo { debug_macinfo_section }
+    }
+
+    /// Iterate over the entries at the given offset, which is usually the value
+    /// of a compilation unit's `DW_AT_macro_info` attribute.
+    pub fn entries(&self, offset: u64) -> Result<MacinfoEntriesIter<'input>, Error> {
+        if offset >= self.debug_macinfo_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        Ok(MacinfoEntriesIter { input: &self.debug_macinfo_section[offset as usize..] })
+    }
+}
+
+/// Parse an entry from the `.debug_macinfo` section, or `None` for the entry
+/// that ends a compilation unit's entries.
+fn parse_macinfo_entry<'input>(input: &'input [u8])
+                               -> ParseResult<&'input [u8], Option<MacroEntry<'input>>> {
+    let (rest, macinfo_type) = try_parse!(input, parse_u8);
+    match MacinfoType(macinfo_type) {
+        MacinfoType(0) => IResult::Done(rest, None),
+        MacinfoType::Define => {
+            let (rest, line) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, text) = try_parse!(rest, parse_null_terminated_string);
+            IResult::Done(rest,
+                          Some(MacroEntry::Define {
+                              line,
+                              text: AttributeValue::String(text),
+                          }))
+        },
+        MacinfoType::Undef => {
+            let (rest, line) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, name) = try_parse!(rest, parse_null_terminated_string);
+            IResult::Done(rest,
+                          Some(MacroEntry::Undef {
+                              line,
+                              name: AttributeValue::String(name),
+                          }))
+        },
+        MacinfoType::StartFile => {
+            let (rest, line) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, file) = try_parse!(rest, parse_unsigned_leb);
+            IResult::Done(rest,
+                          Some(MacroEntry::StartFile {
+                              line,
+                              file,
+                          }))
+        },
+        MacinfoType::EndFile => IResult::Done(rest, Some(MacroEntry::EndFile)),
+        MacinfoType::VendorExt => {
+            let (rest, constant) = try_parse!(rest, parse_unsigned_leb);
+            let (rest, text) = try_parse!(rest, parse_null_terminated_string);
+            IResult::Done(rest,
+                          Some(MacroEntry::VendorExt {
+                              constant,
+                              text,
+                          }))
+        },
+        _ => IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownMacroType), input)),
+    }
+}
+
+/// An iterator over the entries of a compilation unit in the `.debug_macinfo`
+/// section.
+#[derive(Debug, Clone)]
+pub struct MacinfoEntriesIter<'input> {
+    input: &'input [u8],
+}
+
+impl<'input> Iterator for MacinfoEntriesIter<'input> {
+    type Item = Result<MacroEntry<'input>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match into_result(parse_macinfo_entry(self.input)) {
+            Ok((rest, entry)) => {
+                self.input = if entry.is_some() { rest } else { &[] };
+                entry.map(Ok)
+            },
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+/// The `.debug_macro` section, which holds the macro definitions that
+/// `DW_AT_macros` and `DW_AT_GNU_macros` attributes refer to.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugMacro<'input, Endian>
+    where Endian: Endianity
+{
+    debug_macro_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugMacro<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugMacro` instance from the data in the
+    /// `.debug_macro` section, which is encoded in the given byte order.
+    pub fn new(debug_macro_section: &'input [u8], endian: Endian) -> DebugMacro<'input, Endian> {
+        DebugMacro {
+            debug_macro_section,
+            endian,
+        }
+    }
+
+    /// Parse the header of the macro unit at the given offset, which is usually
+    /// the value of a compilation unit's `DW_AT_macros` or `DW_AT_GNU_macros`
+    /// attribute, or the offset of a `MacroEntry::Import`, and iterate over
+    /// the unit's entries.
+    pub fn entries(&self, offset: u64) -> Result<MacroEntriesIter<'input, Endian>, Error> {
+        if offset >= self.debug_macro_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        let input = &self.debug_macro_section[offset as usize..];
+        let (rest, header) = into_result(parse_macro_unit_header(input, self.endian))?;
+        Ok(MacroEntriesIter {
+            input: rest,
+            header,
+            endian: self.endian,
+        })
+    }
+}
+
+/// The header of a unit in the `.debug_macro` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroUnitHeader {
+    version: u16,
+    format: Format,
+    debug_line_offset: Option<u64>,
+    opcode_operands: Vec<(MacroType, Vec<AttributeForm>)>,
+}
+
+impl MacroUnitHeader {
+    /// Get the version of this unit's encoding, which is 5 for DWARF 5 and 4
+    /// for the GNU extension.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Return true if this unit's offsets are 64 bits, false if they are 32
+    /// bits.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Get the offset of the line number program in the `.debug_line` section
+    /// that this unit's file indices refer to, if the header has one.
+    pub fn debug_line_offset(&self) -> Option<u64> {
+        self.debug_line_offset
+    }
+
+    /// Get the forms of the operands of the given entry type, if this unit's
+    /// opcode operands table describes it.
+    pub fn opcode_operands(&self, macro_type: MacroType) -> Option<&[AttributeForm]> {
+        self.opcode_operands
+            .iter()
+            .find(|&&(t, _)| t == macro_type)
+            .map(|(_, forms)| &forms[..])
+    }
+}
+
+/// Parse the header of a unit in the `.debug_macro` section.
+fn parse_macro_unit_header<Endian>(input: &[u8],
+                                   endian: Endian)
+                                   -> ParseResult<&[u8], MacroUnitHeader>
+    where Endian: Endianity
+{
+    let (rest, version) = try_parse!(input, call!(parse_u16, endian));
+    if version != 4 && version != 5 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+
+    let (rest, flags) = try_parse!(rest, parse_u8);
+    let format = if flags & 0x1 != 0 {
+        Format::Dwarf64
+    } else {
+        Format::Dwarf32
+    };
+
+    let (rest, debug_line_offset) = if flags & 0x2 != 0 {
+        let (rest, offset) = try_parse!(rest, call!(parse_offset, format, endian));
+        (rest, Some(offset))
+    } else {
+        (rest, None)
+    };
+
+    let mut opcode_operands = Vec::new();
+    let mut rest = rest;
+    if flags & 0x4 != 0 {
+        let (rest1, count) = try_parse!(rest, parse_u8);
+        rest = rest1;
+        for _ in 0..count {
+            let (rest1, macro_type) = try_parse!(rest, parse_u8);
+            let (mut rest1, operand_count) = try_parse!(rest1, parse_unsigned_leb);
+            let mut forms = Vec::new();
+            for _ in 0..operand_count {
+                let (rest2, form) = try_parse!(rest1, parse_attribute_form);
+                forms.push(form);
+                rest1 = rest2;
+            }
+            opcode_operands.push((MacroType(macro_type), forms));
+            rest = rest1;
+        }
+    }
+
+    IResult::Done(rest,
+                  MacroUnitHeader {
+                      version,
+                      format,
+                      debug_line_offset,
+                      opcode_operands,
+                  })
+}
+
+/// An iterator over the entries of a unit in the `.debug_macro` section.
+///
+/// `MacroEntry::Import` entries are yielded rather than followed.
+#[derive(Debug, Clone)]
+pub struct MacroEntriesIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    header: MacroUnitHeader,
+    endian: Endian,
+}
+
+impl<'input, Endian> MacroEntriesIter<'input, Endian>
+    where Endian: Endianity
+{
+    /// Get the header of the unit whose entries this iterates over.
+    pub fn header(&self) -> &MacroUnitHeader {
+        &self.header
+    }
+
+    fn parse_line_and_string(&self,
+                             input: &'input [u8],
+                             form: AttributeForm)
+                             -> Result<(&'input [u8], u64, AttributeValue<'input>), Error> {
+        let (rest, line) = into_result(parse_unsigned_leb(input))?;
+        let (rest, string) = self.parse_operand(rest, form)?;
+        Ok((rest, line, string))
+    }
+
+    fn parse_operand(&self,
+                     input: &'input [u8],
+                     form: AttributeForm)
+                     -> Result<(&'input [u8], AttributeValue<'input>), Error> {
+        // Macro entries have no addresses, so there is no address size.
+        into_result(parse_attribute_value(input,
+                                          form,
+                                          self.header.version,
+                                          0,
+                                          self.header.format,
+                                          self.endian))
+    }
+
+    fn parse_entry(&self) -> Result<(&'input [u8], Option<MacroEntry<'input>>), Error> {
+        let (rest, macro_type) = into_result(parse_u8(self.input))?;
+        let macro_type = MacroType(macro_type);
+
+        // Since DWARF 5 strx forms are only valid in version 5 units, the GNU
+        // extension's string forms are read as their DWARF 4 equivalents.
+        let (rest, entry) = match macro_type {
+            MacroType(0) => return Ok((rest, None)),
+            MacroType::Define | MacroType::Undef | MacroType::DefineStrp |
+            MacroType::UndefStrp | MacroType::DefineSup | MacroType::UndefSup |
+            MacroType::DefineStrx | MacroType::UndefStrx
+                if self.header.opcode_operands(macro_type).is_none() => {
+                let form = match macro_type {
+                    MacroType::Define | MacroType::Undef => AttributeForm::String,
+                    MacroType::DefineStrp | MacroType::UndefStrp => AttributeForm::Strp,
+                    MacroType::DefineSup | MacroType::UndefSup => AttributeForm::StrpSup,
+                    _ => AttributeForm::Strx,
+                };
+                let form = match (form, self.header.version) {
+                    (AttributeForm::StrpSup, 4) => AttributeForm::GnuStrpAlt,
+                    (AttributeForm::Strx, 4) => AttributeForm::GnuStrIndex,
+                    _ => form,
+                };
+                let (rest, line, string) = self.parse_line_and_string(rest, form)?;
+                let entry = match macro_type {
+                    MacroType::Define | MacroType::DefineStrp | MacroType::DefineSup |
+                    MacroType::DefineStrx => {
+                        MacroEntry::Define {
+                            line,
+                            text: string,
+                        }
+                    },
+                    _ => {
+                        MacroEntry::Undef {
+                            line,
+                            name: string,
+                        }
+                    },
+                };
+                (rest, entry)
+            },
+            MacroType::StartFile if self.header.opcode_operands(macro_type).is_none() => {
+                let (rest, line) = into_result(parse_unsigned_leb(rest))?;
+                let (rest, file) = into_result(parse_unsigned_leb(rest))?;
+                (rest,
+                 MacroEntry::StartFile {
+                     line,
+                     file,
+                 })
+            },
+            MacroType::EndFile if self.header.opcode_operands(macro_type).is_none() => {
+                (rest, MacroEntry::EndFile)
+            },
+            MacroType::Import | MacroType::ImportSup
+                if self.header.opcode_operands(macro_type).is_none() => {
+                let (rest, offset) = into_result(parse_offset(rest,
+                                                              self.header.format,
+                                                              self.endian))?;
+                let entry = if macro_type == MacroType::Import {
+                    MacroEntry::Import { offset }
+                } else {
+                    MacroEntry::ImportSup { offset }
+                };
+                (rest, entry)
+            },
+            _ => {
+                let forms = match self.header.opcode_operands(macro_type) {
+                    Some(forms) => forms,
+                    None => return Err(Error::UnknownMacroType),
+                };
+                let mut rest = rest;
+                let mut operands = Vec::with_capacity(forms.len());
+                for &form in forms {
+                    let (rest1, operand) = self.parse_operand(rest, form)?;
+                    operands.push(operand);
+                    rest = rest1;
+                }
+                (rest,
+                 MacroEntry::Unknown {
+                     macro_type,
+                     operands,
+                 })
+            },
+        };
+        Ok((rest, Some(entry)))
+    }
+}
+
+impl<'input, Endian> Iterator for MacroEntriesIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<MacroEntry<'input>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match self.parse_entry() {
+            Ok((rest, entry)) => {
+                self.input = if entry.is_some() { rest } else { &[] };
+                entry.map(Ok)
+            },
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+/// Get the name of a macro from the text of its definition, which is followed
+/// by either its parameters or a space and its body.
+fn macro_name(text: &[u8]) -> &[u8] {
+    let end = text.iter().position(|&c| c == b'(' || c == b' ').unwrap_or(text.len());
+    &text[..end]
+}
+
+/// A macro definition that is in scope at some source location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MacroDefinition<'input> {
+    text: &'input [u8],
+    file: Option<u64>,
+    line: u64,
+}
+
+impl<'input> MacroDefinition<'input> {
+    /// Get the name of the macro.
+    pub fn name(&self) -> &'input [u8] {
+        macro_name(self.text)
+    }
+
+    /// Get the body of the macro, after its name and its parameters.
+    pub fn body(&self) -> &'input [u8] {
+        let rest = &self.text[self.name().len()..];
+        let rest = if rest.starts_with(b"(") {
+            rest.iter().position(|&c| c == b')').map_or(&[][..], |end| &rest[end + 1..])
+        } else {
+            rest
+        };
+        if rest.starts_with(b" ") { &rest[1..] } else { rest }
+    }
+
+    /// Get the full text of the definition, as in `NAME(x) body`.
+    pub fn text(&self) -> &'input [u8] {
+        self.text
+    }
+
+    /// Get the index of the file that defines the macro in the line number
+    /// program header, or `None` for definitions from the command line or the
+    /// compiler.
+    pub fn file(&self) -> Option<u64> {
+        self.file
+    }
+
+    /// Get the line of the definition.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+}
+
+/// The macro definitions of a compilation unit, with the entries of any
+/// imported `.debug_macro` units in place of the `MacroEntry::Import` entries
+/// that refer to them.
+#[derive(Debug, Clone)]
+pub struct MacroTable<'input, Endian>
+    where Endian: Endianity
+{
+    header: LineNumberProgramHeader<'input, Endian>,
+    entries: Vec<MacroEntry<'input>>,
+    str_offsets: Option<(CompilationUnit<'input, Endian>, u64)>,
+}
+
+/// The most entries, including `MacroEntry::Import` entries, that are read
+/// for one compilation unit's `MacroTable`. A unit that is imported several
+/// times is read each time, so a few small units can import each other into
+/// an exponential number of entries.
+const MAX_MACRO_ENTRIES: usize = 1 << 20;
+
+/// Append the entries of the `.debug_macro` unit at the given offset, and of
+/// the units it imports, to `entries`. Import cycles are ignored, and reading
+/// more than `limit` entries is an error.
+///
+/// The imported units are followed with an explicit stack rather than by
+/// recursion, so that long chains of imports can not overflow the stack.
+fn push_macro_entries<'input, Endian>(debug_macro: &DebugMacro<'input, Endian>,
+                                      offset: u64,
+                                      limit: usize,
+                                      entries: &mut Vec<MacroEntry<'input>>)
+                                      -> Result<(), Error>
+    where Endian: Endianity
+{
+    // The units that are being appended, each with the rest of its entries,
+    // and their offsets, to find import cycles.
+    let mut stack = vec![(offset, debug_macro.entries(offset)?)];
+    let mut imports = HashSet::new();
+    imports.insert(offset);
+    let mut count = 0;
+
+    loop {
+        let entry = match stack.last_mut() {
+            Some(&mut (_, ref mut iter)) => iter.next(),
+            None => return Ok(()),
+        };
+        let entry = match entry {
+            Some(entry) => entry?,
+            None => {
+                if let Some((offset, _)) = stack.pop() {
+                    imports.remove(&offset);
+                }
+                continue;
+            },
+        };
+
+        count += 1;
+        if count > limit {
+            return Err(Error::TooManyMacroEntries);
+        }
+        match entry {
+            MacroEntry::Import { offset } => {
+                if imports.insert(offset) {
+                    stack.push((offset, debug_macro.entries(offset)?));
+                }
+            },
+            entry => entries.push(entry),
+        }
+    }
+}
+
+#[test]
+fn test_push_macro_entries() {
+    // Each unit imports the next one `imports` times, and the last one
+    // defines X.
+    let make_units = |units: u32, imports: u32| {
+        let size = 4 + 5 * imports;
+        let mut buf = Vec::new();
+        for unit in 1..units {
+            buf.extend_from_slice(&[0x05, 0x00, 0x00]);     // version 5, no flags
+            for _ in 0..imports {
+                buf.push(0x07);                             // DW_MACRO_import
+                buf.extend_from_slice(&(unit * size).to_le_bytes()[..]);
+            }
+            buf.push(0x00);
+        }
+        buf.extend_from_slice(&[0x05, 0x00, 0x00,           // version 5, no flags
+                                0x01, 0x01, b'X', 0x00,     // DW_MACRO_define 1 "X"
+                                0x00]);
+        buf
+    };
+    let read = |buf: &[u8], limit| {
+        let mut entries = Vec::new();
+        push_macro_entries(&DebugMacro::new(buf, LittleEndian), 0, limit, &mut entries)
+            .map(|_| entries.len())
+    };
+
+    // A long chain of imports does not overflow the stack.
+    let chain = make_units(100_000, 1);
+    assert_eq!(read(&chain, MAX_MACRO_ENTRIES).expect("Should read the chain"), 1);
+
+    // Repeated imports are read each time, up to the limit.
+    let repeated = make_units(4, 2);
+    assert_eq!(read(&repeated, 22).expect("Should read the imports"), 8);
+    match read(&repeated, 21) {
+        Err(Error::TooManyMacroEntries) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    let exponential = make_units(32, 2);
+    match read(&exponential, MAX_MACRO_ENTRIES) {
+        Err(Error::TooManyMacroEntries) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+impl<'input, Endian> MacroTable<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `MacroTable` from a compilation unit's macro entries,
+    /// whose file indices refer to the given line number program header.
+    pub fn new(header: LineNumberProgramHeader<'input, Endian>,
+               entries: Vec<MacroEntry<'input>>)
+               -> MacroTable<'input, Endian> {
+        MacroTable {
+            header,
+            entries,
+            str_offsets: None,
+        }
+    }
+
+    /// Set the compilation unit and the offset of its table in the
+    /// `.debug_str_offsets` section, from `CompilationUnit::str_offsets_base`,
+    /// that the entries' `DW_FORM_strx` strings are looked up with.
+    pub fn with_str_offsets(mut self,
+                            unit: CompilationUnit<'input, Endian>,
+                            str_offsets_base: u64)
+                            -> MacroTable<'input, Endian> {
+        self.str_offsets = Some((unit, str_offsets_base));
+        self
+    }
+
+    /// Construct a `MacroTable` for the given compilation unit, whose root
+    /// entry's `DW_AT_macros` or `DW_AT_GNU_macros` attribute refers to the
+    /// `.debug_macro` section, or whose `DW_AT_macro_info` attribute refers to
+    /// the `.debug_macinfo` section. `DW_FORM_strx` strings are looked up with
+    /// the unit's `str_offsets_base`, if it has one.
+    ///
+    /// Returns `Ok(None)` if the unit does not have macro information, or does
+    /// not have the line number information that its file indices refer to.
+    pub fn for_unit(debug_line: &DebugLine<'input, Endian>,
+                    debug_macinfo: &DebugMacinfo<'input>,
+                    debug_macro: &DebugMacro<'input, Endian>,
+                    unit: &CompilationUnit<'input, Endian>,
+                    abbrevs: &Abbreviations)
+                    -> Result<Option<MacroTable<'input, Endian>>, Error> {
+        let offset = |value: AttributeValue| match value {
+            AttributeValue::SecOffset(offset) => Some(offset),
+            value => value.udata_value(),
+        };
+
+        let mut entries = unit.entries(abbrevs);
+        entries.next_entry()?;
+        let (macros, macro_info) = match entries.current() {
+            Some(entry) => {
+                (entry.attr_value(AttributeName::Macros)
+                     .or_else(|| entry.attr_value(AttributeName::GnuMacros))
+                     .and_then(&offset),
+                 entry.attr_value(AttributeName::MacroInfo).and_then(&offset))
+            },
+            None => return Ok(None),
+        };
+
+        let mut macro_entries = Vec::new();
+        if let Some(macros) = macros {
+            push_macro_entries(debug_macro, macros, MAX_MACRO_ENTRIES, &mut macro_entries)?;
+        } else if let Some(macro_info) = macro_info {
+            for entry in debug_macinfo.entries(macro_info)? {
+                macro_entries.push(entry?);
+            }
+        } else {
+            return Ok(None);
+        }
+
+        let header = match debug_line.unit_header(unit, abbrevs)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let table = MacroTable::new(header, macro_entries);
+        match unit.str_offsets_base(abbrevs) {
+            Ok(base) => Ok(Some(table.with_str_offsets(*unit, base))),
+            Err(Error::MissingStrOffsetsBase) => Ok(Some(table)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the line number program header that the entries' file indices
+    /// refer to.
+    pub fn header(&self) -> &LineNumberProgramHeader<'input, Endian> {
+        &self.header
+    }
+
+    /// Get the entries of the compilation unit.
+    pub fn entries(&self) -> &[MacroEntry<'input>] {
+        &self.entries
+    }
+
+    /// Get the string of a macro entry, looking up `DW_FORM_strx` strings
+    /// through the unit's table in the `.debug_str_offsets` section.
+    fn entry_string(&self,
+                    value: AttributeValue<'input>,
+                    debug_str: &DebugStr<'input>,
+                    debug_str_offsets: &DebugStrOffsets<'input, Endian>)
+                    -> Result<&'input [u8], Error> {
+        let string = match (value, self.str_offsets) {
+            (value, Some((unit, base))) => {
+                debug_str_offsets.string_value(value, &unit, base, debug_str)?
+            },
+            (AttributeValue::DebugStrOffsetsIndex(_), None) => {
+                return Err(Error::MissingStrOffsetsBase)
+            },
+            (value, None) => value.string_value(debug_str)?,
+        };
+        string.ok_or(Error::UnsupportedMacroString)
+    }
+
+    /// Find the macros that are defined at the given line of the given file,
+    /// sorted by name, by replaying the definitions and undefinitions of the
+    /// unit and of the files it includes up to that line. Definitions on the
+    /// line itself are not in scope yet.
+    ///
+    /// The file is the first one included whose path matches, where either
+    /// path may be a suffix of the other. Returns `None` if the unit does not
+    /// include the file.
+    ///
+    /// Strings are read from `debug_str`, through `debug_str_offsets` for
+    /// `DW_FORM_strx` strings, and `debug_line_str` is used to read DWARF 5
+    /// `DW_FORM_line_strp` paths. Returns an error if the string of an entry
+    /// up to the line can not be read, rather than leaving it out.
+    pub fn defined_at(&self,
+                      path: &[u8],
+                      line: u64,
+                      debug_str: &DebugStr<'input>,
+                      debug_str_offsets: &DebugStrOffsets<'input, Endian>,
+                      debug_line_str: &DebugStr<'input>)
+                      -> Result<Option<Vec<MacroDefinition<'input>>>, Error> {
+        let mut definitions = HashMap::new();
+        let mut files = Vec::new();
+        let mut depth = None;
+
+        for entry in &self.entries {
+            let entry_line = match *entry {
+                MacroEntry::Define { line, .. } |
+                MacroEntry::Undef { line, .. } |
+                MacroEntry::StartFile { line, .. } => Some(line),
+                _ => None,
+            };
+            let is_past_line = entry_line.is_some_and(|entry_line| entry_line >= line);
+            if depth == Some(files.len()) && is_past_line {
+                break;
+            }
+
+            match *entry {
+                MacroEntry::Define { line, text } => {
+                    let definition = MacroDefinition {
+                        text: self.entry_string(text, debug_str, debug_str_offsets)?,
+                        file: files.last().cloned(),
+                        line,
+                    };
+                    definitions.insert(definition.name(), definition);
+                },
+                MacroEntry::Undef { name, .. } => {
+                    let name = self.entry_string(name, debug_str, debug_str_offsets)?;
+                    definitions.remove(macro_name(name));
+                },
+                MacroEntry::StartFile { file, .. } => {
+                    files.push(file);
+                    let is_match = match self.header.file(file) {
+                        Some(file) => {
+                            file_matches(&self.header, file, path, debug_str, debug_line_str)?
+                        },
+                        None => false,
+                    };
+                    if depth.is_none() && is_match {
+                        depth = Some(files.len());
+                    }
+                },
+                MacroEntry::EndFile => {
+                    if depth == Some(files.len()) {
+                        break;
+                    }
+                    files.pop();
+                },
+                _ => {},
+            }
+        }
+
+        Ok(depth.map(|_| {
+            let mut definitions: Vec<_> = definitions.into_values().collect();
+            definitions.sort_by_key(|definition| definition.name());
+            definitions
+        }))
+    }
+}
+
+#[test]
+fn test_macinfo_entries() {
+    let buf = [
+        0xaa,                                           // another unit's entries
+        0x03, 0x00, 0x01,                               // DW_MACINFO_start_file 0, 1
+        0x01, 0x01, b'F', b'O', b'O', b' ', b'1', 0x00, // DW_MACINFO_define 1 "FOO 1"
+        0x03, 0x02, 0x02,                               // DW_MACINFO_start_file 2, 2
+        0x04,                                           // DW_MACINFO_end_file
+        0x02, 0x05, b'F', b'O', b'O', 0x00,             // DW_MACINFO_undef 5 "FOO"
+        0xff, 0x07, b'x', 0x00,                         // DW_MACINFO_vendor_ext 7 "x"
+        0x04,                                           // DW_MACINFO_end_file
+        0x00,                                           // end of the unit's entries
+        0x01, 0x01, b'B', 0x00,                         // the next unit's entries
+    ];
+
+    let debug_macinfo = DebugMacinfo::new(&buf);
+    let entries = debug_macinfo.entries(1)
+        .expect("Should find the entries")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the entries");
+    assert_eq!(entries,
+               vec![MacroEntry::StartFile { line: 0, file: 1 },
+                    MacroEntry::Define {
+                        line: 1,
+                        text: AttributeValue::String(b"FOO 1"),
+                    },
+                    MacroEntry::StartFile { line: 2, file: 2 },
+                    MacroEntry::EndFile,
+                    MacroEntry::Undef {
+                        line: 5,
+                        name: AttributeValue::String(b"FOO"),
+                    },
+                    MacroEntry::VendorExt {
+                        constant: 7,
+                        text: b"x",
+                    },
+                    MacroEntry::EndFile]);
+}
+
+#[test]
+fn test_macinfo_entries_errors() {
+    let buf = [
+        0x01, 0x01, b'F', 0x00,                         // DW_MACINFO_define 1 "F"
+        0x05,                                           // unknown type
+        0x02, 0x01, b'F',                               // unterminated string
+    ];
+
+    let debug_macinfo = DebugMacinfo::new(&buf);
+    match debug_macinfo.entries(buf.len() as u64) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut entries = debug_macinfo.entries(0).expect("Should find the entries");
+    assert!(entries.next().expect("Should have an entry").is_ok());
+    match entries.next() {
+        Some(Err(Error::UnknownMacroType)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(entries.next().is_none());
+
+    match debug_macinfo.entries(5).expect("Should find the entries").next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_macro_entries_v5() {
+    let buf = [
+        0x05, 0x00,                                     // version 5
+        0x06,                                           // debug_line_offset, opcode operands
+        0x10, 0x00, 0x00, 0x00,                         // debug_line_offset
+        0x01,                                           // one opcode in the table
+        0xe0, 0x02, 0x0f, 0x08,                         // 0xe0: DW_FORM_udata, DW_FORM_string
+
+        0x03, 0x00, 0x01,                               // DW_MACRO_start_file 0, 1
+        0x01, 0x01, b'A', b' ', b'1', 0x00,             // DW_MACRO_define 1 "A 1"
+        0x05, 0x02, 0x20, 0x00, 0x00, 0x00,             // DW_MACRO_define_strp 2
+        0x06, 0x03, 0x24, 0x00, 0x00, 0x00,             // DW_MACRO_undef_strp 3
+        0x0b, 0x04, 0x03,                               // DW_MACRO_define_strx 4
+        0x0c, 0x05, 0x04,                               // DW_MACRO_undef_strx 5
+        0x08, 0x06, 0x30, 0x00, 0x00, 0x00,             // DW_MACRO_define_sup 6
+        0x09, 0x07, 0x31, 0x00, 0x00, 0x00,             // DW_MACRO_undef_sup 7
+        0xe0, 0x07, b'v', 0x00,                         // vendor extension
+        0x07, 0x40, 0x00, 0x00, 0x00,                   // DW_MACRO_import
+        0x0a, 0x50, 0x00, 0x00, 0x00,                   // DW_MACRO_import_sup
+        0x02, 0x08, b'A', 0x00,                         // DW_MACRO_undef 8 "A"
+        0x04,                                           // DW_MACRO_end_file
+        0x00,                                           // end of the unit's entries
+    ];
+
+    let debug_macro = DebugMacro::new(&buf, LittleEndian);
+    let mut entries = debug_macro.entries(0).expect("Should parse the header");
+    {
+        let header = entries.header();
+        assert_eq!(header.version(), 5);
+        assert!(!header.is_dwarf64());
+        assert_eq!(header.debug_line_offset(), Some(0x10));
+        assert_eq!(header.opcode_operands(MacroType(0xe0)),
+                   Some(&[AttributeForm::Udata, AttributeForm::String][..]));
+        assert_eq!(header.opcode_operands(MacroType::Define), None);
+    }
+
+    let entries = entries.by_ref()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the entries");
+    assert_eq!(entries,
+               vec![MacroEntry::StartFile { line: 0, file: 1 },
+                    MacroEntry::Define {
+                        line: 1,
+                        text: AttributeValue::String(b"A 1"),
+                    },
+                    MacroEntry::Define {
+                        line: 2,
+                        text: AttributeValue::DebugStrRef(0x20),
+                    },
+                    MacroEntry::Undef {
+                        line: 3,
+                        name: AttributeValue::DebugStrRef(0x24),
+                    },
+                    MacroEntry::Define {
+                        line: 4,
+                        text: AttributeValue::DebugStrOffsetsIndex(3),
+                    },
+                    MacroEntry::Undef {
+                        line: 5,
+                        name: AttributeValue::DebugStrOffsetsIndex(4),
+                    },
+                    MacroEntry::Define {
+                        line: 6,
+                        text: AttributeValue::DebugStrRefSup(0x30),
+                    },
+                    MacroEntry::Undef {
+                        line: 7,
+                        name: AttributeValue::DebugStrRefSup(0x31),
+                    },
+                    MacroEntry::Unknown {
+                        macro_type: MacroType::LoUser,
+                        operands: vec![AttributeValue::Udata(7), AttributeValue::String(b"v")],
+                    },
+                    MacroEntry::Import { offset: 0x40 },
+                    MacroEntry::ImportSup { offset: 0x50 },
+                    MacroEntry::Undef {
+                        line: 8,
+                        name: AttributeValue::String(b"A"),
+                    },
+                    MacroEntry::EndFile]);
+}
+
+#[test]
+fn test_macro_entries_gnu_64() {
+    let buf = [
+        0x00, 0x04,                                     // version 4
+        0x03,                                           // 64-bit, debug_line_offset
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, // debug_line_offset
+        0x05, 0x01,                                     // DW_MACRO_GNU_define_indirect 1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20,
+        0x07,                                           // DW_MACRO_GNU_transparent_include
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+        0x08, 0x02,                                     // DW_MACRO_GNU_define_indirect_alt 2
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30,
+        0x00,                                           // end of the unit's entries
+    ];
+
+    let debug_macro = DebugMacro::new(&buf, BigEndian);
+    let mut entries = debug_macro.entries(0).expect("Should parse the header");
+    assert_eq!(entries.header().version(), 4);
+    assert!(entries.header().is_dwarf64());
+    assert_eq!(entries.header().debug_line_offset(), Some(8));
+
+    let entries = entries.by_ref()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the entries");
+    assert_eq!(entries,
+               vec![MacroEntry::Define {
+                        line: 1,
+                        text: AttributeValue::DebugStrRef(0x20),
+                    },
+                    MacroEntry::Import { offset: 0x100 },
+                    MacroEntry::Define {
+                        line: 2,
+                        text: AttributeValue::DebugStrRefSup(0x30),
+                    }]);
+}
+
+#[test]
+fn test_macro_entries_errors() {
+    let buf = [
+        0x03, 0x00,                                     // version 3
+        0x00,                                           // no flags
+        0x00,
+    ];
+    match DebugMacro::new(&buf, LittleEndian).entries(0) {
+        Err(Error::UnknownDwarfVersion) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise.map(|_| ())),
+    };
+
+    let buf = [
+        0x05, 0x00,                                     // version 5
+        0x00,                                           // no flags
+        0x04,                                           // DW_MACRO_end_file
+        0xe1, 0x00,                                     // unknown vendor extension
+    ];
+    let mut entries = DebugMacro::new(&buf, LittleEndian)
+        .entries(0)
+        .expect("Should parse the header");
+    assert_eq!(entries.next().map(|entry| entry.expect("Should parse the entry")),
+               Some(MacroEntry::EndFile));
+    match entries.next() {
+        Some(Err(Error::UnknownMacroType)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(entries.next().is_none());
+
+    let buf = [
+        0x05, 0x00,                                     // version 5
+        0x02,                                           // debug_line_offset
+        0x10, 0x00,                                     // truncated
+    ];
+    match DebugMacro::new(&buf, LittleEndian).entries(0) {
+        Err(Error::UnexpectedEof) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise.map(|_| ())),
+    };
+}
+
+#[test]
+fn test_macro_table_defined_at() {
+    let debug_macinfo = [
+        0x01, 0x00, b'C', b'M', b'D', 0x00,             // DW_MACINFO_define 0 "CMD"
+        0x03, 0x00, 0x01,                               // DW_MACINFO_start_file 0, foo.c
+        0x01, 0x01, b'A', b' ', b'1', 0x00,             // DW_MACINFO_define 1 "A 1"
+        0x03, 0x02, 0x02,                               // DW_MACINFO_start_file 2, bar.h
+        0x01, 0x01, b'B', b'(', b'x', b')', b' ',       // DW_MACINFO_define 1 "B(x) (x+1)"
+        b'(', b'x', b'+', b'1', b')', 0x00,
+        0x01, 0x03, b'C', 0x00,                         // DW_MACINFO_define 3 "C"
+        0x02, 0x05, b'C', 0x00,                         // DW_MACINFO_undef 5 "C"
+        0x01, 0x07, b'D', b' ', b'4', 0x00,             // DW_MACINFO_define 7 "D 4"
+        0x04,                                           // DW_MACINFO_end_file
+        0x02, 0x04, b'A', 0x00,                         // DW_MACINFO_undef 4 "A"
+        0x01, 0x06, b'A', b' ', b'2', 0x00,             // DW_MACINFO_define 6 "A 2"
+        0x04,                                           // DW_MACINFO_end_file
+        0x00,
+    ];
+    let entries = DebugMacinfo::new(&debug_macinfo)
+        .entries(0)
+        .expect("Should find the entries")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the entries");
+    let header = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian)
+        .header(0, 4)
+        .expect("Should parse the header");
+    let table = MacroTable::new(header, entries);
+    let debug_str = DebugStr::new(&[]);
+    let debug_str_offsets = DebugStrOffsets::new(&[], LittleEndian);
+
+    let names = |path: &[u8], line| {
+        table.defined_at(path, line, &debug_str, &debug_str_offsets, &debug_str)
+            .expect("Should read the entries")
+            .map(|definitions| {
+                definitions.iter().map(|definition| definition.name()).collect::<Vec<_>>()
+            })
+    };
+    assert_eq!(names(b"foo.c", 2), Some(vec![&b"A"[..], b"CMD"]));
+    assert_eq!(names(b"foo.c", 3), Some(vec![&b"A"[..], b"B", b"CMD", b"D"]));
+    assert_eq!(names(b"foo.c", 5), Some(vec![&b"B"[..], b"CMD", b"D"]));
+    assert_eq!(names(b"foo.c", 100), Some(vec![&b"A"[..], b"B", b"CMD", b"D"]));
+    assert_eq!(names(b"bar.h", 4), Some(vec![&b"A"[..], b"B", b"C", b"CMD"]));
+    assert_eq!(names(b"/inc/bar.h", 6), Some(vec![&b"A"[..], b"B", b"CMD"]));
+    assert_eq!(names(b"baz.c", 1), None);
+
+    let definitions = table.defined_at(b"foo.c", 100, &debug_str, &debug_str_offsets, &debug_str)
+        .expect("Should read the entries")
+        .expect("Should find the file");
+    assert_eq!(definitions[0].text(), b"A 2");
+    assert_eq!(definitions[0].body(), b"2");
+    assert_eq!(definitions[0].file(), Some(1));
+    assert_eq!(definitions[0].line(), 6);
+    assert_eq!(definitions[1].body(), b"(x+1)");
+    assert_eq!(definitions[1].file(), Some(2));
+    assert_eq!(definitions[2].body(), b"");
+    assert_eq!(definitions[2].file(), None);
+}
+
+#[test]
+fn test_macro_table_for_unit() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x00,                               // code 1, DW_TAG_compile_unit
+        0x10, 0x17,                                     // DW_AT_stmt_list, DW_FORM_sec_offset
+        0x79, 0x17,                                     // DW_AT_macros, DW_FORM_sec_offset
+        0x00, 0x00,
+        0x02, 0x11, 0x00,                               // code 2, DW_TAG_compile_unit
+        0x10, 0x17,                                     // DW_AT_stmt_list, DW_FORM_sec_offset
+        0x43, 0x17,                                     // DW_AT_macro_info, DW_FORM_sec_offset
+        0x00, 0x00,
+        0x03, 0x11, 0x00,                               // code 3, DW_TAG_compile_unit
+        0x10, 0x17,                                     // DW_AT_stmt_list, DW_FORM_sec_offset
+        0x00, 0x00,
+        0x00,
+    ];
+    let debug_info = [
+        0x10, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x01,                                           // abbreviation code
+        0x00, 0x00, 0x00, 0x00,                         // DW_AT_stmt_list
+        0x0a, 0x00, 0x00, 0x00,                         // DW_AT_macros
+
+        0x10, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x02,                                           // abbreviation code
+        0x00, 0x00, 0x00, 0x00,                         // DW_AT_stmt_list
+        0x00, 0x00, 0x00, 0x00,                         // DW_AT_macro_info
+
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x03,                                           // abbreviation code
+        0x00, 0x00, 0x00, 0x00,                         // DW_AT_stmt_list
+    ];
+    let debug_macro = [
+        0x05, 0x00, 0x00,                               // version 5, no flags
+        0x01, 0x01, b'X', b' ', b'1', 0x00,             // DW_MACRO_define 1 "X 1"
+        0x00,
+
+        0x05, 0x00, 0x00,                               // version 5, no flags
+        0x03, 0x00, 0x01,                               // DW_MACRO_start_file 0, foo.c
+        0x07, 0x00, 0x00, 0x00, 0x00,                   // DW_MACRO_import 0
+        0x01, 0x02, b'Y', 0x00,                         // DW_MACRO_define 2 "Y"
+        0x07, 0x0a, 0x00, 0x00, 0x00,                   // DW_MACRO_import, a cycle
+        0x04,                                           // DW_MACRO_end_file
+        0x00,
+    ];
+    let debug_macinfo = [
+        0x03, 0x00, 0x01,                               // DW_MACINFO_start_file 0, foo.c
+        0x01, 0x01, b'Z', 0x00,                         // DW_MACINFO_define 1 "Z"
+        0x04,                                           // DW_MACINFO_end_file
+        0x00,
+    ];
+
+    let debug_line = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian);
+    let debug_macinfo = DebugMacinfo::new(&debug_macinfo);
+    let debug_macro = DebugMacro::new(&debug_macro, LittleEndian);
+    let debug_str = DebugStr::new(&[]);
+    let debug_str_offsets = DebugStrOffsets::new(&[], LittleEndian);
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let mut units = CompilationUnitsIter::new(&debug_info, LittleEndian);
+    let mut next_table = || {
+        let unit = units.next().expect("Should have a unit").expect("Should parse the unit");
+        MacroTable::for_unit(&debug_line, &debug_macinfo, &debug_macro, &unit, &abbrevs)
+            .expect("Should build the table")
+    };
+
+    let table = next_table().expect("Should have macro information");
+    assert_eq!(table.entries().len(), 4);
+    let names = |table: &MacroTable<LittleEndian>, line| {
+        table.defined_at(b"foo.c", line, &debug_str, &debug_str_offsets, &debug_str)
+            .expect("Should read the entries")
+            .expect("Should find the file")
+            .iter()
+            .map(|definition| definition.name().to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&table, 2), vec![b"X".to_vec()]);
+    assert_eq!(names(&table, 3), vec![b"X".to_vec(), b"Y".to_vec()]);
+
+    let table = next_table().expect("Should have macro information");
+    assert_eq!(names(&table, 2), vec![b"Z".to_vec()]);
+
+    assert!(next_table().is_none());
+}
+
+#[test]
+fn test_macro_table_defined_at_strx() {
+    let debug_info = [
+        0x08, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x01,                                           // DW_UT_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+    ];
+    let debug_str_offsets = [
+        0x10, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x00, 0x00,                                     // padding
+        0x01, 0x00, 0x00, 0x00,                         // "A 1"
+        0x05, 0x00, 0x00, 0x00,                         // "B 2"
+        0x09, 0x00, 0x00, 0x00,                         // "A"
+    ];
+    let debug_str = DebugStr::new(b"\0A 1\0B 2\0A\0");
+    let debug_str_offsets = DebugStrOffsets::new(&debug_str_offsets, LittleEndian);
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    let header = DebugLine::new(&TEST_DEBUG_LINE_V4, LittleEndian)
+        .header(0, 4)
+        .expect("Should parse the header");
+    let table = |undef| {
+        let entries = vec![MacroEntry::StartFile { line: 0, file: 1 },
+                           MacroEntry::Define {
+                               line: 1,
+                               text: AttributeValue::DebugStrOffsetsIndex(0),
+                           },
+                           MacroEntry::Define {
+                               line: 2,
+                               text: AttributeValue::DebugStrOffsetsIndex(1),
+                           },
+                           MacroEntry::Undef { line: 3, name: undef },
+                           MacroEntry::EndFile];
+        MacroTable::new(header.clone(), entries)
+    };
+    let strx_table = table(AttributeValue::DebugStrOffsetsIndex(2)).with_str_offsets(unit, 8);
+    let names = |line| {
+        strx_table.defined_at(b"foo.c", line, &debug_str, &debug_str_offsets, &debug_str)
+            .expect("Should read the entries")
+            .expect("Should find the file")
+            .iter()
+            .map(|definition| definition.name())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(3), vec![&b"A"[..], b"B"]);
+    assert_eq!(names(4), vec![&b"B"[..]]);
+
+    // An undefinition that can not be read is an error, rather than leaving a
+    // stale definition in scope.
+    let bad_table = table(AttributeValue::DebugStrOffsetsIndex(3)).with_str_offsets(unit, 8);
+    match bad_table.defined_at(b"foo.c", 4, &debug_str, &debug_str_offsets, &debug_str) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    let sup_table = table(AttributeValue::DebugStrRefSup(9)).with_str_offsets(unit, 8);
+    match sup_table.defined_at(b"foo.c", 4, &debug_str, &debug_str_offsets, &debug_str) {
+        Err(Error::UnsupportedMacroString) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    let no_base_table = table(AttributeValue::DebugStrOffsetsIndex(2));
+    match no_base_table.defined_at(b"foo.c", 2, &debug_str, &debug_str_offsets, &debug_str) {
+        Err(Error::MissingStrOffsetsBase) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
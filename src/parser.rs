@@ -1,10 +1,18 @@
 //! Functions for parsing DWARF debugging information.
 
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian, RunTimeEndian};
+use endianity::Endianity;
 use leb128;
-use nom::{self, Err, ErrorKind, IResult, le_u8, le_u16, le_u32, le_u64, Needed};
+use nom::{self, Err, ErrorKind, IResult, le_u8, Needed};
+use std::collections::hash_map;
+use std::error;
 use std::fmt;
+use std::rc::Rc;
+use debug_addr::DebugAddr;
+use debug_str::DebugStr;
 use types::{Abbreviation, AbbreviationHasChildren, Abbreviations, AbbreviationTag, AttributeForm,
-            AttributeName, AttributeSpecification, CompilationUnitHeader};
+            AttributeName, AttributeSpecification, CompilationUnitHeader, UnitType};
 
 /// A parse error.
 #[derive(Debug)]
@@ -15,20 +23,19 @@ pub enum Error {
     /// Zero is an illegal value for an abbreviation code.
     AbbreviationCodeZero,
 
-    /// The abbreviation's tag is not a known variant of `AbbreviationTag` (aka
-    /// `DW_TAG_*`).
+    /// The abbreviation's tag is zero, which is not a valid `DW_TAG_*` value.
     InvalidAbbreviationTag,
 
     /// The abbreviation's "does the abbreviated type have children?" byte was
     /// not one of `DW_CHILDREN_yes` or `DW_CHILDREN_no`.
     InvalidAbbreviationHasChildren,
 
-    /// The abbreviation's attribute name is not a valid variant of
-    /// `AttributeName` (aka `DW_AT_*`).
+    /// The abbreviation's attribute name is zero, which is not a valid
+    /// `DW_AT_*` value.
     InvalidAttributeName,
 
-    /// The abbreviation's attribute form is not a valid variant of
-    /// `AttributeForm` (aka `DW_FORM_*`).
+    /// An entry has an attribute whose form (aka `DW_FORM_*`) we do not know
+    /// how to decode, or that is not valid in the unit's DWARF version.
     InvalidAttributeForm,
 
     /// Expected a zero byte, but did not find one.
@@ -56,6 +63,84 @@ pub enum Error {
 
     /// The reported DWARF version is a version we do not know how to parse.
     UnknownDwarfVersion,
+
+    /// The unit uses the 64-bit DWARF format, which its DWARF version does not
+    /// support.
+    UnsupportedDwarf64Version,
+
+    /// The unit type of a DWARF 5 unit header is one we do not know how to
+    /// parse.
+    UnknownUnitType,
+
+    /// Found a debugging information entry whose abbreviation code is not in
+    /// the compilation unit's set of abbreviations.
+    UnknownAbbreviation,
+
+    /// The input ended before we were finished parsing.
+    UnexpectedEof,
+
+    /// The compilation unit's address size is not one that we support.
+    UnsupportedAddressSize,
+
+    /// The segment selector size is not one that we support.
+    UnsupportedSegmentSize,
+
+    /// An offset pointed outside the bounds of the section it refers to.
+    OffsetOutOfBounds,
+
+    /// A string in a string section was not null-terminated.
+    UnterminatedString,
+
+    /// A line number program header's `line_range` is zero, so special opcodes
+    /// cannot be decoded.
+    LineRangeZero,
+
+    /// A line number program header's `opcode_base` is zero, so it has no room
+    /// for extended opcodes.
+    OpcodeBaseZero,
+
+    /// A line number program header's `maximum_operations_per_instruction` is
+    /// zero.
+    MaximumOperationsPerInstructionZero,
+
+    /// Found a location list entry whose kind we do not know how to parse.
+    UnknownLocationListEntry,
+
+    /// Found a macro entry whose type we do not know how to parse.
+    UnknownMacroType,
+
+    /// A DWARF 5 line number program header's directory or file name entry
+    /// format does not have a `DW_LNCT_path` field.
+    MissingFileEntryFormatPath,
+
+    /// A DWARF 5 line number program header's directory or file name entry
+    /// format does not consume any input, so the entry count is unbounded.
+    EmptyFileEntryFormat,
+
+    /// A unit that is not a split DWARF unit does not have a
+    /// `DW_AT_str_offsets_base` attribute.
+    MissingStrOffsetsBase,
+
+    /// A macro entry's string lives in a section that can not be read, such
+    /// as the `.debug_str` section of a supplementary object file.
+    UnsupportedMacroString,
+
+    /// An entry refers to its unit's table of addresses in the `.debug_addr`
+    /// section, but the unit does not have a `DW_AT_addr_base` attribute.
+    MissingAddrBase,
+
+    /// An attribute has a form that can not represent the value we need from
+    /// it, such as a `DW_AT_low_pc` that is not an address.
+    UnsupportedAttributeForm,
+
+    /// A compilation unit's macro entries, together with the entries of the
+    /// `.debug_macro` units that it imports, are more than we are willing to
+    /// read.
+    TooManyMacroEntries,
+
+    /// One of the underlying `nom` combinators failed without a more specific
+    /// error.
+    NomError,
 }
 
 impl fmt::Display for Error {
@@ -64,7 +149,7 @@ impl fmt::Display for Error {
     }
 }
 
-impl ::std::error::Error for Error {
+impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::LebError(_) =>
@@ -78,7 +163,7 @@ impl ::std::error::Error for Error {
             Error::InvalidAttributeName =>
                 "The abbreviation's attribute name is invalid",
             Error::InvalidAttributeForm =>
-                "The abbreviation's attribute form is invalid",
+                "Found an attribute with a form that we do not know how to decode",
             Error::ExpectedZero =>
                 "Expected zero",
             Error::DuplicateAbbreviationCode =>
@@ -95,10 +180,52 @@ impl ::std::error::Error for Error {
                 "Unkown reserved compilation unit length value found",
             Error::UnknownDwarfVersion =>
                 "The DWARF version is a version that we do not know how to parse",
+            Error::UnsupportedDwarf64Version =>
+                "The 64-bit DWARF format is not supported by the unit's DWARF version",
+            Error::UnknownUnitType =>
+                "The unit type is a type that we do not know how to parse",
+            Error::UnknownAbbreviation =>
+                "Found a debugging information entry with an unknown abbreviation code",
+            Error::UnexpectedEof =>
+                "Hit the end of input before it was expected",
+            Error::UnsupportedAddressSize =>
+                "The address size is not supported",
+            Error::UnsupportedSegmentSize =>
+                "The segment selector size is not supported",
+            Error::OffsetOutOfBounds =>
+                "An offset pointed outside the bounds of its section",
+            Error::UnterminatedString =>
+                "Found a string that is not null-terminated",
+            Error::LineRangeZero =>
+                "The line number program header's line_range is zero",
+            Error::OpcodeBaseZero =>
+                "The line number program header's opcode_base is zero",
+            Error::MaximumOperationsPerInstructionZero =>
+                "The line number program header's maximum_operations_per_instruction is zero",
+            Error::UnknownLocationListEntry =>
+                "Found a location list entry of an unknown kind",
+            Error::UnknownMacroType =>
+                "Found a macro entry of an unknown type",
+            Error::MissingFileEntryFormatPath =>
+                "A directory or file name entry format is missing DW_LNCT_path",
+            Error::EmptyFileEntryFormat =>
+                "A directory or file name entry format does not consume any input",
+            Error::MissingStrOffsetsBase =>
+                "A unit that is not split DWARF does not have a DW_AT_str_offsets_base",
+            Error::UnsupportedMacroString =>
+                "A macro entry's string lives in a section that can not be read",
+            Error::MissingAddrBase =>
+                "An entry refers to the .debug_addr section but its unit has no DW_AT_addr_base",
+            Error::UnsupportedAttributeForm =>
+                "An attribute has a form that can not represent the value we need from it",
+            Error::TooManyMacroEntries =>
+                "A unit's macro entries, with the entries it imports, are too many to read",
+            Error::NomError =>
+                "An underlying nom combinator failed",
         }
     }
 
-    fn cause(&self) -> Option<&::std::error::Error> {
+    fn cause(&self) -> Option<&dyn error::Error> {
         match *self {
             Error::LebError(ref e) => Some(e),
             Error::AbbreviationCodeZero => None,
@@ -114,6 +241,27 @@ impl ::std::error::Error for Error {
             Error::ExpectedUnsigned64 => None,
             Error::UnkownReservedCompilationUnitLength => None,
             Error::UnknownDwarfVersion => None,
+            Error::UnsupportedDwarf64Version => None,
+            Error::UnknownUnitType => None,
+            Error::UnknownAbbreviation => None,
+            Error::UnexpectedEof => None,
+            Error::UnsupportedAddressSize => None,
+            Error::UnsupportedSegmentSize => None,
+            Error::OffsetOutOfBounds => None,
+            Error::UnterminatedString => None,
+            Error::LineRangeZero => None,
+            Error::OpcodeBaseZero => None,
+            Error::MaximumOperationsPerInstructionZero => None,
+            Error::UnknownLocationListEntry => None,
+            Error::UnknownMacroType => None,
+            Error::MissingFileEntryFormatPath => None,
+            Error::EmptyFileEntryFormat => None,
+            Error::MissingStrOffsetsBase => None,
+            Error::UnsupportedMacroString => None,
+            Error::MissingAddrBase => None,
+            Error::UnsupportedAttributeForm => None,
+            Error::TooManyMacroEntries => None,
+            Error::NomError => None,
         }
     }
 }
@@ -121,8 +269,22 @@ impl ::std::error::Error for Error {
 /// The result of an attempted parse.
 pub type ParseResult<Input, T> = IResult<Input, T, Error>;
 
+/// Convert the result of a `nom` style parse into a plain `Result`, for the
+/// higher level APIs that aren't written in terms of `nom`.
+pub(crate) fn into_result<Input, T>(result: ParseResult<Input, T>) -> Result<(Input, T), Error> {
+    match result {
+        IResult::Done(rest, val) => Ok((rest, val)),
+        IResult::Incomplete(_) => Err(Error::UnexpectedEof),
+        IResult::Error(Err::Code(ErrorKind::Custom(e))) |
+        IResult::Error(Err::Node(ErrorKind::Custom(e), _)) |
+        IResult::Error(Err::Position(ErrorKind::Custom(e), _)) |
+        IResult::Error(Err::NodePosition(ErrorKind::Custom(e), _, _)) => Err(e),
+        IResult::Error(_) => Err(Error::NomError),
+    }
+}
+
 /// Parse an unsigned LEB128 encoded integer.
-fn parse_unsigned_leb(mut input: &[u8]) -> ParseResult<&[u8], u64> {
+pub(crate) fn parse_unsigned_leb(mut input: &[u8]) -> ParseResult<&[u8], u64> {
     match leb128::read::unsigned(&mut input) {
         Ok(val) =>
             IResult::Done(input, val),
@@ -133,21 +295,97 @@ fn parse_unsigned_leb(mut input: &[u8]) -> ParseResult<&[u8], u64> {
     }
 }
 
-// /// TODO FITZGEN
-// fn parse_signed_leb(mut input: &[u8]) -> ParseResult<&[u8], i64> {
-//     match leb128::read::signed(&mut input) {
-//         Ok(val) =>
-//             IResult::Done(input, val),
-//         Err(leb128::read::Error::UnexpectedEndOfData) =>
-//             IResult::Incomplete(Needed::Unknown),
-//         Err(e) =>
-//             IResult::Error(Err::Position(ErrorKind::Custom(Error::LebError(e)), input)),
-//     }
-// }
+/// Parse an unsigned 8 bit integer.
+pub(crate) fn parse_u8(input: &[u8]) -> ParseResult<&[u8], u8> {
+    match le_u8(input) {
+        IResult::Done(rest, val) => IResult::Done(rest, val),
+        IResult::Error(_) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedUnsigned8), input)),
+        IResult::Incomplete(needed) => IResult::Incomplete(needed),
+    }
+}
+
+/// Parse an unsigned 16 bit integer in the given byte order.
+pub(crate) fn parse_u16<Endian>(input: &[u8], endian: Endian) -> ParseResult<&[u8], u16>
+    where Endian: Endianity
+{
+    if input.len() < 2 {
+        IResult::Incomplete(Needed::Size(2))
+    } else {
+        IResult::Done(&input[2..], endian.read_u16(input))
+    }
+}
+
+/// Parse an unsigned 24 bit integer in the given byte order.
+fn parse_u24<Endian>(input: &[u8], endian: Endian) -> ParseResult<&[u8], u32>
+    where Endian: Endianity
+{
+    if input.len() < 3 {
+        IResult::Incomplete(Needed::Size(3))
+    } else if endian.is_big_endian() {
+        let val = (input[0] as u32) << 16 | (input[1] as u32) << 8 | input[2] as u32;
+        IResult::Done(&input[3..], val)
+    } else {
+        let val = (input[2] as u32) << 16 | (input[1] as u32) << 8 | input[0] as u32;
+        IResult::Done(&input[3..], val)
+    }
+}
+
+/// Parse an unsigned 32 bit integer in the given byte order.
+pub(crate) fn parse_u32<Endian>(input: &[u8], endian: Endian) -> ParseResult<&[u8], u32>
+    where Endian: Endianity
+{
+    if input.len() < 4 {
+        IResult::Incomplete(Needed::Size(4))
+    } else {
+        IResult::Done(&input[4..], endian.read_u32(input))
+    }
+}
+
+/// Parse an unsigned 64 bit integer in the given byte order.
+pub(crate) fn parse_u64<Endian>(input: &[u8], endian: Endian) -> ParseResult<&[u8], u64>
+    where Endian: Endianity
+{
+    if input.len() < 8 {
+        IResult::Incomplete(Needed::Size(8))
+    } else {
+        IResult::Done(&input[8..], endian.read_u64(input))
+    }
+}
+
+/// Take the given number of bytes from the front of the input.
+pub(crate) fn take(input: &[u8], length: u64) -> ParseResult<&[u8], &[u8]> {
+    if (input.len() as u64) < length {
+        IResult::Incomplete(Needed::Size(length as usize))
+    } else {
+        let length = length as usize;
+        IResult::Done(&input[length..], &input[..length])
+    }
+}
+
+/// Parse a null-terminated string, returning it without its terminator.
+pub(crate) fn parse_null_terminated_string(input: &[u8]) -> ParseResult<&[u8], &[u8]> {
+    match input.iter().position(|&b| b == 0) {
+        Some(idx) => IResult::Done(&input[idx + 1..], &input[..idx]),
+        None => IResult::Incomplete(Needed::Unknown),
+    }
+}
+
+/// Parse a signed LEB128 encoded integer.
+pub(crate) fn parse_signed_leb(mut input: &[u8]) -> ParseResult<&[u8], i64> {
+    match leb128::read::signed(&mut input) {
+        Ok(val) =>
+            IResult::Done(input, val),
+        Err(leb128::read::Error::UnexpectedEndOfData) =>
+            IResult::Incomplete(Needed::Unknown),
+        Err(e) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::LebError(e)), input)),
+    }
+}
 
 /// Parse an abbreviation's code.
-fn parse_abbreviation_code(mut input: &[u8]) -> ParseResult<&[u8], u64> {
-    match parse_unsigned_leb(&mut input) {
+fn parse_abbreviation_code(input: &[u8]) -> ParseResult<&[u8], u64> {
+    match parse_unsigned_leb(input) {
         IResult::Done(input, val) =>
             if val == 0 {
                 IResult::Error(Err::Position(ErrorKind::Custom(Error::AbbreviationCodeZero),
@@ -161,197 +399,16 @@ fn parse_abbreviation_code(mut input: &[u8]) -> ParseResult<&[u8], u64> {
 }
 
 /// Parse an abbreviation's tag.
-fn parse_abbreviation_tag(mut input: &[u8]) -> ParseResult<&[u8], AbbreviationTag> {
-    match parse_unsigned_leb(&mut input) {
-        IResult::Done(input, val) if AbbreviationTag::ArrayType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ArrayType),
-
-        IResult::Done(input, val) if AbbreviationTag::ClassType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ClassType),
-
-        IResult::Done(input, val) if AbbreviationTag::EntryPoint as u64 == val =>
-            IResult::Done(input, AbbreviationTag::EntryPoint),
-
-        IResult::Done(input, val) if AbbreviationTag::EnumerationType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::EnumerationType),
-
-        IResult::Done(input, val) if AbbreviationTag::FormalParameter as u64 == val =>
-            IResult::Done(input, AbbreviationTag::FormalParameter),
-
-        IResult::Done(input, val) if AbbreviationTag::ImportedDeclaration as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ImportedDeclaration),
-
-        IResult::Done(input, val) if AbbreviationTag::Label as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Label),
-
-        IResult::Done(input, val) if AbbreviationTag::LexicalBlock as u64 == val =>
-            IResult::Done(input, AbbreviationTag::LexicalBlock),
-
-        IResult::Done(input, val) if AbbreviationTag::Member as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Member),
-
-        IResult::Done(input, val) if AbbreviationTag::PointerType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::PointerType),
-
-        IResult::Done(input, val) if AbbreviationTag::ReferenceType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ReferenceType),
-
-        IResult::Done(input, val) if AbbreviationTag::CompileUnit as u64 == val =>
-            IResult::Done(input, AbbreviationTag::CompileUnit),
-
-        IResult::Done(input, val) if AbbreviationTag::StringType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::StringType),
-
-        IResult::Done(input, val) if AbbreviationTag::StructureType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::StructureType),
-
-        IResult::Done(input, val) if AbbreviationTag::SubroutineType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::SubroutineType),
-
-        IResult::Done(input, val) if AbbreviationTag::Typedef as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Typedef),
-
-        IResult::Done(input, val) if AbbreviationTag::UnionType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::UnionType),
-
-        IResult::Done(input, val) if AbbreviationTag::UnspecifiedParameters as u64 == val =>
-            IResult::Done(input, AbbreviationTag::UnspecifiedParameters),
-
-        IResult::Done(input, val) if AbbreviationTag::Variant as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Variant),
-
-        IResult::Done(input, val) if AbbreviationTag::CommonBlock as u64 == val =>
-            IResult::Done(input, AbbreviationTag::CommonBlock),
-
-        IResult::Done(input, val) if AbbreviationTag::CommonInclusion as u64 == val =>
-            IResult::Done(input, AbbreviationTag::CommonInclusion),
-
-        IResult::Done(input, val) if AbbreviationTag::Inheritance as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Inheritance),
-
-        IResult::Done(input, val) if AbbreviationTag::InlinedSubroutine as u64 == val =>
-            IResult::Done(input, AbbreviationTag::InlinedSubroutine),
-
-        IResult::Done(input, val) if AbbreviationTag::Module as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Module),
-
-        IResult::Done(input, val) if AbbreviationTag::PtrToMemberType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::PtrToMemberType),
-
-        IResult::Done(input, val) if AbbreviationTag::SetType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::SetType),
-
-        IResult::Done(input, val) if AbbreviationTag::SubrangeType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::SubrangeType),
-
-        IResult::Done(input, val) if AbbreviationTag::WithStmt as u64 == val =>
-            IResult::Done(input, AbbreviationTag::WithStmt),
-
-        IResult::Done(input, val) if AbbreviationTag::AccessDeclaration as u64 == val =>
-            IResult::Done(input, AbbreviationTag::AccessDeclaration),
-
-        IResult::Done(input, val) if AbbreviationTag::BaseType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::BaseType),
-
-        IResult::Done(input, val) if AbbreviationTag::CatchBlock as u64 == val =>
-            IResult::Done(input, AbbreviationTag::CatchBlock),
-
-        IResult::Done(input, val) if AbbreviationTag::ConstType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ConstType),
-
-        IResult::Done(input, val) if AbbreviationTag::Constant as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Constant),
-
-        IResult::Done(input, val) if AbbreviationTag::Enumerator as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Enumerator),
-
-        IResult::Done(input, val) if AbbreviationTag::FileType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::FileType),
-
-        IResult::Done(input, val) if AbbreviationTag::Friend as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Friend),
-
-        IResult::Done(input, val) if AbbreviationTag::Namelist as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Namelist),
-
-        IResult::Done(input, val) if AbbreviationTag::NamelistItem as u64 == val =>
-            IResult::Done(input, AbbreviationTag::NamelistItem),
-
-        IResult::Done(input, val) if AbbreviationTag::PackedType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::PackedType),
-
-        IResult::Done(input, val) if AbbreviationTag::Subprogram as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Subprogram),
-
-        IResult::Done(input, val) if AbbreviationTag::TemplateTypeParameter as u64 == val =>
-            IResult::Done(input, AbbreviationTag::TemplateTypeParameter),
-
-        IResult::Done(input, val) if AbbreviationTag::TemplateValueParameter as u64 == val =>
-            IResult::Done(input, AbbreviationTag::TemplateValueParameter),
-
-        IResult::Done(input, val) if AbbreviationTag::ThrownType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ThrownType),
-
-        IResult::Done(input, val) if AbbreviationTag::TryBlock as u64 == val =>
-            IResult::Done(input, AbbreviationTag::TryBlock),
-
-        IResult::Done(input, val) if AbbreviationTag::VariantPart as u64 == val =>
-            IResult::Done(input, AbbreviationTag::VariantPart),
-
-        IResult::Done(input, val) if AbbreviationTag::Variable as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Variable),
-
-        IResult::Done(input, val) if AbbreviationTag::VolatileType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::VolatileType),
-
-        IResult::Done(input, val) if AbbreviationTag::RestrictType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::RestrictType),
-
-        IResult::Done(input, val) if AbbreviationTag::InterfaceType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::InterfaceType),
-
-        IResult::Done(input, val) if AbbreviationTag::Namespace as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Namespace),
-
-        IResult::Done(input, val) if AbbreviationTag::ImportedModule as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ImportedModule),
-
-        IResult::Done(input, val) if AbbreviationTag::UnspecifiedType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::UnspecifiedType),
-
-        IResult::Done(input, val) if AbbreviationTag::PartialUnit as u64 == val =>
-            IResult::Done(input, AbbreviationTag::PartialUnit),
-
-        IResult::Done(input, val) if AbbreviationTag::ImportedUnit as u64 == val =>
-            IResult::Done(input, AbbreviationTag::ImportedUnit),
-
-        IResult::Done(input, val) if AbbreviationTag::Condition as u64 == val =>
-            IResult::Done(input, AbbreviationTag::Condition),
-
-        IResult::Done(input, val) if AbbreviationTag::SharedType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::SharedType),
-
-        IResult::Done(input, val) if AbbreviationTag::TypeUnit as u64 == val =>
-            IResult::Done(input, AbbreviationTag::TypeUnit),
-
-        IResult::Done(input, val) if AbbreviationTag::RvalueReferenceType as u64 == val =>
-            IResult::Done(input, AbbreviationTag::RvalueReferenceType),
-
-        IResult::Done(input, val) if AbbreviationTag::TemplateAlias as u64 == val =>
-            IResult::Done(input, AbbreviationTag::TemplateAlias),
-
-        IResult::Done(input, val) if AbbreviationTag::LoUser as u64 == val =>
-            IResult::Done(input, AbbreviationTag::LoUser),
-
-        IResult::Done(input, val) if AbbreviationTag::HiUser as u64 == val =>
-            IResult::Done(input, AbbreviationTag::HiUser),
-
-        IResult::Done(input, _) =>
+///
+/// Any non-zero value is accepted, whether or not we know about it.
+fn parse_abbreviation_tag(input: &[u8]) -> ParseResult<&[u8], AbbreviationTag> {
+    match parse_unsigned_leb(input) {
+        IResult::Done(_, 0) =>
             IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAbbreviationTag), input)),
-
+        IResult::Done(rest, val) =>
+            IResult::Done(rest, AbbreviationTag(val)),
         IResult::Incomplete(needed) =>
             IResult::Incomplete(needed),
-
         IResult::Error(error) =>
             IResult::Error(error),
     }
@@ -379,519 +436,372 @@ fn parse_abbreviation_has_children(input: &[u8]) -> ParseResult<&[u8], Abbreviat
 }
 
 /// Parse an attribute's name.
+///
+/// Any non-zero value is accepted, whether or not we know about it.
 fn parse_attribute_name(input: &[u8]) -> ParseResult<&[u8], AttributeName> {
     match parse_unsigned_leb(input) {
-        IResult::Done(input, val) if AttributeName::Sibling as u64 == val =>
-            IResult::Done(input, AttributeName::Sibling),
-
-        IResult::Done(input, val) if AttributeName::Location as u64 == val =>
-            IResult::Done(input, AttributeName::Location),
+        IResult::Done(_, 0) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeName), input)),
+        IResult::Done(rest, val) =>
+            IResult::Done(rest, AttributeName(val)),
+        IResult::Incomplete(needed) =>
+            IResult::Incomplete(needed),
+        IResult::Error(error) =>
+            IResult::Error(error),
+    }
+}
 
-        IResult::Done(input, val) if AttributeName::Name as u64 == val =>
-            IResult::Done(input, AttributeName::Name),
+/// Parse an attribute's form.
+///
+/// Any value is accepted, whether or not we know about it. Forms that we don't
+/// know how to decode are only an error once an entry uses them.
+pub(crate) fn parse_attribute_form(input: &[u8]) -> ParseResult<&[u8], AttributeForm> {
+    let (rest, val) = try_parse!(input, parse_unsigned_leb);
+    IResult::Done(rest, AttributeForm(val))
+}
 
-        IResult::Done(input, val) if AttributeName::Ordering as u64 == val =>
-            IResult::Done(input, AttributeName::Ordering),
+/// Parse a non-null attribute specification.
+///
+/// A `DW_FORM_implicit_const` specification is followed by its value, as a
+/// signed LEB128 encoded integer.
+fn parse_attribute_specification(input: &[u8]) -> ParseResult<&[u8], AttributeSpecification> {
+    let (rest, name) = try_parse!(input, parse_attribute_name);
+    let (rest, form) = try_parse!(rest, parse_attribute_form);
+    if form == AttributeForm::ImplicitConst {
+        let (rest, value) = try_parse!(rest, parse_signed_leb);
+        IResult::Done(rest, AttributeSpecification::new_implicit_const(name, value))
+    } else {
+        IResult::Done(rest, AttributeSpecification::new(name, form))
+    }
+}
 
-        IResult::Done(input, val) if AttributeName::ByteSize as u64 == val =>
-            IResult::Done(input, AttributeName::ByteSize),
+#[test]
+fn test_parse_attribute_specification_implicit_const() {
+    // DW_AT_decl_file as DW_FORM_implicit_const with value -3, followed by
+    // DW_AT_name as DW_FORM_strx1.
+    let buf = [0x3a, 0x21, 0x7d, 0x03, 0x25];
+
+    let rest = match parse_attribute_specification(&buf) {
+        IResult::Done(rest, spec) => {
+            assert_eq!(spec,
+                       AttributeSpecification::new_implicit_const(AttributeName::DeclFile, -3));
+            assert_eq!(spec.form(), AttributeForm::ImplicitConst);
+            assert_eq!(spec.implicit_const_value(), Some(-3));
+            rest
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
 
-        IResult::Done(input, val) if AttributeName::BitOffset as u64 == val =>
-            IResult::Done(input, AttributeName::BitOffset),
+    match parse_attribute_specification(rest) {
+        IResult::Done(rest, spec) => {
+            assert_eq!(rest.len(), 0);
+            assert_eq!(spec,
+                       AttributeSpecification::new(AttributeName::Name, AttributeForm::Strx1));
+            assert_eq!(spec.implicit_const_value(), None);
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
 
-        IResult::Done(input, val) if AttributeName::BitSize as u64 == val =>
-            IResult::Done(input, AttributeName::BitSize),
+/// Parse the null attribute specification.
+fn parse_null_attribute_specification(input: &[u8]) -> ParseResult<&[u8], ()> {
+    let (input1, name) = try_parse!(input, parse_unsigned_leb);
+    if name != 0 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input));
+    }
 
-        IResult::Done(input, val) if AttributeName::StmtList as u64 == val =>
-            IResult::Done(input, AttributeName::StmtList),
+    let (input2, form) = try_parse!(input1, parse_unsigned_leb);
+    if form != 0 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input1));
+    }
 
-        IResult::Done(input, val) if AttributeName::LowPc as u64 == val =>
-            IResult::Done(input, AttributeName::LowPc),
+    IResult::Done(input2, ())
+}
 
-        IResult::Done(input, val) if AttributeName::HighPc as u64 == val =>
-            IResult::Done(input, AttributeName::HighPc),
+/// Parse a series of attribute specifications, terminated by a null attribute
+/// specification, appending them to `results`.
+fn parse_attribute_specifications<'input>(mut input: &'input [u8],
+                                          results: &mut Vec<AttributeSpecification>)
+                                          -> ParseResult<&'input [u8], ()> {
+    // There has to be a better way to keep parsing attributes until we see two
+    // 0 LEB128s, but take_until!/take_while! aren't quite expressive enough for
+    // this case.
 
-        IResult::Done(input, val) if AttributeName::Language as u64 == val =>
-            IResult::Done(input, AttributeName::Language),
+    loop {
+        let (input1, attribute) = try_parse!(
+            input,
+            alt!(parse_null_attribute_specification => { |_| None } |
+                 parse_attribute_specification      => { Some }));
 
-        IResult::Done(input, val) if AttributeName::Discr as u64 == val =>
-            IResult::Done(input, AttributeName::Discr),
+        input = input1;
 
-        IResult::Done(input, val) if AttributeName::DiscrValue as u64 == val =>
-            IResult::Done(input, AttributeName::DiscrValue),
+        match attribute {
+            None => break,
+            Some(attr) => results.push(attr),
+        };
+    }
 
-        IResult::Done(input, val) if AttributeName::Visibility as u64 == val =>
-            IResult::Done(input, AttributeName::Visibility),
+    IResult::Done(input, ())
+}
 
-        IResult::Done(input, val) if AttributeName::Import as u64 == val =>
-            IResult::Done(input, AttributeName::Import),
+/// Parse a non-null abbreviation.
+///
+/// The abbreviation's attributes are parsed into the given scratch buffer,
+/// which is cleared first, so that parsing many abbreviations doesn't need an
+/// allocation for each of them.
+fn parse_abbreviation<'input, 'attrs>(input: &'input [u8],
+                                      attributes: &'attrs mut Vec<AttributeSpecification>)
+                                      -> ParseResult<&'input [u8], Abbreviation<'attrs>> {
+    attributes.clear();
+    let (input, code) = try_parse!(input, parse_abbreviation_code);
+    let (input, tag) = try_parse!(input, parse_abbreviation_tag);
+    let (input, has_children) = try_parse!(input, parse_abbreviation_has_children);
+    let (input, _) = try_parse!(input, call!(parse_attribute_specifications, attributes));
+    IResult::Done(input, Abbreviation::new(code, tag, has_children, attributes))
+}
 
-        IResult::Done(input, val) if AttributeName::StringLength as u64 == val =>
-            IResult::Done(input, AttributeName::StringLength),
+/// Parse a null abbreviation.
+fn parse_null_abbreviation(input: &[u8]) -> ParseResult<&[u8], ()> {
+    let (input1, name) = try_parse!(input, parse_unsigned_leb);
+    if name == 0 {
+        IResult::Done(input1, ())
+    } else {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input))
+    }
 
-        IResult::Done(input, val) if AttributeName::CommonReference as u64 == val =>
-            IResult::Done(input, AttributeName::CommonReference),
+}
 
-        IResult::Done(input, val) if AttributeName::CompDir as u64 == val =>
-            IResult::Done(input, AttributeName::CompDir),
+/// Parse a series of abbreviations, terminated by a null abbreviation.
+pub fn parse_abbreviations(mut input: &[u8]) -> ParseResult<&[u8], Abbreviations> {
+    // Again with the super funky keep-parsing-X-while-we-can't-parse-a-Y
+    // thing... This should definitely be abstracted out.
 
-        IResult::Done(input, val) if AttributeName::ConstValue as u64 == val =>
-            IResult::Done(input, AttributeName::ConstValue),
+    let mut results = Abbreviations::new();
+    let mut attributes = Vec::new();
 
-        IResult::Done(input, val) if AttributeName::ContainingType as u64 == val =>
-            IResult::Done(input, AttributeName::ContainingType),
+    loop {
+        let (input1, abbrev) = try_parse!(input,
+                                          alt!(parse_null_abbreviation => { |_| None } |
+                                               call!(parse_abbreviation, &mut attributes) =>
+                                                   { Some }));
 
-        IResult::Done(input, val) if AttributeName::DefaultValue as u64 == val =>
-            IResult::Done(input, AttributeName::DefaultValue),
+        match abbrev {
+            None => {
+                input = input1;
+                break;
+            },
+            Some(abbrev) => {
+                match results.insert(abbrev) {
+                    Ok(_) =>
+                        input = input1,
+                    Err(_) =>
+                        return IResult::Error(
+                            Err::Position(
+                                ErrorKind::Custom(Error::DuplicateAbbreviationCode),
+                                input)),
+                }
+            }
+        }
+    }
 
-        IResult::Done(input, val) if AttributeName::Inline as u64 == val =>
-            IResult::Done(input, AttributeName::Inline),
+    IResult::Done(input, results)
+}
 
-        IResult::Done(input, val) if AttributeName::IsOptional as u64 == val =>
-            IResult::Done(input, AttributeName::IsOptional),
+/// Parse the series of abbreviations found at the given offset into the
+/// `.debug_abbrev` section, such as a `CompilationUnitHeader`'s
+/// `debug_abbrev_offset`.
+pub fn parse_abbreviations_at(debug_abbrev: &[u8], offset: u64) -> Result<Abbreviations, Error> {
+    if (debug_abbrev.len() as u64) < offset {
+        return Err(Error::OffsetOutOfBounds);
+    }
 
-        IResult::Done(input, val) if AttributeName::LowerBound as u64 == val =>
-            IResult::Done(input, AttributeName::LowerBound),
+    into_result(parse_abbreviations(&debug_abbrev[offset as usize..])).map(|(_, abbrevs)| abbrevs)
+}
 
-        IResult::Done(input, val) if AttributeName::Producer as u64 == val =>
-            IResult::Done(input, AttributeName::Producer),
+/// A cache of parsed abbreviations from the `.debug_abbrev` section, keyed by
+/// their offset into the section.
+///
+/// Many compilation units typically share the same set of abbreviations, and
+/// this cache ensures that each set is only parsed once.
+#[derive(Debug, Clone)]
+pub struct AbbreviationsCache<'input> {
+    debug_abbrev: &'input [u8],
+    abbrevs: hash_map::HashMap<u64, Rc<Abbreviations>>,
+}
 
-        IResult::Done(input, val) if AttributeName::Prototyped as u64 == val =>
-            IResult::Done(input, AttributeName::Prototyped),
+impl<'input> AbbreviationsCache<'input> {
+    /// Construct a new, empty cache for the given `.debug_abbrev` section.
+    pub fn new(debug_abbrev: &'input [u8]) -> AbbreviationsCache<'input> {
+        AbbreviationsCache {
+            debug_abbrev,
+            abbrevs: hash_map::HashMap::new(),
+        }
+    }
 
-        IResult::Done(input, val) if AttributeName::ReturnAddr as u64 == val =>
-            IResult::Done(input, AttributeName::ReturnAddr),
+    /// Get the abbreviations at the given offset into the `.debug_abbrev`
+    /// section, parsing them if they are not already in the cache.
+    pub fn get(&mut self, offset: u64) -> Result<Rc<Abbreviations>, Error> {
+        match self.abbrevs.entry(offset) {
+            hash_map::Entry::Occupied(entry) =>
+                Ok(entry.get().clone()),
+            hash_map::Entry::Vacant(entry) => {
+                let abbrevs = Rc::new(parse_abbreviations_at(self.debug_abbrev, offset)?);
+                Ok(entry.insert(abbrevs).clone())
+            },
+        }
+    }
 
-        IResult::Done(input, val) if AttributeName::StartScope as u64 == val =>
-            IResult::Done(input, AttributeName::StartScope),
+    /// Get the abbreviations used by the compilation unit with the given
+    /// header.
+    pub fn get_for_unit(&mut self, header: &CompilationUnitHeader)
+                        -> Result<Rc<Abbreviations>, Error> {
+        self.get(header.debug_abbrev_offset())
+    }
 
-        IResult::Done(input, val) if AttributeName::BitStride as u64 == val =>
-            IResult::Done(input, AttributeName::BitStride),
+    /// Get the number of distinct sets of abbreviations in the cache.
+    pub fn len(&self) -> usize {
+        self.abbrevs.len()
+    }
 
-        IResult::Done(input, val) if AttributeName::UpperBound as u64 == val =>
-            IResult::Done(input, AttributeName::UpperBound),
+    /// Return true if the cache is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.abbrevs.is_empty()
+    }
+}
 
-        IResult::Done(input, val) if AttributeName::AbstractOrigin as u64 == val =>
-            IResult::Done(input, AttributeName::AbstractOrigin),
+#[cfg(test)]
+const TEST_DEBUG_ABBREV: [u8; 15] = [
+    0xff, 0xff,             // Padding, so that the first set isn't at offset 0.
 
-        IResult::Done(input, val) if AttributeName::Accessibility as u64 == val =>
-            IResult::Done(input, AttributeName::Accessibility),
+    // Offset 2: code 1 is a DW_TAG_base_type with no children, and code 2 is
+    // a DW_TAG_member with no children and DW_AT_name as DW_FORM_string.
+    0x01, 0x24, 0x00, 0x00, 0x00,
+    0x02, 0x0d, 0x00, 0x03, 0x08, 0x00, 0x00,
+    0x00,
+];
 
-        IResult::Done(input, val) if AttributeName::AddressClass as u64 == val =>
-            IResult::Done(input, AttributeName::AddressClass),
+#[test]
+fn test_parse_abbreviations_at() {
+    let buf = TEST_DEBUG_ABBREV;
 
-        IResult::Done(input, val) if AttributeName::Artificial as u64 == val =>
-            IResult::Done(input, AttributeName::Artificial),
+    let abbrevs = parse_abbreviations_at(&buf, 2).expect("Should parse the abbreviations");
+    assert_eq!(abbrevs.len(), 2);
+    assert!(!abbrevs.is_empty());
 
-        IResult::Done(input, val) if AttributeName::BaseTypes as u64 == val =>
-            IResult::Done(input, AttributeName::BaseTypes),
+    let base_type = abbrevs.get(1).expect("Should have code 1");
+    assert_eq!(base_type.tag(), AbbreviationTag::BaseType);
+    assert!(!base_type.has_children());
+    assert!(base_type.attributes().is_empty());
 
-        IResult::Done(input, val) if AttributeName::CallingConvention as u64 == val =>
-            IResult::Done(input, AttributeName::CallingConvention),
+    let member = abbrevs.get(2).expect("Should have code 2");
+    assert_eq!(member.tag(), AbbreviationTag::Member);
+    assert_eq!(member.attributes(),
+               &[AttributeSpecification::new(AttributeName::Name, AttributeForm::String)]);
 
-        IResult::Done(input, val) if AttributeName::Count as u64 == val =>
-            IResult::Done(input, AttributeName::Count),
+    assert!(abbrevs.get(3).is_none());
 
-        IResult::Done(input, val) if AttributeName::DataMemberLocation as u64 == val =>
-            IResult::Done(input, AttributeName::DataMemberLocation),
+    let codes: Vec<_> = abbrevs.iter().map(|abbrev| abbrev.code()).collect();
+    assert_eq!(codes, vec![1, 2]);
 
-        IResult::Done(input, val) if AttributeName::DeclColumn as u64 == val =>
-            IResult::Done(input, AttributeName::DeclColumn),
+    match parse_abbreviations_at(&buf, 100) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
 
-        IResult::Done(input, val) if AttributeName::DeclFile as u64 == val =>
-            IResult::Done(input, AttributeName::DeclFile),
+#[test]
+fn test_abbreviations_cache() {
+    let buf = TEST_DEBUG_ABBREV;
+
+    let mut cache = AbbreviationsCache::new(&buf);
+    assert!(cache.is_empty());
+
+    let first = cache.get(2).expect("Should parse the abbreviations at offset 2");
+    let header = CompilationUnitHeader::new(7, 4, 2, 8);
+    let second = cache.get_for_unit(&header).expect("Should find the cached abbreviations");
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+
+    // Offset 7 is the start of the second abbreviation, which is also a valid
+    // (if unusual) place for a set of abbreviations to begin.
+    let third = cache.get(7).expect("Should parse the abbreviations at offset 7");
+    assert!(!Rc::ptr_eq(&first, &third));
+    assert_eq!(third.len(), 1);
+    assert_eq!(cache.len(), 2);
+}
 
-        IResult::Done(input, val) if AttributeName::DeclLine as u64 == val =>
-            IResult::Done(input, AttributeName::DeclLine),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Unknown,
+    Dwarf64,
+    Dwarf32,
+}
 
-        IResult::Done(input, val) if AttributeName::Declaration as u64 == val =>
-            IResult::Done(input, AttributeName::Declaration),
+impl Format {
+    /// The size (in bytes) of the initial length field of a unit in this
+    /// format.
+    pub(crate) fn initial_length_size(&self) -> u8 {
+        match *self {
+            Format::Unknown =>
+                panic!("Need to know if this is 32- or 64-bit DWARF to know the initial length size"),
+            Format::Dwarf32 => 4,
+            Format::Dwarf64 => 12,
+        }
+    }
 
-        IResult::Done(input, val) if AttributeName::DiscrList as u64 == val =>
-            IResult::Done(input, AttributeName::DiscrList),
+    /// The size (in bytes) of section offsets in this format.
+    pub(crate) fn offset_size(&self) -> u8 {
+        match *self {
+            Format::Unknown =>
+                panic!("Need to know if this is 32- or 64-bit DWARF to know the offset size"),
+            Format::Dwarf32 => 4,
+            Format::Dwarf64 => 8,
+        }
+    }
+}
 
-        IResult::Done(input, val) if AttributeName::Encoding as u64 == val =>
-            IResult::Done(input, AttributeName::Encoding),
+/// Parse a section offset, whose size depends on the format.
+pub(crate) fn parse_offset<Endian>(input: &[u8],
+                                   format: Format,
+                                   endian: Endian)
+                                   -> ParseResult<&[u8], u64>
+    where Endian: Endianity
+{
+    match format {
+        Format::Unknown =>
+            panic!("Need to know if this is 32- or 64-bit DWARF to parse an offset"),
+        Format::Dwarf32 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, offset as u64)
+        },
+        Format::Dwarf64 =>
+            parse_u64(input, endian),
+    }
+}
 
-        IResult::Done(input, val) if AttributeName::External as u64 == val =>
-            IResult::Done(input, AttributeName::External),
-
-        IResult::Done(input, val) if AttributeName::FrameBase as u64 == val =>
-            IResult::Done(input, AttributeName::FrameBase),
-
-        IResult::Done(input, val) if AttributeName::Friend as u64 == val =>
-            IResult::Done(input, AttributeName::Friend),
-
-        IResult::Done(input, val) if AttributeName::IdentifierCase as u64 == val =>
-            IResult::Done(input, AttributeName::IdentifierCase),
-
-        IResult::Done(input, val) if AttributeName::MacroInfo as u64 == val =>
-            IResult::Done(input, AttributeName::MacroInfo),
-
-        IResult::Done(input, val) if AttributeName::NamelistItem as u64 == val =>
-            IResult::Done(input, AttributeName::NamelistItem),
-
-        IResult::Done(input, val) if AttributeName::Priority as u64 == val =>
-            IResult::Done(input, AttributeName::Priority),
-
-        IResult::Done(input, val) if AttributeName::Segment as u64 == val =>
-            IResult::Done(input, AttributeName::Segment),
-
-        IResult::Done(input, val) if AttributeName::Specification as u64 == val =>
-            IResult::Done(input, AttributeName::Specification),
-
-        IResult::Done(input, val) if AttributeName::StaticLink as u64 == val =>
-            IResult::Done(input, AttributeName::StaticLink),
-
-        IResult::Done(input, val) if AttributeName::Type as u64 == val =>
-            IResult::Done(input, AttributeName::Type),
-
-        IResult::Done(input, val) if AttributeName::UseLocation as u64 == val =>
-            IResult::Done(input, AttributeName::UseLocation),
-
-        IResult::Done(input, val) if AttributeName::VariableParameter as u64 == val =>
-            IResult::Done(input, AttributeName::VariableParameter),
-
-        IResult::Done(input, val) if AttributeName::Virtuality as u64 == val =>
-            IResult::Done(input, AttributeName::Virtuality),
-
-        IResult::Done(input, val) if AttributeName::VtableElemLocation as u64 == val =>
-            IResult::Done(input, AttributeName::VtableElemLocation),
-
-        IResult::Done(input, val) if AttributeName::Allocated as u64 == val =>
-            IResult::Done(input, AttributeName::Allocated),
-
-        IResult::Done(input, val) if AttributeName::Associated as u64 == val =>
-            IResult::Done(input, AttributeName::Associated),
-
-        IResult::Done(input, val) if AttributeName::DataLocation as u64 == val =>
-            IResult::Done(input, AttributeName::DataLocation),
-
-        IResult::Done(input, val) if AttributeName::ByteStride as u64 == val =>
-            IResult::Done(input, AttributeName::ByteStride),
-
-        IResult::Done(input, val) if AttributeName::EntryPc as u64 == val =>
-            IResult::Done(input, AttributeName::EntryPc),
-
-        IResult::Done(input, val) if AttributeName::UseUtf8 as u64 == val =>
-            IResult::Done(input, AttributeName::UseUtf8),
-
-        IResult::Done(input, val) if AttributeName::Extension as u64 == val =>
-            IResult::Done(input, AttributeName::Extension),
-
-        IResult::Done(input, val) if AttributeName::Ranges as u64 == val =>
-            IResult::Done(input, AttributeName::Ranges),
-
-        IResult::Done(input, val) if AttributeName::Trampoline as u64 == val =>
-            IResult::Done(input, AttributeName::Trampoline),
-
-        IResult::Done(input, val) if AttributeName::CallColumn as u64 == val =>
-            IResult::Done(input, AttributeName::CallColumn),
-
-        IResult::Done(input, val) if AttributeName::CallFile as u64 == val =>
-            IResult::Done(input, AttributeName::CallFile),
-
-        IResult::Done(input, val) if AttributeName::CallLine as u64 == val =>
-            IResult::Done(input, AttributeName::CallLine),
-
-        IResult::Done(input, val) if AttributeName::Description as u64 == val =>
-            IResult::Done(input, AttributeName::Description),
-
-        IResult::Done(input, val) if AttributeName::BinaryScale as u64 == val =>
-            IResult::Done(input, AttributeName::BinaryScale),
-
-        IResult::Done(input, val) if AttributeName::DecimalScale as u64 == val =>
-            IResult::Done(input, AttributeName::DecimalScale),
-
-        IResult::Done(input, val) if AttributeName::Small as u64 == val =>
-            IResult::Done(input, AttributeName::Small),
-
-        IResult::Done(input, val) if AttributeName::DecimalSign as u64 == val =>
-            IResult::Done(input, AttributeName::DecimalSign),
-
-        IResult::Done(input, val) if AttributeName::DigitCount as u64 == val =>
-            IResult::Done(input, AttributeName::DigitCount),
-
-        IResult::Done(input, val) if AttributeName::PictureString as u64 == val =>
-            IResult::Done(input, AttributeName::PictureString),
-
-        IResult::Done(input, val) if AttributeName::Mutable as u64 == val =>
-            IResult::Done(input, AttributeName::Mutable),
-
-        IResult::Done(input, val) if AttributeName::ThreadsScaled as u64 == val =>
-            IResult::Done(input, AttributeName::ThreadsScaled),
-
-        IResult::Done(input, val) if AttributeName::Explicit as u64 == val =>
-            IResult::Done(input, AttributeName::Explicit),
-
-        IResult::Done(input, val) if AttributeName::ObjectPointer as u64 == val =>
-            IResult::Done(input, AttributeName::ObjectPointer),
-
-        IResult::Done(input, val) if AttributeName::Endianity as u64 == val =>
-            IResult::Done(input, AttributeName::Endianity),
-
-        IResult::Done(input, val) if AttributeName::Elemental as u64 == val =>
-            IResult::Done(input, AttributeName::Elemental),
-
-        IResult::Done(input, val) if AttributeName::Pure as u64 == val =>
-            IResult::Done(input, AttributeName::Pure),
-
-        IResult::Done(input, val) if AttributeName::Recursive as u64 == val =>
-            IResult::Done(input, AttributeName::Recursive),
-
-        IResult::Done(input, val) if AttributeName::Signature as u64 == val =>
-            IResult::Done(input, AttributeName::Signature),
-
-        IResult::Done(input, val) if AttributeName::MainSubprogram as u64 == val =>
-            IResult::Done(input, AttributeName::MainSubprogram),
-
-        IResult::Done(input, val) if AttributeName::DataBitOffset as u64 == val =>
-            IResult::Done(input, AttributeName::DataBitOffset),
-
-        IResult::Done(input, val) if AttributeName::ConstExpr as u64 == val =>
-            IResult::Done(input, AttributeName::ConstExpr),
-
-        IResult::Done(input, val) if AttributeName::EnumClass as u64 == val =>
-            IResult::Done(input, AttributeName::EnumClass),
-
-        IResult::Done(input, val) if AttributeName::LinkageName as u64 == val =>
-            IResult::Done(input, AttributeName::LinkageName),
-
-        IResult::Done(input, val) if AttributeName::LoUser as u64 == val =>
-            IResult::Done(input, AttributeName::LoUser),
-
-        IResult::Done(input, val) if AttributeName::HiUser as u64 == val =>
-            IResult::Done(input, AttributeName::HiUser),
-
-        IResult::Done(input, _) =>
-            IResult::Error(
-                Err::Position(ErrorKind::Custom(Error::InvalidAttributeName), input)),
-
-        IResult::Incomplete(needed) =>
-            IResult::Incomplete(needed),
-
-        IResult::Error(error) =>
-            IResult::Error(error),
-    }
-}
-
-/// Parse an attribute's form.
-fn parse_attribute_form(input: &[u8]) -> ParseResult<&[u8], AttributeForm> {
-    match parse_unsigned_leb(input) {
-        IResult::Done(input, val) if AttributeForm::Addr as u64 == val =>
-            IResult::Done(input, AttributeForm::Addr),
-
-        IResult::Done(input, val) if AttributeForm::Block2 as u64 == val =>
-            IResult::Done(input, AttributeForm::Block2),
-
-        IResult::Done(input, val) if AttributeForm::Block4 as u64 == val =>
-            IResult::Done(input, AttributeForm::Block4),
-
-        IResult::Done(input, val) if AttributeForm::Data2 as u64 == val =>
-            IResult::Done(input, AttributeForm::Data2),
-
-        IResult::Done(input, val) if AttributeForm::Data4 as u64 == val =>
-            IResult::Done(input, AttributeForm::Data4),
-
-        IResult::Done(input, val) if AttributeForm::Data8 as u64 == val =>
-            IResult::Done(input, AttributeForm::Data8),
-
-        IResult::Done(input, val) if AttributeForm::String as u64 == val =>
-            IResult::Done(input, AttributeForm::String),
-
-        IResult::Done(input, val) if AttributeForm::Block as u64 == val =>
-            IResult::Done(input, AttributeForm::Block),
-
-        IResult::Done(input, val) if AttributeForm::Block1 as u64 == val =>
-            IResult::Done(input, AttributeForm::Block1),
-
-        IResult::Done(input, val) if AttributeForm::Data1 as u64 == val =>
-            IResult::Done(input, AttributeForm::Data1),
-
-        IResult::Done(input, val) if AttributeForm::Flag as u64 == val =>
-            IResult::Done(input, AttributeForm::Flag),
-
-        IResult::Done(input, val) if AttributeForm::Sdata as u64 == val =>
-            IResult::Done(input, AttributeForm::Sdata),
-
-        IResult::Done(input, val) if AttributeForm::Strp as u64 == val =>
-            IResult::Done(input, AttributeForm::Strp),
-
-        IResult::Done(input, val) if AttributeForm::Udata as u64 == val =>
-            IResult::Done(input, AttributeForm::Udata),
-
-        IResult::Done(input, val) if AttributeForm::RefAddr as u64 == val =>
-            IResult::Done(input, AttributeForm::RefAddr),
-
-        IResult::Done(input, val) if AttributeForm::Ref1 as u64 == val =>
-            IResult::Done(input, AttributeForm::Ref1),
-
-        IResult::Done(input, val) if AttributeForm::Ref2 as u64 == val =>
-            IResult::Done(input, AttributeForm::Ref2),
-
-        IResult::Done(input, val) if AttributeForm::Ref4 as u64 == val =>
-            IResult::Done(input, AttributeForm::Ref4),
-
-        IResult::Done(input, val) if AttributeForm::Ref8 as u64 == val =>
-            IResult::Done(input, AttributeForm::Ref8),
-
-        IResult::Done(input, val) if AttributeForm::RefUdata as u64 == val =>
-            IResult::Done(input, AttributeForm::RefUdata),
-
-        IResult::Done(input, val) if AttributeForm::Indirect as u64 == val =>
-            IResult::Done(input, AttributeForm::Indirect),
-
-        IResult::Done(input, val) if AttributeForm::SecOffset as u64 == val =>
-            IResult::Done(input, AttributeForm::SecOffset),
-
-        IResult::Done(input, val) if AttributeForm::Exprloc as u64 == val =>
-            IResult::Done(input, AttributeForm::Exprloc),
-
-        IResult::Done(input, val) if AttributeForm::FlagPresent as u64 == val =>
-            IResult::Done(input, AttributeForm::FlagPresent),
-
-        IResult::Done(input, val) if AttributeForm::RefSig8 as u64 == val =>
-            IResult::Done(input, AttributeForm::RefSig8),
-
-        IResult::Done(input, _) =>
-            IResult::Error(
-                Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), input)),
-
-        IResult::Incomplete(needed) =>
-            IResult::Incomplete(needed),
-
-        IResult::Error(error) =>
-            IResult::Error(error),
-    }
-}
-
-/// Parse a non-null attribute specification.
-fn parse_attribute_specification(input: &[u8]) -> ParseResult<&[u8], AttributeSpecification> {
-    chain!(input,
-           name: parse_attribute_name ~
-           form: parse_attribute_form,
-           || AttributeSpecification::new(name, form))
-}
-
-/// Parse the null attribute specification.
-fn parse_null_attribute_specification(input: &[u8]) -> ParseResult<&[u8], ()> {
-    let (input1, name) = try_parse!(input, parse_unsigned_leb);
-    if name != 0 {
-        return IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input));
-    }
-
-    let (input2, form) = try_parse!(input1, parse_unsigned_leb);
-    if form != 0 {
-        return IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input1));
-    }
-
-    IResult::Done(input2, ())
-}
-
-/// Parse a series of attribute specifications, terminated by a null attribute
-/// specification.
-fn parse_attribute_specifications(mut input: &[u8]) -> ParseResult<&[u8], Vec<AttributeSpecification>> {
-    // There has to be a better way to keep parsing attributes until we see two
-    // 0 LEB128s, but take_until!/take_while! aren't quite expressive enough for
-    // this case.
-
-    let mut results = Vec::new();
-
-    loop {
-        let (input1, attribute) = try_parse!(
-            input,
-            alt!(parse_null_attribute_specification => { |_| None } |
-                 parse_attribute_specification      => { |a| Some(a) }));
-
-        input = input1;
-
-        match attribute {
-            None => break,
-            Some(attr) => results.push(attr),
-        };
-    }
-
-    IResult::Done(input, results)
-}
-
-/// Parse a non-null abbreviation.
-fn parse_abbreviation(input: &[u8]) -> ParseResult<&[u8], Abbreviation> {
-    chain!(input,
-           code: parse_abbreviation_code ~
-           tag: parse_abbreviation_tag ~
-           has_children: parse_abbreviation_has_children ~
-           attributes: parse_attribute_specifications,
-           || Abbreviation::new(code, tag, has_children, attributes))
-}
-
-/// Parse a null abbreviation.
-fn parse_null_abbreviation(input: &[u8]) -> ParseResult<&[u8], ()> {
-    let (input1, name) = try_parse!(input, parse_unsigned_leb);
-    if name == 0 {
-        IResult::Done(input1, ())
-    } else {
-        IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedZero), input))
-    }
-
-}
-
-/// Parse a series of abbreviations, terminated by a null abbreviation.
-pub fn parse_abbreviations(mut input: &[u8]) -> ParseResult<&[u8], Abbreviations> {
-    // Again with the super funky keep-parsing-X-while-we-can't-parse-a-Y
-    // thing... This should definitely be abstracted out.
-
-    let mut results = Abbreviations::new();
-
-    loop {
-        let (input1, abbrev) = try_parse!(input,
-                                          alt!(parse_null_abbreviation => { |_| None } |
-                                               parse_abbreviation      => { |a| Some(a) }));
-
-        match abbrev {
-            None => break,
-            Some(abbrev) => {
-                match results.insert(abbrev) {
-                    Ok(_) =>
-                        input = input1,
-                    Err(_) =>
-                        return IResult::Error(
-                            Err::Position(
-                                ErrorKind::Custom(Error::DuplicateAbbreviationCode),
-                                input)),
-                }
-            }
-        }
-    }
-
-    IResult::Done(input, results)
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Format {
-    Unknown,
-    Dwarf64,
-    Dwarf32,
-}
-
-/// The input to parsing debugging information.
+/// The input to parsing debugging information: the data of the `.debug_info`
+/// section, along with its byte order.
 ///
-/// To parse debugging information, we need to have the abbreviations that
-/// describe the shape of the debugging information entries. Use
-/// `parse_abbreviations` to get that information from the `.debug_abbrevs`
-/// section, and then pass that here.
+/// Parsing a unit header also records whether the unit is in the 32- or
+/// 64-bit format, which the rest of the unit depends on.
 #[derive(Debug, Clone, Copy)]
-pub struct DebugInfoInput<'a>(&'a [u8], &'a Abbreviations, Format);
+pub struct DebugInfoInput<'a, Endian>(&'a [u8], Format, Endian)
+    where Endian: Endianity;
 
-impl<'a> nom::InputLength for DebugInfoInput<'a> {
+impl<'a, Endian> nom::InputLength for DebugInfoInput<'a, Endian>
+    where Endian: Endianity
+{
     fn input_len(&self) -> usize {
         self.0.len()
     }
 }
 
-impl<'a> DebugInfoInput<'a> {
+impl<'a, Endian> DebugInfoInput<'a, Endian>
+    where Endian: Endianity
+{
     /// Construct a new `DebugInfoInput`.
-    pub fn new(input: &'a [u8], abbrevs: &'a Abbreviations) -> DebugInfoInput<'a> {
-        DebugInfoInput(input, abbrevs, Format::Unknown)
+    pub fn new(input: &'a [u8], endian: Endian) -> DebugInfoInput<'a, Endian> {
+        DebugInfoInput(input, Format::Unknown, endian)
     }
 }
 
@@ -899,24 +809,22 @@ const MAX_DWARF_32_UNIT_LENGTH: u32 = 0xfffffff0;
 
 const DWARF_64_INITIAL_UNIT_LENGTH: u32 = 0xffffffff;
 
-/// Parse the compilation unit header's length.
-fn parse_unit_length(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u64> {
-    match le_u32(input.0) {
+/// Parse the initial length field that starts every unit in the DWARF
+/// sections, which also determines whether the unit is in the 32- or 64-bit
+/// format.
+pub(crate) fn parse_initial_length<Endian>(input: &[u8],
+                                           endian: Endian)
+                                           -> ParseResult<&[u8], (u64, Format)>
+    where Endian: Endianity
+{
+    match parse_u32(input, endian) {
         IResult::Done(rest, val) if val < MAX_DWARF_32_UNIT_LENGTH =>
-            IResult::Done(DebugInfoInput(rest, input.1, Format::Dwarf32),
-                          val as u64),
-
-        IResult::Done(rest1, val) if val == DWARF_64_INITIAL_UNIT_LENGTH =>
-            match le_u64(rest1) {
-                IResult::Done(rest2, val) =>
-                    IResult::Done(DebugInfoInput(rest2, input.1, Format::Dwarf64), val),
-                IResult::Error(_) =>
-                    IResult::Error(Err::Position(
-                        ErrorKind::Custom(Error::ExpectedUnsigned64),
-                        DebugInfoInput(rest1, input.1, input.2))),
-                IResult::Incomplete(needed) =>
-                    IResult::Incomplete(needed),
-            },
+            IResult::Done(rest, (val as u64, Format::Dwarf32)),
+
+        IResult::Done(rest1, val) if val == DWARF_64_INITIAL_UNIT_LENGTH => {
+            let (rest2, val) = try_parse!(rest1, call!(parse_u64, endian));
+            IResult::Done(rest2, (val, Format::Dwarf64))
+        },
 
         IResult::Done(_, _) =>
             IResult::Error(Err::Position(
@@ -931,19 +839,34 @@ fn parse_unit_length(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u64>
     }
 }
 
+/// Parse the compilation unit header's length.
+fn parse_unit_length<Endian>(input: DebugInfoInput<Endian>)
+                             -> ParseResult<DebugInfoInput<Endian>, u64>
+    where Endian: Endianity
+{
+    match parse_initial_length(input.0, input.2) {
+        IResult::Done(rest, (length, format)) =>
+            IResult::Done(DebugInfoInput(rest, format, input.2), length),
+        IResult::Error(Err::Position(kind, _)) =>
+            IResult::Error(Err::Position(kind, input)),
+        IResult::Error(_) =>
+            IResult::Error(Err::Code(ErrorKind::Custom(Error::NomError))),
+        IResult::Incomplete(needed) =>
+            IResult::Incomplete(needed),
+    }
+}
+
 #[test]
 fn test_parse_unit_length_32_ok() {
     let buf = [0x12, 0x34, 0x56, 0x78];
-    let abbrevs = Abbreviations::new();
 
-    match parse_unit_length(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
+    match parse_unit_length(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
         IResult::Done(rest, length) => {
             assert_eq!(rest.0.len(), 0);
-            assert_eq!(rest.2, Format::Dwarf32);
+            assert_eq!(rest.1, Format::Dwarf32);
             assert_eq!(0x78563412, length);
         },
-        _ =>
-            assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     }
 }
 
@@ -951,41 +874,35 @@ fn test_parse_unit_length_32_ok() {
 fn test_parse_unit_length_64_ok() {
     let buf = [0xff, 0xff, 0xff, 0xff, // DWARF_64_INITIAL_UNIT_LENGTH
                0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xff]; // Actual length
-    let abbrevs = Abbreviations::new();
 
-    match parse_unit_length(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
+    match parse_unit_length(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
         IResult::Done(rest, length) => {
             assert_eq!(rest.0.len(), 0);
-            assert_eq!(rest.2, Format::Dwarf64);
+            assert_eq!(rest.1, Format::Dwarf64);
             assert_eq!(0xffdebc9a78563412, length);
         },
-        _ =>
-            assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     }
 }
 
 #[test]
 fn test_parse_compilation_unit_unknown_reserved_value() {
     let buf = [0xfe, 0xff, 0xff, 0xff];
-    let abbrevs = Abbreviations::new();
 
-    match parse_unit_length(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
+    match parse_unit_length(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
         IResult::Error(Err::Position(ErrorKind::Custom(Error::UnkownReservedCompilationUnitLength),
-                                     _)) =>
-            assert!(true),
-        _ =>
-            assert!(false),
+                                     _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_parse_unit_length_incomplete() {
     let buf = [0xff, 0xff, 0xff]; // Need at least 4 bytes.
-    let abbrevs = Abbreviations::new();
 
-    match parse_unit_length(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
-        IResult::Incomplete(_) => assert!(true),
-        _ => assert!(false),
+    match parse_unit_length(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
@@ -993,18 +910,19 @@ fn test_parse_unit_length_incomplete() {
 fn test_parse_unit_length_64_incomplete() {
     let buf = [0xff, 0xff, 0xff, 0xff, // DWARF_64_INITIAL_UNIT_LENGTH
                0x12, 0x34, 0x56, 0x78, ]; // Actual length is not long enough
-    let abbrevs = Abbreviations::new();
 
-    match parse_unit_length(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
-        IResult::Incomplete(_) => assert!(true),
-        _ => assert!(false),
+    match parse_unit_length(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 /// Parse the DWARF version from the compilation unit header.
-fn parse_version(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u16> {
-    match le_u16(input.0) {
-        IResult::Done(rest, val) if 1 <= val && val <= 4 =>
+fn parse_version<Endian>(input: DebugInfoInput<Endian>) -> ParseResult<DebugInfoInput<Endian>, u16>
+    where Endian: Endianity
+{
+    match parse_u16(input.0, input.2) {
+        IResult::Done(rest, val) if (2..=5).contains(&val) =>
             IResult::Done(DebugInfoInput(rest, input.1, input.2), val),
 
         IResult::Done(_, _) =>
@@ -1023,115 +941,109 @@ fn parse_version(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u16> {
 #[test]
 fn test_compilation_unit_version_ok() {
     let buf = [0x04, 0x00, 0xff, 0xff]; // Version 4 and two extra bytes
-    let abbrevs = Abbreviations::new();
 
-    match parse_version(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
+    match parse_version(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
         IResult::Done(rest, val) => {
             assert_eq!(val, 4);
             assert_eq!(rest.0, &[0xff, 0xff]);
         },
-        _ =>
-            assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_compilation_unit_version_unknown_version() {
     let buf = [0xab, 0xcd];
-    let abbrevs = Abbreviations::new();
 
-    match parse_version(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
-        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion), _)) =>
-            assert!(true),
-        _ =>
-            assert!(false),
+    match parse_version(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_compilation_unit_version_one() {
+    let buf = [0x01, 0x00];
+
+    match parse_version(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_compilation_unit_version_incomplete() {
     let buf = [0x04];
-    let abbrevs = Abbreviations::new();
 
-    match parse_version(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
-        IResult::Incomplete(_) =>
-            assert!(true),
-        _ =>
-            assert!(false),
+    match parse_version(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 /// Parse the debug_abbrev_offset in the compilation unit header.
-fn parse_debug_abbrev_offset(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u64> {
-    match input.2 {
-        Format::Unknown =>
-            panic!("Need to know if this is 32- or 64-bit DWARF to parse the debug_abbrev_offset"),
-        Format::Dwarf32 =>
-            match le_u32(input.0) {
-                IResult::Done(rest, offset) =>
-                    IResult::Done(DebugInfoInput(rest, input.1, input.2),
-                                  offset as u64),
-                IResult::Error(_) =>
-                    IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedUnsigned32),
-                                                 input)),
-                IResult::Incomplete(needed) =>
-                    IResult::Incomplete(needed),
-            },
-        Format::Dwarf64 =>
-            match le_u64(input.0) {
-                IResult::Done(rest, offset) =>
-                    IResult::Done(DebugInfoInput(rest, input.1, input.2),
-                                  offset),
-                IResult::Error(_) =>
-                    IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedUnsigned64),
-                                                 input)),
-                IResult::Incomplete(needed) =>
-                    IResult::Incomplete(needed),
-            },
+fn parse_debug_abbrev_offset<Endian>(input: DebugInfoInput<Endian>)
+                                     -> ParseResult<DebugInfoInput<Endian>, u64>
+    where Endian: Endianity
+{
+    parse_unit_offset(input)
+}
+
+/// Parse an offset in the compilation unit header, whose size depends on the
+/// format.
+fn parse_unit_offset<Endian>(input: DebugInfoInput<Endian>)
+                             -> ParseResult<DebugInfoInput<Endian>, u64>
+    where Endian: Endianity
+{
+    match parse_offset(input.0, input.1, input.2) {
+        IResult::Done(rest, offset) =>
+            IResult::Done(DebugInfoInput(rest, input.1, input.2), offset),
+        IResult::Error(Err::Position(kind, _)) =>
+            IResult::Error(Err::Position(kind, input)),
+        IResult::Error(_) =>
+            IResult::Error(Err::Code(ErrorKind::Custom(Error::NomError))),
+        IResult::Incomplete(needed) =>
+            IResult::Incomplete(needed),
     }
 }
 
 #[test]
 fn test_parse_debug_abbrev_offset_32() {
     let buf = [0x01, 0x02, 0x03, 0x04];
-    let abbrevs = Abbreviations::new();
 
-    match parse_debug_abbrev_offset(DebugInfoInput(&buf, &abbrevs, Format::Dwarf32)) {
+    match parse_debug_abbrev_offset(DebugInfoInput(&buf, Format::Dwarf32, LittleEndian)) {
         IResult::Done(_, val) => assert_eq!(val, 0x04030201),
-        _ => assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_parse_debug_abbrev_offset_32_incomplete() {
     let buf = [0x01, 0x02];
-    let abbrevs = Abbreviations::new();
 
-    match parse_debug_abbrev_offset(DebugInfoInput(&buf, &abbrevs, Format::Dwarf32)) {
-        IResult::Incomplete(_) => assert!(true),
-        _ => assert!(false),
+    match parse_debug_abbrev_offset(DebugInfoInput(&buf, Format::Dwarf32, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_parse_debug_abbrev_offset_64() {
     let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
-    let abbrevs = Abbreviations::new();
 
-    match parse_debug_abbrev_offset(DebugInfoInput(&buf, &abbrevs, Format::Dwarf64)) {
+    match parse_debug_abbrev_offset(DebugInfoInput(&buf, Format::Dwarf64, LittleEndian)) {
         IResult::Done(_, val) => assert_eq!(val, 0x0807060504030201),
-        _ => assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
 #[test]
 fn test_parse_debug_abbrev_offset_64_incomplete() {
     let buf = [0x01, 0x02];
-    let abbrevs = Abbreviations::new();
 
-    match parse_debug_abbrev_offset(DebugInfoInput(&buf, &abbrevs, Format::Dwarf64)) {
-        IResult::Incomplete(_) => assert!(true),
-        _ => assert!(false),
+    match parse_debug_abbrev_offset(DebugInfoInput(&buf, Format::Dwarf64, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
@@ -1139,13 +1051,15 @@ fn test_parse_debug_abbrev_offset_64_incomplete() {
 #[should_panic]
 fn test_parse_debug_abbrev_offset_unknown() {
     let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
-    let abbrevs = Abbreviations::new();
 
-    parse_debug_abbrev_offset(DebugInfoInput(&buf, &abbrevs, Format::Unknown));
+    parse_debug_abbrev_offset(DebugInfoInput(&buf, Format::Unknown, LittleEndian));
 }
 
 /// Parse the size of addresses (in bytes) on the target architecture.
-fn parse_address_size(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u8> {
+fn parse_address_size<Endian>(input: DebugInfoInput<Endian>)
+                              -> ParseResult<DebugInfoInput<Endian>, u8>
+    where Endian: Endianity
+{
     match le_u8(input.0) {
         IResult::Done(rest, val) =>
             IResult::Done(DebugInfoInput(rest, input.1, input.2), val),
@@ -1160,65 +1074,2869 @@ fn parse_address_size(input: DebugInfoInput) -> ParseResult<DebugInfoInput, u8>
 #[test]
 fn test_parse_address_size_ok() {
     let buf = [0x04];
-    let abbrevs = Abbreviations::new();
 
-    match parse_address_size(DebugInfoInput(&buf, &abbrevs, Format::Unknown)) {
+    match parse_address_size(DebugInfoInput(&buf, Format::Unknown, LittleEndian)) {
         IResult::Done(_, val) => assert_eq!(val, 4),
-        _ => assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
     };
 }
 
-/// Parse a compilation unit header.
-pub fn parse_compilation_unit_header(input: DebugInfoInput)
-                                     -> ParseResult<DebugInfoInput, CompilationUnitHeader>
+/// Parse the unit type from a DWARF 5 unit header.
+fn parse_unit_type<Endian>(input: DebugInfoInput<Endian>)
+                           -> ParseResult<DebugInfoInput<Endian>, UnitType>
+    where Endian: Endianity
 {
-    chain!(input,
-           unit_length: parse_unit_length ~
-           version: parse_version ~
-           offset: parse_debug_abbrev_offset ~
-           address_size: parse_address_size,
-           || CompilationUnitHeader::new(unit_length,
-                                         version,
-                                         offset,
-                                         address_size))
+    let known = UnitType::Compile.0..=UnitType::SplitType.0;
+    match le_u8(input.0) {
+        IResult::Done(rest, val) if known.contains(&val) =>
+            IResult::Done(DebugInfoInput(rest, input.1, input.2), UnitType(val)),
+        IResult::Done(_, _) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownUnitType), input)),
+        IResult::Error(_) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedUnsigned8),
+                                         input)),
+        IResult::Incomplete(needed) =>
+            IResult::Incomplete(needed),
+    }
 }
 
 #[test]
-fn test_parse_compilation_unit_header_32_ok() {
-    let buf = [
-        0x01, 0x02, 0x03, 0x04, // 32-bit unit length
-        0x04, 0x00,             // version 4
-        0x05, 0x06, 0x07, 0x08, // debug_abbrev_offset
-        0x04                    // address size
-    ];
-    let abbrevs = Abbreviations::new();
+fn test_parse_unit_type_unknown() {
+    let buf = [0x80];
 
-    match parse_compilation_unit_header(DebugInfoInput::new(&buf, &abbrevs)) {
-        IResult::Done(_, header) =>
-            assert_eq!(header, CompilationUnitHeader::new(0x04030201, 4, 0x08070605, 4)),
-        _ =>
-            assert!(false),
-    }
+    match parse_unit_type(DebugInfoInput(&buf, Format::Dwarf32, LittleEndian)) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownUnitType), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
 }
 
-#[test]
-fn test_parse_compilation_unit_header_64_ok() {
-    let buf = [
-        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
-        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // unit length
+/// Parse the 8 byte type signature or split DWARF id from a unit header.
+fn parse_unit_signature<Endian>(input: DebugInfoInput<Endian>)
+                                -> ParseResult<DebugInfoInput<Endian>, u64>
+    where Endian: Endianity
+{
+    match parse_u64(input.0, input.2) {
+        IResult::Done(rest, val) =>
+            IResult::Done(DebugInfoInput(rest, input.1, input.2), val),
+        IResult::Error(_) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::ExpectedUnsigned64),
+                                         input)),
+        IResult::Incomplete(needed) =>
+            IResult::Incomplete(needed),
+    }
+}
+
+/// Parse a compilation unit header.
+///
+/// Before DWARF 5, the header is followed directly by the `debug_abbrev_offset`
+/// and `address_size`. DWARF 5 headers have a unit type before the
+/// `address_size` and `debug_abbrev_offset`, and then extra fields that depend
+/// on that unit type.
+pub fn parse_compilation_unit_header<Endian>(input: DebugInfoInput<Endian>)
+                                             -> ParseResult<DebugInfoInput<Endian>,
+                                                            CompilationUnitHeader>
+    where Endian: Endianity
+{
+    let (rest, unit_length) = try_parse!(input, parse_unit_length);
+    let (rest, version) = try_parse!(rest, parse_version);
+    if version == 2 && rest.1 == Format::Dwarf64 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnsupportedDwarf64Version),
+                                            input));
+    }
+
+    if version < 5 {
+        let (rest, offset) = try_parse!(rest, parse_debug_abbrev_offset);
+        let (rest, address_size) = try_parse!(rest, parse_address_size);
+        return IResult::Done(rest,
+                             CompilationUnitHeader::new(unit_length,
+                                                        version,
+                                                        offset,
+                                                        address_size));
+    }
+
+    let (rest, unit_type) = try_parse!(rest, parse_unit_type);
+    let (rest, address_size) = try_parse!(rest, parse_address_size);
+    let (rest, offset) = try_parse!(rest, parse_debug_abbrev_offset);
+    let header = CompilationUnitHeader::new(unit_length, version, offset, address_size)
+        .with_unit_type(unit_type);
+
+    match unit_type {
+        UnitType::Type | UnitType::SplitType => {
+            let (rest, type_signature) = try_parse!(rest, parse_unit_signature);
+            let (rest, type_offset) = try_parse!(rest, parse_unit_offset);
+            IResult::Done(rest, header.with_type_signature(type_signature, type_offset))
+        },
+        UnitType::Skeleton | UnitType::SplitCompile => {
+            let (rest, dwo_id) = try_parse!(rest, parse_unit_signature);
+            IResult::Done(rest, header.with_dwo_id(dwo_id))
+        },
+        _ =>
+            IResult::Done(rest, header),
+    }
+}
+
+/// Parse a type unit header from the DWARF 4 `.debug_types` section.
+///
+/// This is a compilation unit header followed by the type's 8 byte signature
+/// and the offset of the type's debugging information entry within the unit.
+/// The returned header's unit type is `UnitType::Type`.
+pub fn parse_type_unit_header<Endian>(input: DebugInfoInput<Endian>)
+                                      -> ParseResult<DebugInfoInput<Endian>, CompilationUnitHeader>
+    where Endian: Endianity
+{
+    let (rest, unit_length) = try_parse!(input, parse_unit_length);
+    let (rest, version) = try_parse!(rest, parse_version);
+    // The `.debug_types` section only exists in DWARF 4.
+    if version != 4 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+
+    let (rest, offset) = try_parse!(rest, parse_debug_abbrev_offset);
+    let (rest, address_size) = try_parse!(rest, parse_address_size);
+    let (rest, type_signature) = try_parse!(rest, parse_unit_signature);
+    let (rest, type_offset) = try_parse!(rest, parse_unit_offset);
+    IResult::Done(rest,
+                  CompilationUnitHeader::new(unit_length, version, offset, address_size)
+                      .with_unit_type(UnitType::Type)
+                      .with_type_signature(type_signature, type_offset))
+}
+
+/// Get the size of a unit's header, not including its initial length field.
+fn unit_header_size(header: &CompilationUnitHeader, format: Format) -> u64 {
+    let offset_size = format.offset_size() as u64;
+    let size = 2 + offset_size + 1;
+    if header.version() < 5 {
+        // Type units from `.debug_types` have a signature and type offset.
+        return match header.type_signature() {
+            Some(_) => size + 8 + offset_size,
+            None => size,
+        };
+    }
+
+    match header.unit_type() {
+        UnitType::Type | UnitType::SplitType => size + 1 + 8 + offset_size,
+        UnitType::Skeleton | UnitType::SplitCompile => size + 1 + 8,
+        _ => size + 1,
+    }
+}
+
+#[test]
+fn test_parse_type_unit_header() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // unit length
+        0x04, 0x00,                                     // version 4
+        0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, // debug_abbrev_offset
+        0x08,                                           // address size
+        0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01, // type signature
+        0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // type offset
+    ];
+
+    match parse_type_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
+        IResult::Done(rest, header) => {
+            assert!(rest.0.is_empty());
+            assert_eq!(unit_header_size(&header, rest.1) + 12, buf.len() as u64);
+            assert_eq!(header,
+                       CompilationUnitHeader::new(0x0807060504030201, 4, 0x0c0b0a0908070605, 8)
+                           .with_unit_type(UnitType::Type)
+                           .with_type_signature(0x0123456789abcdef, 0x30));
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+
+    let mut buf = buf;
+    for &version in &[0x02, 0x03, 0x05] {
+        buf[12] = version;
+        match parse_type_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion), _)) => {},
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_32_ok() {
+    let buf = [
+        0x01, 0x02, 0x03, 0x04, // 32-bit unit length
+        0x04, 0x00,             // version 4
+        0x05, 0x06, 0x07, 0x08, // debug_abbrev_offset
+        0x04                    // address size
+    ];
+
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
+        IResult::Done(_, header) =>
+            assert_eq!(header, CompilationUnitHeader::new(0x04030201, 4, 0x08070605, 4)),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_64_ok() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // unit length
         0x04, 0x00,                                     // version 4
         0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, // debug_abbrev_offset
         0x08                                            // address size
     ];
-    let abbrevs = Abbreviations::new();
 
-    match parse_compilation_unit_header(DebugInfoInput::new(&buf, &abbrevs)) {
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
         IResult::Done(_, header) =>
             assert_eq!(header, CompilationUnitHeader::new(0x0807060504030201,
                                                           4,
                                                           0x0102030405060708,
                                                           8)),
-        _ =>
-            assert!(false),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_32_big_endian() {
+    let buf = [
+        0x01, 0x02, 0x03, 0x04, // 32-bit unit length
+        0x00, 0x04,             // version 4
+        0x05, 0x06, 0x07, 0x08, // debug_abbrev_offset
+        0x04                    // address size
+    ];
+
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, BigEndian)) {
+        IResult::Done(_, header) =>
+            assert_eq!(header, CompilationUnitHeader::new(0x01020304, 4, 0x05060708, 4)),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_64_big_endian() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // unit length
+        0x00, 0x04,                                     // version 4
+        0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, // debug_abbrev_offset
+        0x08                                            // address size
+    ];
+
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, RunTimeEndian::Big)) {
+        IResult::Done(_, header) =>
+            assert_eq!(header, CompilationUnitHeader::new(0x0102030405060708,
+                                                          4,
+                                                          0x0807060504030201,
+                                                          8)),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_v2_64_unsupported() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // unit length
+        0x02, 0x00,                                     // version 2
+        0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, // debug_abbrev_offset
+        0x08                                            // address size
+    ];
+
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnsupportedDwarf64Version), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_v5() {
+    let tests: [(&[u8], CompilationUnitHeader); 6] = [
+        (&[0x08, 0x00, 0x00, 0x00,          // 32-bit unit length
+           0x05, 0x00,                      // version 5
+           0x01,                            // DW_UT_compile
+           0x08,                            // address size
+           0x01, 0x02, 0x03, 0x04],         // debug_abbrev_offset
+         CompilationUnitHeader::new(8, 5, 0x04030201, 8)),
+        (&[0x08, 0x00, 0x00, 0x00,
+           0x05, 0x00,
+           0x03,                            // DW_UT_partial
+           0x04,
+           0x01, 0x02, 0x03, 0x04],
+         CompilationUnitHeader::new(8, 5, 0x04030201, 4).with_unit_type(UnitType::Partial)),
+        (&[0x14, 0x00, 0x00, 0x00,
+           0x05, 0x00,
+           0x02,                            // DW_UT_type
+           0x08,
+           0x01, 0x02, 0x03, 0x04,
+           0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // type signature
+           0x19, 0x00, 0x00, 0x00],         // type offset
+         CompilationUnitHeader::new(0x14, 5, 0x04030201, 8)
+             .with_unit_type(UnitType::Type)
+             .with_type_signature(0x0807060504030201, 0x19)),
+        (&[0x14, 0x00, 0x00, 0x00,
+           0x05, 0x00,
+           0x06,                            // DW_UT_split_type
+           0x08,
+           0x01, 0x02, 0x03, 0x04,
+           0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // type signature
+           0x19, 0x00, 0x00, 0x00],         // type offset
+         CompilationUnitHeader::new(0x14, 5, 0x04030201, 8)
+             .with_unit_type(UnitType::SplitType)
+             .with_type_signature(0x0807060504030201, 0x19)),
+        (&[0x10, 0x00, 0x00, 0x00,
+           0x05, 0x00,
+           0x04,                            // DW_UT_skeleton
+           0x08,
+           0x01, 0x02, 0x03, 0x04,
+           0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08], // dwo_id
+         CompilationUnitHeader::new(0x10, 5, 0x04030201, 8)
+             .with_unit_type(UnitType::Skeleton)
+             .with_dwo_id(0x0807060504030201)),
+        (&[0x10, 0x00, 0x00, 0x00,
+           0x05, 0x00,
+           0x05,                            // DW_UT_split_compile
+           0x08,
+           0x01, 0x02, 0x03, 0x04,
+           0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08], // dwo_id
+         CompilationUnitHeader::new(0x10, 5, 0x04030201, 8)
+             .with_unit_type(UnitType::SplitCompile)
+             .with_dwo_id(0x0807060504030201)),
+    ];
+
+    for &(buf, expect) in tests.iter() {
+        match parse_compilation_unit_header(DebugInfoInput::new(buf, LittleEndian)) {
+            IResult::Done(rest, header) => {
+                assert_eq!(rest.0.len(), 0);
+                assert_eq!(unit_header_size(&header, rest.1) + 4, buf.len() as u64);
+                assert_eq!(header, expect);
+            },
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+}
+
+#[test]
+fn test_parse_compilation_unit_header_v5_64_incomplete() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x1c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // unit length
+        0x05, 0x00,                                     // version 5
+        0x02,                                           // DW_UT_type
+        0x08,                                           // address size
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // debug_abbrev_offset
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // type signature
+        0x19, 0x00, 0x00, 0x00,                         // truncated type offset
+    ];
+
+    match parse_compilation_unit_header(DebugInfoInput::new(&buf, LittleEndian)) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    }
+}
+
+impl<'a, Endian> DebugInfoInput<'a, Endian>
+    where Endian: Endianity
+{
+    /// Navigate the debugging information entries of the compilation unit
+    /// whose header was just parsed, using the abbreviations found at the
+    /// header's `debug_abbrev_offset`.
+    ///
+    /// This input must be positioned directly after the given header, as it is
+    /// when returned by `parse_compilation_unit_header`, and the entries are
+    /// bounded by the header's unit length. Returns `Error::UnexpectedEof` if
+    /// the input is shorter than that.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the header has not been parsed yet, since we need to know if
+    /// this is 32- or 64-bit DWARF.
+    pub fn entries<'abbrev>(self,
+                            header: &CompilationUnitHeader,
+                            abbrevs: &'abbrev Abbreviations)
+                            -> Result<EntriesCursor<'a, 'abbrev, Endian>, Error> {
+        let format = self.1;
+        let header_size = unit_header_size(header, format);
+        let entries_length = match header.unit_length().checked_sub(header_size) {
+            Some(length) if length <= self.0.len() as u64 => length,
+            _ => return Err(Error::UnexpectedEof),
+        };
+
+        Ok(EntriesCursor::new(&self.0[..entries_length as usize],
+                              format.initial_length_size() as u64 + header_size,
+                              abbrevs,
+                              header.version(),
+                              format,
+                              header.address_size(),
+                              self.2))
+    }
+}
+
+/// A compilation unit in the `.debug_info` section, as yielded by
+/// `CompilationUnitsIter`.
+#[derive(Debug, Clone, Copy)]
+pub struct CompilationUnit<'input, Endian>
+    where Endian: Endianity
+{
+    header: CompilationUnitHeader,
+    offset: u64,
+    entries_offset: u64,
+    data: &'input [u8],
+    format: Format,
+    endian: Endian,
+}
+
+impl<'input, Endian> CompilationUnit<'input, Endian>
+    where Endian: Endianity
+{
+    /// Get this compilation unit's header.
+    pub fn header(&self) -> &CompilationUnitHeader {
+        &self.header
+    }
+
+    /// Get the offset of this compilation unit's header from the start of the
+    /// `.debug_info` section.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the offset of this compilation unit's first debugging information
+    /// entry from the start of the unit's header.
+    pub fn entries_offset(&self) -> u64 {
+        self.entries_offset
+    }
+
+    /// Get this compilation unit's raw data, including its header, bounded by
+    /// its unit length.
+    pub fn data(&self) -> &'input [u8] {
+        self.data
+    }
+
+    /// Return true if this compilation unit uses the 64-bit DWARF format, false
+    /// if it uses the 32-bit format.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Navigate this compilation unit's debugging information entries, using
+    /// the abbreviations found at the header's `debug_abbrev_offset`.
+    pub fn entries<'abbrev>(&self, abbrevs: &'abbrev Abbreviations)
+                            -> EntriesCursor<'input, 'abbrev, Endian> {
+        EntriesCursor::new(&self.data[self.entries_offset as usize..],
+                           self.entries_offset,
+                           abbrevs,
+                           self.header.version(),
+                           self.format,
+                           self.header.address_size(),
+                           self.endian)
+    }
+
+    /// Navigate this compilation unit's debugging information entries as a
+    /// tree, starting at its root entry.
+    pub fn entries_tree<'abbrev>(&self, abbrevs: &'abbrev Abbreviations)
+                                 -> Result<EntriesTreeCursor<'input, 'abbrev, Endian>, Error> {
+        EntriesTreeCursor::new(self.entries(abbrevs))
+    }
+
+    /// Navigate this compilation unit's debugging information entries, starting
+    /// at the entry with the given offset from the start of the unit's header.
+    ///
+    /// For example, the entry of a type unit's type is at the header's
+    /// `type_offset`.
+    pub fn entries_at<'abbrev>(&self,
+                               abbrevs: &'abbrev Abbreviations,
+                               offset: u64)
+                               -> Result<EntriesCursor<'input, 'abbrev, Endian>, Error> {
+        if offset < self.entries_offset || offset >= self.data.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        Ok(EntriesCursor::new(&self.data[offset as usize..],
+                              offset,
+                              abbrevs,
+                              self.header.version(),
+                              self.format,
+                              self.header.address_size(),
+                              self.endian))
+    }
+
+    /// Get this compilation unit's base address, which range lists and
+    /// location lists are relative to by default. This is the root entry's
+    /// `DW_AT_low_pc` attribute, or 0 if it does not have one.
+    ///
+    /// A `DW_AT_low_pc` that is an index into the unit's table of addresses is
+    /// resolved through the given `.debug_addr` section. Returns an error if
+    /// the attribute has any other form that is not an address.
+    pub fn base_address(&self,
+                        abbrevs: &Abbreviations,
+                        debug_addr: &DebugAddr<'input, Endian>)
+                        -> Result<u64, Error> {
+        let mut entries = self.entries(abbrevs);
+        entries.next_entry()?;
+        let low_pc = match entries.current()
+            .and_then(|entry| entry.attr_value(AttributeName::LowPc)) {
+            Some(low_pc) => low_pc,
+            None => return Ok(0),
+        };
+
+        let addr_base = self.addr_base(abbrevs)?;
+        debug_addr.address_value(low_pc, self.header.address_size(), addr_base)?
+            .ok_or(Error::UnsupportedAttributeForm)
+    }
+
+    /// Get the offset of this compilation unit's table of addresses in the
+    /// `.debug_addr` section, which `DW_FORM_addrx` attribute values index.
+    /// This is the root entry's `DW_AT_addr_base` attribute, or the GNU
+    /// extension's `DW_AT_GNU_addr_base`, or `None` if it has neither.
+    pub fn addr_base(&self, abbrevs: &Abbreviations) -> Result<Option<u64>, Error> {
+        let mut entries = self.entries(abbrevs);
+        entries.next_entry()?;
+        let base = entries.current()
+            .and_then(|entry| {
+                entry.attr_value(AttributeName::AddrBase)
+                    .or_else(|| entry.attr_value(AttributeName::GnuAddrBase))
+            })
+            .and_then(|value| match value {
+                AttributeValue::SecOffset(offset) => Some(offset),
+                value => value.udata_value(),
+            });
+        Ok(base)
+    }
+
+    /// Get the offset of this compilation unit's table of offsets in the
+    /// `.debug_str_offsets` section, which `DW_FORM_strx` attribute values
+    /// index. This is the root entry's `DW_AT_str_offsets_base` attribute.
+    ///
+    /// Split DWARF units do not have the attribute, and their table starts at
+    /// the beginning of the section: after the contribution header for
+    /// `DW_UT_split_compile` and `DW_UT_split_type` units, and at offset 0 for
+    /// the GNU extension's units, which have a `DW_AT_GNU_dwo_id` instead.
+    /// Returns an error for other units without the attribute.
+    pub fn str_offsets_base(&self, abbrevs: &Abbreviations) -> Result<u64, Error> {
+        let mut entries = self.entries(abbrevs);
+        entries.next_entry()?;
+        let root = entries.current();
+        let base = root.and_then(|entry| entry.attr_value(AttributeName::StrOffsetsBase))
+            .and_then(|value| match value {
+                AttributeValue::SecOffset(offset) => Some(offset),
+                value => value.udata_value(),
+            });
+        if let Some(offset) = base {
+            return Ok(offset);
+        }
+
+        match self.header.unit_type() {
+            UnitType::SplitCompile | UnitType::SplitType => {
+                Ok(self.format.initial_length_size() as u64 + 4)
+            },
+            _ if self.header.version() < 5 &&
+                 root.is_some_and(|entry| entry.attr(AttributeName::GnuDwoId).is_some()) => Ok(0),
+            _ => Err(Error::MissingStrOffsetsBase),
+        }
+    }
+}
+
+/// An iterator over the compilation units in a `.debug_info` section.
+#[derive(Debug, Clone)]
+pub struct CompilationUnitsIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    offset: u64,
+    type_units: bool,
+    endian: Endian,
+}
+
+impl<'input, Endian> CompilationUnitsIter<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new iterator over the compilation units in the given
+    /// `.debug_info` section, which is encoded in the given byte order.
+    pub fn new(debug_info: &'input [u8], endian: Endian) -> CompilationUnitsIter<'input, Endian> {
+        CompilationUnitsIter {
+            input: debug_info,
+            offset: 0,
+            type_units: false,
+            endian,
+        }
+    }
+
+    fn parse_unit(&self) -> Result<CompilationUnit<'input, Endian>, Error> {
+        let input = DebugInfoInput::new(self.input, self.endian);
+        let (rest, header) = if self.type_units {
+            into_result(parse_type_unit_header(input))?
+        } else {
+            into_result(parse_compilation_unit_header(input))?
+        };
+
+        let format = rest.1;
+        let unit_size = (format.initial_length_size() as u64)
+            .checked_add(header.unit_length())
+            .ok_or(Error::UnexpectedEof)?;
+        let entries_offset = (self.input.len() - rest.0.len()) as u64;
+        if unit_size < entries_offset || (self.input.len() as u64) < unit_size {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(CompilationUnit {
+            header,
+            offset: self.offset,
+            entries_offset,
+            data: &self.input[..unit_size as usize],
+            format,
+            endian: self.endian,
+        })
+    }
+}
+
+impl<'input, Endian> Iterator for CompilationUnitsIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<CompilationUnit<'input, Endian>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match self.parse_unit() {
+            Ok(unit) => {
+                self.input = &self.input[unit.data.len()..];
+                self.offset += unit.data.len() as u64;
+                Some(Ok(unit))
+            },
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+#[test]
+fn test_compilation_units_iter() {
+    let buf = [
+        // A 32-bit unit.
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x01, b'a', 0x00, 0x00, 0x00,                   // entries
+
+        // A 64-bit unit.
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // unit length
+        0x04, 0x00,                                     // version 4
+        0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x08,                                           // address size
+        0x01, b'b', b'c', 0x00, 0x00,                   // entries
+
+        // Another 32-bit unit.
+        0x07, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+    ];
+
+    let units: Vec<_> = CompilationUnitsIter::new(&buf, LittleEndian)
+        .map(|unit| unit.expect("Should parse the unit"))
+        .collect();
+    assert_eq!(units.len(), 3);
+
+    assert_eq!(*units[0].header(), CompilationUnitHeader::new(0x0c, 4, 0, 4));
+    assert_eq!(units[0].offset(), 0);
+    assert_eq!(units[0].entries_offset(), 11);
+    assert_eq!(units[0].data(), &buf[0..16]);
+    assert!(!units[0].is_dwarf64());
+
+    assert_eq!(*units[1].header(), CompilationUnitHeader::new(0x10, 4, 7, 8));
+    assert_eq!(units[1].offset(), 16);
+    assert_eq!(units[1].entries_offset(), 23);
+    assert_eq!(units[1].data(), &buf[16..44]);
+    assert!(units[1].is_dwarf64());
+
+    assert_eq!(*units[2].header(), CompilationUnitHeader::new(0x07, 4, 0, 4));
+    assert_eq!(units[2].offset(), 44);
+    assert_eq!(units[2].entries_offset(), 11);
+    assert_eq!(units[2].data(), &buf[44..]);
+
+    let abbrevs = test_abbrevs();
+    let mut cursor = units[1].entries(&abbrevs);
+    {
+        let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+        assert_eq!(entry.offset(), 23);
+        assert_eq!(entry.attr(AttributeName::Name).map(|attr| attr.value()),
+                   Some(AttributeValue::String(b"bc")));
+    }
+    assert!(cursor.next_dfs().unwrap().is_none());
+}
+
+/// An iterator over the type units in a DWARF 4 `.debug_types` section.
+///
+/// The headers of the units that it yields have the type signature and the
+/// offset of the type's entry.
+#[derive(Debug, Clone)]
+pub struct TypeUnitsIter<'input, Endian>(CompilationUnitsIter<'input, Endian>)
+    where Endian: Endianity;
+
+impl<'input, Endian> TypeUnitsIter<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new iterator over the type units in the given
+    /// `.debug_types` section, which is encoded in the given byte order.
+    pub fn new(debug_types: &'input [u8], endian: Endian) -> TypeUnitsIter<'input, Endian> {
+        TypeUnitsIter(CompilationUnitsIter {
+            input: debug_types,
+            offset: 0,
+            type_units: true,
+            endian,
+        })
+    }
+}
+
+impl<'input, Endian> Iterator for TypeUnitsIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<CompilationUnit<'input, Endian>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// An index of type units by their type signature, for resolving
+/// `DW_FORM_ref_sig8` attribute values to the entries of the types that they
+/// refer to.
+#[derive(Debug, Clone)]
+pub struct TypeUnitsIndex<'input, Endian>
+    where Endian: Endianity
+{
+    units: hash_map::HashMap<u64, CompilationUnit<'input, Endian>>,
+}
+
+impl<'input, Endian> TypeUnitsIndex<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new, empty index.
+    pub fn new() -> TypeUnitsIndex<'input, Endian> {
+        TypeUnitsIndex { units: hash_map::HashMap::new() }
+    }
+
+    /// Construct an index of every type unit in the given `.debug_types`
+    /// section.
+    pub fn from_debug_types(debug_types: &'input [u8],
+                            endian: Endian)
+                            -> Result<TypeUnitsIndex<'input, Endian>, Error> {
+        let mut index = TypeUnitsIndex::new();
+        for unit in TypeUnitsIter::new(debug_types, endian) {
+            index.insert(unit?);
+        }
+        Ok(index)
+    }
+
+    /// Add a type unit to the index, such as a DWARF 5 type unit from the
+    /// `.debug_info` section. Returns false, and does not add the unit, if it
+    /// does not have a type signature.
+    pub fn insert(&mut self, unit: CompilationUnit<'input, Endian>) -> bool {
+        match unit.header().type_signature() {
+            Some(signature) => {
+                self.units.insert(signature, unit);
+                true
+            },
+            None => false,
+        }
     }
+
+    /// Get the type unit with the given type signature.
+    pub fn get(&self, signature: u64) -> Option<&CompilationUnit<'input, Endian>> {
+        self.units.get(&signature)
+    }
+
+    /// Get the type unit that a `DW_FORM_ref_sig8` attribute value refers to,
+    /// along with the offset of the type's entry within that unit, which can
+    /// be passed to `CompilationUnit::entries_at`.
+    pub fn resolve(&self,
+                   value: AttributeValue)
+                   -> Option<(&CompilationUnit<'input, Endian>, u64)> {
+        match value {
+            AttributeValue::DebugTypesRef(signature) => {
+                let unit = self.get(signature)?;
+                let offset = unit.header().type_offset()?;
+                Some((unit, offset))
+            },
+            _ => None,
+        }
+    }
+
+    /// Get the number of type units in the index.
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    /// Return true if the index does not have any type units.
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+}
+
+impl<'input, Endian> Default for TypeUnitsIndex<'input, Endian>
+    where Endian: Endianity
+{
+    fn default() -> TypeUnitsIndex<'input, Endian> {
+        TypeUnitsIndex::new()
+    }
+}
+
+#[test]
+fn test_type_units_index() {
+    let debug_abbrev = [
+        0x01, 0x41, 0x01, 0x00, 0x00,                   // DW_TAG_type_unit, children
+        0x02, 0x24, 0x00, 0x03, 0x08, 0x00, 0x00,       // DW_TAG_base_type, DW_AT_name
+        0x00,
+    ];
+    let debug_types = [
+        0x1a, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x08,                                           // address size
+        0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01, // type signature
+        0x18, 0x00, 0x00, 0x00,                         // type offset
+        0x01,                                           // DW_TAG_type_unit
+        0x02, b'i', b'n', b't', 0x00,                   // DW_TAG_base_type "int"
+        0x00,
+    ];
+
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let index = TypeUnitsIndex::from_debug_types(&debug_types, LittleEndian)
+        .expect("Should index the type units");
+    assert_eq!(index.len(), 1);
+    assert!(index.get(0x0123456789abcdef).is_some());
+    assert!(index.get(0x0123456789abcdee).is_none());
+    assert!(index.resolve(AttributeValue::DebugTypesRef(0)).is_none());
+    assert!(index.resolve(AttributeValue::Data8(0x0123456789abcdef)).is_none());
+
+    let (unit, offset) = index.resolve(AttributeValue::DebugTypesRef(0x0123456789abcdef))
+        .expect("Should resolve the signature");
+    assert_eq!(unit.header().unit_type(), UnitType::Type);
+    assert_eq!(unit.entries_offset(), 23);
+    assert_eq!(offset, 24);
+
+    let mut entries = unit.entries_at(&abbrevs, offset).expect("Should find the entry");
+    entries.next_entry().expect("Should parse the entry");
+    let entry = entries.current().expect("Should have an entry");
+    assert_eq!(entry.offset(), 24);
+    assert_eq!(entry.tag(), AbbreviationTag::BaseType);
+    assert_eq!(entry.attr_value(AttributeName::Name), Some(AttributeValue::String(b"int")));
+
+    let mut entries = unit.entries(&abbrevs);
+    let (_, entry) = entries.next_dfs().unwrap().expect("Should have an entry");
+    assert_eq!(entry.tag(), AbbreviationTag::TypeUnit);
+
+    match unit.entries_at(&abbrevs, 22) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise.map(|_| ())),
+    };
+    match unit.entries_at(&abbrevs, debug_types.len() as u64) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise.map(|_| ())),
+    };
+
+    let mut index = TypeUnitsIndex::new();
+    let unit = CompilationUnitsIter::new(&debug_types, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    assert!(!index.insert(unit));
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_compilation_unit_base_address() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x00, 0x11, 0x01, 0x00, 0x00,       // DW_TAG_compile_unit, DW_AT_low_pc
+        0x02, 0x11, 0x00, 0x03, 0x08, 0x00, 0x00,       // DW_TAG_compile_unit, DW_AT_name
+        0x03, 0x11, 0x00,                               // DW_TAG_compile_unit
+        0xb3, 0x42, 0x17,                               // DW_AT_GNU_addr_base
+        0x11, 0x81, 0x3e, 0x00, 0x00,                   // DW_AT_low_pc, DW_FORM_GNU_addr_index
+        0x04, 0x11, 0x00,                               // DW_TAG_compile_unit
+        0x11, 0x81, 0x3e, 0x00, 0x00,                   // DW_AT_low_pc, DW_FORM_GNU_addr_index
+        0x05, 0x11, 0x00, 0x11, 0x06, 0x00, 0x00,       // DW_TAG_compile_unit, DW_AT_low_pc
+        0x00,
+    ];
+    let buf = [
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x01, 0x00, 0x10, 0x00, 0x00,                   // DW_AT_low_pc 0x1000
+
+        0x0a, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x02, b'a', 0x00,                               // DW_AT_name "a"
+
+        0x0d, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x03, 0x04, 0x00, 0x00, 0x00,                   // DW_AT_GNU_addr_base 4
+        0x01,                                           // DW_AT_low_pc index 1
+
+        0x09, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x04, 0x01,                                     // DW_AT_low_pc index 1
+
+        0x0c, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x05, 0x00, 0x10, 0x00, 0x00,                   // DW_AT_low_pc data4 0x1000
+    ];
+    let debug_addr = [
+        0xaa, 0xbb, 0xcc, 0xdd,                         // another unit's addresses
+        0x00, 0x10, 0x00, 0x00,
+        0x00, 0x20, 0x00, 0x00,
+    ];
+    let debug_addr = DebugAddr::new(&debug_addr, LittleEndian);
+
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let units: Vec<_> = CompilationUnitsIter::new(&buf, LittleEndian)
+        .map(|unit| unit.expect("Should parse the unit"))
+        .collect();
+    assert_eq!(units[0].base_address(&abbrevs, &debug_addr).expect("Should find the base"),
+               0x1000);
+    assert_eq!(units[1].base_address(&abbrevs, &debug_addr).expect("Should find the base"), 0);
+    assert_eq!(units[2].addr_base(&abbrevs).expect("Should parse the root"), Some(4));
+    assert_eq!(units[2].base_address(&abbrevs, &debug_addr).expect("Should find the base"),
+               0x2000);
+
+    match units[3].base_address(&abbrevs, &debug_addr) {
+        Err(Error::MissingAddrBase) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match units[4].base_address(&abbrevs, &debug_addr) {
+        Err(Error::UnsupportedAttributeForm) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_compilation_unit_str_offsets_base() {
+    let debug_abbrev = [
+        0x01, 0x11, 0x00, 0x03, 0x08, 0x00, 0x00,       // DW_TAG_compile_unit, DW_AT_name
+        0x02, 0x11, 0x00, 0xb1, 0x42, 0x07, 0x00, 0x00, // DW_TAG_compile_unit,
+                                                        // DW_AT_GNU_dwo_id
+        0x03, 0x11, 0x00, 0x72, 0x17, 0x00, 0x00,       // DW_TAG_compile_unit,
+                                                        // DW_AT_str_offsets_base
+        0x00,
+    ];
+    let buf = [
+        0x10, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x02,                                           // DW_AT_GNU_dwo_id
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+
+        0x13, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x05,                                           // DW_UT_split_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // dwo_id
+        0x01, b'a', 0x00,                               // DW_AT_name "a"
+
+        0x0d, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x01,                                           // DW_UT_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x03,                                           // DW_AT_str_offsets_base
+        0x20, 0x00, 0x00, 0x00,
+
+        0x0b, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x01,                                           // DW_UT_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x01, b'a', 0x00,                               // DW_AT_name "a"
+
+        0x0a, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x01, b'a', 0x00,                               // DW_AT_name "a"
+    ];
+
+    let abbrevs = parse_abbreviations_at(&debug_abbrev, 0).expect("Should parse abbrevs");
+    let units: Vec<_> = CompilationUnitsIter::new(&buf, LittleEndian)
+        .map(|unit| unit.expect("Should parse the unit"))
+        .collect();
+    assert_eq!(units[0].str_offsets_base(&abbrevs).expect("Should find the base"), 0);
+    assert_eq!(units[1].str_offsets_base(&abbrevs).expect("Should find the base"), 8);
+    assert_eq!(units[2].str_offsets_base(&abbrevs).expect("Should find the base"), 0x20);
+
+    // Units that are not split DWARF must say where their table starts.
+    for unit in &units[3..] {
+        match unit.str_offsets_base(&abbrevs) {
+            Err(Error::MissingStrOffsetsBase) => {},
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+    }
+}
+
+#[test]
+fn test_compilation_units_iter_truncated() {
+    let buf = [
+        0x10, 0x00, 0x00, 0x00, // 32-bit unit length, longer than the section
+        0x04, 0x00,             // version 4
+        0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x04,                   // address size
+        0x00,
+    ];
+
+    let mut units = CompilationUnitsIter::new(&buf, LittleEndian);
+    match units.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(units.next().is_none());
+}
+
+#[test]
+fn test_compilation_units_iter_unit_length_overflow() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff,                         // 64-bit unit length
+        0xfc, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0x04, 0x00,                                     // version 4
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x04,                                           // address size
+    ];
+
+    let mut units = CompilationUnitsIter::new(&buf, LittleEndian);
+    match units.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(units.next().is_none());
+}
+
+#[test]
+fn test_compilation_units_iter_v5() {
+    let buf = [
+        0x0d, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x05, 0x00,                                     // version 5
+        0x01,                                           // DW_UT_compile
+        0x04,                                           // address size
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x01, b'a', 0x00, 0x00, 0x00,                   // entries
+    ];
+
+    let unit = CompilationUnitsIter::new(&buf, LittleEndian)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    assert_eq!(unit.header().unit_type(), UnitType::Compile);
+    assert_eq!(unit.entries_offset(), 12);
+
+    let abbrevs = test_abbrevs();
+    let mut cursor = unit.entries(&abbrevs);
+    let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+    assert_eq!(entry.offset(), 12);
+    assert_eq!(entry.attr_value(AttributeName::Name), Some(AttributeValue::String(b"a")));
+}
+
+#[test]
+fn test_compilation_units_iter_big_endian() {
+    let buf = [
+        0x00, 0x00, 0x00, 0x0e,                         // 32-bit unit length
+        0x00, 0x04,                                     // version 4
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x04,                                           // address size
+        0x02, b'f', 0x00, 0x12, 0x34, 0x56, 0x78, 0x00, // entries
+    ];
+
+    let unit = CompilationUnitsIter::new(&buf, RunTimeEndian::Big)
+        .next()
+        .expect("Should have a unit")
+        .expect("Should parse the unit");
+    assert_eq!(*unit.header(), CompilationUnitHeader::new(0x0e, 4, 0, 4));
+
+    let abbrevs = test_abbrevs();
+    let mut cursor = unit.entries(&abbrevs);
+    let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+    assert_eq!(entry.attr_value(AttributeName::LowPc), Some(AttributeValue::Addr(0x12345678)));
+}
+
+/// The value of an attribute in a `DebuggingInformationEntry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeValue<'input> {
+    /// A target address, from `DW_FORM_addr`.
+    Addr(u64),
+
+    /// An arbitrary block of bytes, from `DW_FORM_block{,1,2,4}`.
+    Block(&'input [u8]),
+
+    /// A one byte constant, from `DW_FORM_data1`.
+    Data1(u8),
+
+    /// A two byte constant, from `DW_FORM_data2`.
+    Data2(u16),
+
+    /// A four byte constant, from `DW_FORM_data4`.
+    Data4(u32),
+
+    /// An eight byte constant, from `DW_FORM_data8`.
+    Data8(u64),
+
+    /// A sixteen byte constant, from `DW_FORM_data16`.
+    Data16(&'input [u8]),
+
+    /// A signed integer constant, from `DW_FORM_sdata` or
+    /// `DW_FORM_implicit_const`.
+    Sdata(i64),
+
+    /// An unsigned integer constant, from `DW_FORM_udata`.
+    Udata(u64),
+
+    /// A DWARF expression, from `DW_FORM_exprloc`.
+    Exprloc(&'input [u8]),
+
+    /// A boolean flag, from `DW_FORM_flag` or `DW_FORM_flag_present`.
+    Flag(bool),
+
+    /// An offset into another section, from `DW_FORM_sec_offset`. Which
+    /// section depends on the attribute's name.
+    SecOffset(u64),
+
+    /// A reference to a DIE in the same compilation unit, as an offset from
+    /// the start of the unit's header. From `DW_FORM_ref{1,2,4,8,_udata}`.
+    UnitRef(u64),
+
+    /// A reference to a DIE anywhere in the `.debug_info` section, as an
+    /// offset from the start of the section. From `DW_FORM_ref_addr`.
+    DebugInfoRef(u64),
+
+    /// A reference to a type unit by its 64 bit type signature, from
+    /// `DW_FORM_ref_sig8`.
+    DebugTypesRef(u64),
+
+    /// An offset into the `.debug_str` section, from `DW_FORM_strp`.
+    DebugStrRef(u64),
+
+    /// An inline null-terminated string, without its terminator, from
+    /// `DW_FORM_string`.
+    String(&'input [u8]),
+
+    /// An offset into the `.debug_line_str` section, from `DW_FORM_line_strp`.
+    DebugLineStrRef(u64),
+
+    /// An index into the `.debug_addr` section, from `DW_FORM_addrx{,1,2,3,4}`
+    /// or the split DWARF `DW_FORM_GNU_addr_index` extension.
+    DebugAddrIndex(u64),
+
+    /// An index into the `.debug_str_offsets` section, from
+    /// `DW_FORM_strx{,1,2,3,4}` or the split DWARF `DW_FORM_GNU_str_index`
+    /// extension.
+    DebugStrOffsetsIndex(u64),
+
+    /// An index into the offsets table of the `.debug_loclists` section, from
+    /// `DW_FORM_loclistx`.
+    DebugLocListsIndex(u64),
+
+    /// An index into the offsets table of the `.debug_rnglists` section, from
+    /// `DW_FORM_rnglistx`.
+    DebugRngListsIndex(u64),
+
+    /// A reference to a DIE in the `.debug_info` section of the supplementary
+    /// object file, from `DW_FORM_ref_sup{4,8}` or the `DW_FORM_GNU_ref_alt`
+    /// extension.
+    DebugInfoRefSup(u64),
+
+    /// An offset into the `.debug_str` section of the supplementary object
+    /// file, from `DW_FORM_strp_sup` or the `DW_FORM_GNU_strp_alt` extension.
+    DebugStrRefSup(u64),
+}
+
+impl<'input> AttributeValue<'input> {
+    /// Get the value of an unsigned constant-class attribute value, from
+    /// `DW_FORM_data{1,2,4,8}` or `DW_FORM_udata`.
+    ///
+    /// Returns `None` if this value is not an unsigned constant.
+    pub fn udata_value(&self) -> Option<u64> {
+        match *self {
+            AttributeValue::Data1(data) => Some(data as u64),
+            AttributeValue::Data2(data) => Some(data as u64),
+            AttributeValue::Data4(data) => Some(data as u64),
+            AttributeValue::Data8(data) => Some(data),
+            AttributeValue::Udata(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Get the bytes of a string-class attribute value, without its null
+    /// terminator, looking up `DW_FORM_strp` values in the given `.debug_str`
+    /// section.
+    ///
+    /// Returns `Ok(None)` if this value is not a string, or if it is a string
+    /// that lives in a section other than `.debug_str`, and the error from
+    /// `DebugStr::get_str` if the `.debug_str` section does not have the
+    /// string.
+    pub fn string_value(&self,
+                        debug_str: &DebugStr<'input>)
+                        -> Result<Option<&'input [u8]>, Error> {
+        match *self {
+            AttributeValue::String(string) => Ok(Some(string)),
+            AttributeValue::DebugStrRef(offset) => debug_str.get_str(offset).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[test]
+fn test_attribute_value_string_value() {
+    let debug_str = DebugStr::new(b"\0producer\0");
+
+    let string_value = |value: AttributeValue<'static>| {
+        value.string_value(&debug_str).expect("Should get the string value")
+    };
+    assert_eq!(string_value(AttributeValue::String(b"name")), Some(&b"name"[..]));
+    assert_eq!(string_value(AttributeValue::DebugStrRef(1)), Some(&b"producer"[..]));
+    assert_eq!(string_value(AttributeValue::Udata(1)), None);
+    assert_eq!(string_value(AttributeValue::DebugStrRefSup(1)), None);
+
+    match AttributeValue::DebugStrRef(10).string_value(&debug_str) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    match AttributeValue::DebugStrRef(1).string_value(&DebugStr::new(b"\0producer")) {
+        Err(Error::UnterminatedString) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_attribute_value_udata_value() {
+    assert_eq!(AttributeValue::Data1(1).udata_value(), Some(1));
+    assert_eq!(AttributeValue::Data2(0x102).udata_value(), Some(0x102));
+    assert_eq!(AttributeValue::Data4(0x1020304).udata_value(), Some(0x1020304));
+    assert_eq!(AttributeValue::Data8(0x102030405060708).udata_value(), Some(0x102030405060708));
+    assert_eq!(AttributeValue::Udata(7).udata_value(), Some(7));
+    assert_eq!(AttributeValue::Sdata(-1).udata_value(), None);
+    assert_eq!(AttributeValue::String(b"x").udata_value(), None);
+}
+
+/// Return true if the given form was introduced in DWARF 5.
+fn is_dwarf5_form(form: AttributeForm) -> bool {
+    matches!(form,
+             AttributeForm::Strx |
+             AttributeForm::Addrx |
+             AttributeForm::RefSup4 |
+             AttributeForm::StrpSup |
+             AttributeForm::Data16 |
+             AttributeForm::LineStrp |
+             AttributeForm::ImplicitConst |
+             AttributeForm::Loclistx |
+             AttributeForm::Rnglistx |
+             AttributeForm::RefSup8 |
+             AttributeForm::Strx1 |
+             AttributeForm::Strx2 |
+             AttributeForm::Strx3 |
+             AttributeForm::Strx4 |
+             AttributeForm::Addrx1 |
+             AttributeForm::Addrx2 |
+             AttributeForm::Addrx3 |
+             AttributeForm::Addrx4)
+}
+
+/// Parse an address of the given size.
+pub(crate) fn parse_address<Endian>(input: &[u8],
+                                    address_size: u8,
+                                    endian: Endian)
+                                    -> ParseResult<&[u8], u64>
+    where Endian: Endianity
+{
+    match address_size {
+        1 => {
+            let (rest, addr) = try_parse!(input, parse_u8);
+            IResult::Done(rest, addr as u64)
+        },
+        2 => {
+            let (rest, addr) = try_parse!(input, call!(parse_u16, endian));
+            IResult::Done(rest, addr as u64)
+        },
+        4 => {
+            let (rest, addr) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, addr as u64)
+        },
+        8 =>
+            parse_u64(input, endian),
+        _ =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::UnsupportedAddressSize), input)),
+    }
+}
+
+/// Parse an attribute value of the given form.
+///
+/// The unit's DWARF version determines the size of `DW_FORM_ref_addr`, and
+/// which forms are valid at all.
+pub(crate) fn parse_attribute_value<'input, Endian>(input: &'input [u8],
+                                                    form: AttributeForm,
+                                                    version: u16,
+                                                    address_size: u8,
+                                                    format: Format,
+                                                    endian: Endian)
+                                                    -> ParseResult<&'input [u8],
+                                                                   AttributeValue<'input>>
+    where Endian: Endianity
+{
+    let mut input = input;
+    let mut form = form;
+    while form == AttributeForm::Indirect {
+        let (rest, indirect_form) = try_parse!(input, parse_attribute_form);
+        input = rest;
+        form = indirect_form;
+    }
+
+    match form {
+        AttributeForm::Addr => {
+            let (rest, addr) = try_parse!(input, call!(parse_address, address_size, endian));
+            IResult::Done(rest, AttributeValue::Addr(addr))
+        },
+        AttributeForm::Block1 => {
+            let (rest, len) = try_parse!(input, parse_u8);
+            let (rest, block) = try_parse!(rest, call!(take, len as u64));
+            IResult::Done(rest, AttributeValue::Block(block))
+        },
+        AttributeForm::Block2 => {
+            let (rest, len) = try_parse!(input, call!(parse_u16, endian));
+            let (rest, block) = try_parse!(rest, call!(take, len as u64));
+            IResult::Done(rest, AttributeValue::Block(block))
+        },
+        AttributeForm::Block4 => {
+            let (rest, len) = try_parse!(input, call!(parse_u32, endian));
+            let (rest, block) = try_parse!(rest, call!(take, len as u64));
+            IResult::Done(rest, AttributeValue::Block(block))
+        },
+        AttributeForm::Block => {
+            let (rest, len) = try_parse!(input, parse_unsigned_leb);
+            let (rest, block) = try_parse!(rest, call!(take, len));
+            IResult::Done(rest, AttributeValue::Block(block))
+        },
+        AttributeForm::Data1 => {
+            let (rest, data) = try_parse!(input, parse_u8);
+            IResult::Done(rest, AttributeValue::Data1(data))
+        },
+        AttributeForm::Data2 => {
+            let (rest, data) = try_parse!(input, call!(parse_u16, endian));
+            IResult::Done(rest, AttributeValue::Data2(data))
+        },
+        AttributeForm::Data4 => {
+            let (rest, data) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, AttributeValue::Data4(data))
+        },
+        AttributeForm::Data8 => {
+            let (rest, data) = try_parse!(input, call!(parse_u64, endian));
+            IResult::Done(rest, AttributeValue::Data8(data))
+        },
+        AttributeForm::Sdata => {
+            let (rest, data) = try_parse!(input, parse_signed_leb);
+            IResult::Done(rest, AttributeValue::Sdata(data))
+        },
+        AttributeForm::Udata => {
+            let (rest, data) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::Udata(data))
+        },
+        AttributeForm::Exprloc |
+        AttributeForm::FlagPresent |
+        AttributeForm::SecOffset |
+        AttributeForm::RefSig8 if version < 4 =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), input)),
+        _ if version < 5 && is_dwarf5_form(form) =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), input)),
+        AttributeForm::Exprloc => {
+            let (rest, len) = try_parse!(input, parse_unsigned_leb);
+            let (rest, expr) = try_parse!(rest, call!(take, len));
+            IResult::Done(rest, AttributeValue::Exprloc(expr))
+        },
+        AttributeForm::Flag => {
+            let (rest, flag) = try_parse!(input, parse_u8);
+            IResult::Done(rest, AttributeValue::Flag(flag != 0))
+        },
+        AttributeForm::FlagPresent =>
+            IResult::Done(input, AttributeValue::Flag(true)),
+        AttributeForm::SecOffset => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::SecOffset(offset))
+        },
+        AttributeForm::Ref1 => {
+            let (rest, offset) = try_parse!(input, parse_u8);
+            IResult::Done(rest, AttributeValue::UnitRef(offset as u64))
+        },
+        AttributeForm::Ref2 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u16, endian));
+            IResult::Done(rest, AttributeValue::UnitRef(offset as u64))
+        },
+        AttributeForm::Ref4 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, AttributeValue::UnitRef(offset as u64))
+        },
+        AttributeForm::Ref8 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u64, endian));
+            IResult::Done(rest, AttributeValue::UnitRef(offset))
+        },
+        AttributeForm::RefUdata => {
+            let (rest, offset) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::UnitRef(offset))
+        },
+        AttributeForm::RefAddr if version == 2 => {
+            let (rest, offset) = try_parse!(input, call!(parse_address, address_size, endian));
+            IResult::Done(rest, AttributeValue::DebugInfoRef(offset))
+        },
+        AttributeForm::RefAddr => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugInfoRef(offset))
+        },
+        AttributeForm::RefSig8 => {
+            let (rest, signature) = try_parse!(input, call!(parse_u64, endian));
+            IResult::Done(rest, AttributeValue::DebugTypesRef(signature))
+        },
+        AttributeForm::Strp => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugStrRef(offset))
+        },
+        AttributeForm::String => {
+            let (rest, string) = try_parse!(input, parse_null_terminated_string);
+            IResult::Done(rest, AttributeValue::String(string))
+        },
+        AttributeForm::Indirect =>
+            unreachable!(),
+        AttributeForm::GnuAddrIndex => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index))
+        },
+        AttributeForm::GnuStrIndex => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index))
+        },
+        AttributeForm::GnuRefAlt => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugInfoRefSup(offset))
+        },
+        AttributeForm::GnuStrpAlt => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugStrRefSup(offset))
+        },
+        AttributeForm::Data16 => {
+            let (rest, data) = try_parse!(input, call!(take, 16));
+            IResult::Done(rest, AttributeValue::Data16(data))
+        },
+        AttributeForm::LineStrp => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugLineStrRef(offset))
+        },
+        AttributeForm::Strx => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index))
+        },
+        AttributeForm::Strx1 => {
+            let (rest, index) = try_parse!(input, parse_u8);
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index as u64))
+        },
+        AttributeForm::Strx2 => {
+            let (rest, index) = try_parse!(input, call!(parse_u16, endian));
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index as u64))
+        },
+        AttributeForm::Strx3 => {
+            let (rest, index) = try_parse!(input, call!(parse_u24, endian));
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index as u64))
+        },
+        AttributeForm::Strx4 => {
+            let (rest, index) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, AttributeValue::DebugStrOffsetsIndex(index as u64))
+        },
+        AttributeForm::Addrx => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index))
+        },
+        AttributeForm::Addrx1 => {
+            let (rest, index) = try_parse!(input, parse_u8);
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index as u64))
+        },
+        AttributeForm::Addrx2 => {
+            let (rest, index) = try_parse!(input, call!(parse_u16, endian));
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index as u64))
+        },
+        AttributeForm::Addrx3 => {
+            let (rest, index) = try_parse!(input, call!(parse_u24, endian));
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index as u64))
+        },
+        AttributeForm::Addrx4 => {
+            let (rest, index) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, AttributeValue::DebugAddrIndex(index as u64))
+        },
+        AttributeForm::Loclistx => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugLocListsIndex(index))
+        },
+        AttributeForm::Rnglistx => {
+            let (rest, index) = try_parse!(input, parse_unsigned_leb);
+            IResult::Done(rest, AttributeValue::DebugRngListsIndex(index))
+        },
+        AttributeForm::RefSup4 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u32, endian));
+            IResult::Done(rest, AttributeValue::DebugInfoRefSup(offset as u64))
+        },
+        AttributeForm::RefSup8 => {
+            let (rest, offset) = try_parse!(input, call!(parse_u64, endian));
+            IResult::Done(rest, AttributeValue::DebugInfoRefSup(offset))
+        },
+        AttributeForm::StrpSup => {
+            let (rest, offset) = try_parse!(input, call!(parse_offset, format, endian));
+            IResult::Done(rest, AttributeValue::DebugStrRefSup(offset))
+        },
+        _ =>
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), input)),
+    }
+}
+
+#[test]
+fn test_parse_attribute_value() {
+    let buf = [0x86, 0x02, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x88, 0x8a, 0xff];
+
+    let tests = [
+        (AttributeForm::Addr, 4, Format::Dwarf32, AttributeValue::Addr(0x80800286), 4),
+        (AttributeForm::Addr, 8, Format::Dwarf32, AttributeValue::Addr(0x8080808080800286), 8),
+        (AttributeForm::Data1, 4, Format::Dwarf32, AttributeValue::Data1(0x86), 1),
+        (AttributeForm::Data2, 4, Format::Dwarf32, AttributeValue::Data2(0x0286), 2),
+        (AttributeForm::Data4, 4, Format::Dwarf32, AttributeValue::Data4(0x80800286), 4),
+        (AttributeForm::Data8, 4, Format::Dwarf32, AttributeValue::Data8(0x8080808080800286), 8),
+        (AttributeForm::Udata, 4, Format::Dwarf32, AttributeValue::Udata(0x106), 2),
+        (AttributeForm::Flag, 4, Format::Dwarf32, AttributeValue::Flag(true), 1),
+        (AttributeForm::FlagPresent, 4, Format::Dwarf32, AttributeValue::Flag(true), 0),
+        (AttributeForm::SecOffset, 4, Format::Dwarf32, AttributeValue::SecOffset(0x80800286), 4),
+        (AttributeForm::SecOffset, 4, Format::Dwarf64,
+         AttributeValue::SecOffset(0x8080808080800286), 8),
+        (AttributeForm::Ref1, 4, Format::Dwarf32, AttributeValue::UnitRef(0x86), 1),
+        (AttributeForm::Ref2, 4, Format::Dwarf32, AttributeValue::UnitRef(0x0286), 2),
+        (AttributeForm::Ref4, 4, Format::Dwarf32, AttributeValue::UnitRef(0x80800286), 4),
+        (AttributeForm::Ref8, 4, Format::Dwarf32, AttributeValue::UnitRef(0x8080808080800286), 8),
+        (AttributeForm::RefUdata, 4, Format::Dwarf32, AttributeValue::UnitRef(0x106), 2),
+        (AttributeForm::RefAddr, 4, Format::Dwarf32, AttributeValue::DebugInfoRef(0x80800286), 4),
+        (AttributeForm::RefAddr, 4, Format::Dwarf64,
+         AttributeValue::DebugInfoRef(0x8080808080800286), 8),
+        (AttributeForm::RefSig8, 4, Format::Dwarf32,
+         AttributeValue::DebugTypesRef(0x8080808080800286), 8),
+        (AttributeForm::Strp, 4, Format::Dwarf32, AttributeValue::DebugStrRef(0x80800286), 4),
+        (AttributeForm::Strp, 4, Format::Dwarf64,
+         AttributeValue::DebugStrRef(0x8080808080800286), 8),
+        (AttributeForm::GnuAddrIndex, 4, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x106), 2),
+        (AttributeForm::GnuStrIndex, 4, Format::Dwarf32,
+         AttributeValue::DebugStrOffsetsIndex(0x106), 2),
+        (AttributeForm::GnuRefAlt, 4, Format::Dwarf32,
+         AttributeValue::DebugInfoRefSup(0x80800286), 4),
+        (AttributeForm::GnuRefAlt, 4, Format::Dwarf64,
+         AttributeValue::DebugInfoRefSup(0x8080808080800286), 8),
+        (AttributeForm::GnuStrpAlt, 4, Format::Dwarf32,
+         AttributeValue::DebugStrRefSup(0x80800286), 4),
+    ];
+
+    for &(form, address_size, format, expected, consumed) in tests.iter() {
+        match parse_attribute_value(&buf, form, 4, address_size, format, LittleEndian) {
+            IResult::Done(rest, value) => {
+                assert_eq!(value, expected);
+                assert_eq!(rest, &buf[consumed..]);
+            },
+            otherwise => panic!("Unexpected result for {:?}: {:?}", form, otherwise),
+        };
+    }
+}
+
+#[test]
+fn test_parse_attribute_value_blocks() {
+    let buf = [0x02, 0x00, 0x00, 0x00, 0x11, 0x22, 0x33];
+
+    let tests = [
+        (AttributeForm::Block1, AttributeValue::Block(&buf[1..3]), 3),
+        (AttributeForm::Block2, AttributeValue::Block(&buf[2..4]), 4),
+        (AttributeForm::Block4, AttributeValue::Block(&buf[4..6]), 6),
+        (AttributeForm::Block, AttributeValue::Block(&buf[1..3]), 3),
+        (AttributeForm::Exprloc, AttributeValue::Exprloc(&buf[1..3]), 3),
+    ];
+
+    for &(form, expected, consumed) in tests.iter() {
+        match parse_attribute_value(&buf, form, 4, 4, Format::Dwarf32, LittleEndian) {
+            IResult::Done(rest, value) => {
+                assert_eq!(value, expected);
+                assert_eq!(rest, &buf[consumed..]);
+            },
+            otherwise => panic!("Unexpected result for {:?}: {:?}", form, otherwise),
+        };
+    }
+}
+
+#[test]
+fn test_parse_attribute_value_sdata() {
+    let buf = [0x7f, 0x80, 0x7f];
+
+    match parse_attribute_value(&buf, AttributeForm::Sdata, 4, 4, Format::Dwarf32, LittleEndian) {
+        IResult::Done(rest, value) => {
+            assert_eq!(value, AttributeValue::Sdata(-1));
+            assert_eq!(rest, &buf[1..]);
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match parse_attribute_value(&buf[1..],
+                                AttributeForm::Sdata,
+                                4,
+                                4,
+                                Format::Dwarf32,
+                                LittleEndian) {
+        IResult::Done(rest, value) => {
+            assert_eq!(value, AttributeValue::Sdata(-128));
+            assert_eq!(rest.len(), 0);
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_string() {
+    let buf = [b'f', b'o', b'o', 0x00, 0x01];
+
+    match parse_attribute_value(&buf, AttributeForm::String, 4, 4, Format::Dwarf32, LittleEndian) {
+        IResult::Done(rest, value) => {
+            assert_eq!(value, AttributeValue::String(b"foo"));
+            assert_eq!(rest, &[0x01]);
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    match parse_attribute_value(&buf[..3],
+                                AttributeForm::String,
+                                4,
+                                4,
+                                Format::Dwarf32,
+                                LittleEndian) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_indirect() {
+    // DW_FORM_indirect to DW_FORM_indirect to DW_FORM_data2.
+    let buf = [0x16, 0x05, 0x34, 0x12, 0xff];
+
+    match parse_attribute_value(&buf,
+                                AttributeForm::Indirect,
+                                4,
+                                4,
+                                Format::Dwarf32,
+                                LittleEndian) {
+        IResult::Done(rest, value) => {
+            assert_eq!(value, AttributeValue::Data2(0x1234));
+            assert_eq!(rest, &[0xff]);
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_indirect_chain() {
+    // A long chain of DW_FORM_indirect must not recurse once per link.
+    let mut buf = vec![0x16; 1_000_000];
+    buf.extend_from_slice(&[0x0b, 0x2a]);
+
+    match parse_attribute_value(&buf, AttributeForm::Indirect, 4, 4, Format::Dwarf32, BigEndian) {
+        IResult::Done(rest, value) => {
+            assert_eq!(value, AttributeValue::Data1(0x2a));
+            assert!(rest.is_empty());
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    buf.truncate(1_000_000);
+    match parse_attribute_value(&buf, AttributeForm::Indirect, 4, 4, Format::Dwarf32, BigEndian) {
+        IResult::Incomplete(_) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_unsupported_address_size() {
+    let buf = [0x01, 0x02, 0x03];
+
+    match parse_attribute_value(&buf, AttributeForm::Addr, 4, 3, Format::Dwarf32, LittleEndian) {
+        IResult::Error(Err::Position(ErrorKind::Custom(Error::UnsupportedAddressSize), _)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_ref_addr_versions() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+    let tests = [
+        (2, 8, Format::Dwarf32, 0x0807060504030201, 0),
+        (2, 4, Format::Dwarf32, 0x04030201, 4),
+        (3, 8, Format::Dwarf32, 0x04030201, 4),
+        (3, 4, Format::Dwarf64, 0x0807060504030201, 0),
+        (4, 8, Format::Dwarf32, 0x04030201, 4),
+    ];
+
+    for test in tests.iter() {
+        let (version, address_size, format, offset, remain) = *test;
+        match parse_attribute_value(&buf,
+                                    AttributeForm::RefAddr,
+                                    version,
+                                    address_size,
+                                    format,
+                                    LittleEndian) {
+            IResult::Done(rest, value) => {
+                assert_eq!(value, AttributeValue::DebugInfoRef(offset));
+                assert_eq!(rest.len(), remain);
+            },
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+        assert_eq!(attribute_form_size(AttributeForm::RefAddr, version, format, address_size),
+                   Some(8 - remain as u64));
+    }
+}
+
+#[test]
+fn test_parse_attribute_value_dwarf5() {
+    let buf = [0x86, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+               0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10];
+
+    let tests = [
+        (AttributeForm::Strx, Format::Dwarf32, AttributeValue::DebugStrOffsetsIndex(0x106), 2),
+        (AttributeForm::Strx1, Format::Dwarf32, AttributeValue::DebugStrOffsetsIndex(0x86), 1),
+        (AttributeForm::Strx2, Format::Dwarf32, AttributeValue::DebugStrOffsetsIndex(0x0286), 2),
+        (AttributeForm::Strx3, Format::Dwarf32, AttributeValue::DebugStrOffsetsIndex(0x030286), 3),
+        (AttributeForm::Strx4, Format::Dwarf32,
+         AttributeValue::DebugStrOffsetsIndex(0x04030286), 4),
+        (AttributeForm::Addrx, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x106), 2),
+        (AttributeForm::Addrx1, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x86), 1),
+        (AttributeForm::Addrx2, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x0286), 2),
+        (AttributeForm::Addrx3, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x030286), 3),
+        (AttributeForm::Addrx4, Format::Dwarf32, AttributeValue::DebugAddrIndex(0x04030286), 4),
+        (AttributeForm::LineStrp, Format::Dwarf32, AttributeValue::DebugLineStrRef(0x04030286), 4),
+        (AttributeForm::LineStrp, Format::Dwarf64,
+         AttributeValue::DebugLineStrRef(0x0807060504030286), 8),
+        (AttributeForm::Data16, Format::Dwarf32, AttributeValue::Data16(&buf), 16),
+        (AttributeForm::Loclistx, Format::Dwarf32, AttributeValue::DebugLocListsIndex(0x106), 2),
+        (AttributeForm::Rnglistx, Format::Dwarf32, AttributeValue::DebugRngListsIndex(0x106), 2),
+        (AttributeForm::RefSup4, Format::Dwarf32, AttributeValue::DebugInfoRefSup(0x04030286), 4),
+        (AttributeForm::RefSup8, Format::Dwarf32,
+         AttributeValue::DebugInfoRefSup(0x0807060504030286), 8),
+        (AttributeForm::StrpSup, Format::Dwarf32, AttributeValue::DebugStrRefSup(0x04030286), 4),
+    ];
+
+    for test in tests.iter() {
+        let (form, format, expect, length) = *test;
+        match parse_attribute_value(&buf, form, 5, 4, format, LittleEndian) {
+            IResult::Done(rest, value) => {
+                assert_eq!(value, expect);
+                assert_eq!(rest.len(), buf.len() - length);
+            },
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+
+        if let Some(size) = attribute_form_size(form, 5, format, 4) {
+            assert_eq!(size, length as u64);
+        }
+
+        match parse_attribute_value(&buf, form, 4, 4, format, LittleEndian) {
+            IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), _)) => {},
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+    }
+
+    match parse_attribute_value(&buf, AttributeForm::Strx3, 5, 4, Format::Dwarf32, BigEndian) {
+        IResult::Done(_, value) =>
+            assert_eq!(value, AttributeValue::DebugStrOffsetsIndex(0x860203)),
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_attribute_value_invalid_for_version() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+    let forms = [
+        AttributeForm::Exprloc,
+        AttributeForm::FlagPresent,
+        AttributeForm::SecOffset,
+        AttributeForm::RefSig8,
+    ];
+
+    for &form in forms.iter() {
+        for &version in [2, 3].iter() {
+            match parse_attribute_value(&buf, form, version, 4, Format::Dwarf32, LittleEndian) {
+                IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm), _)) =>
+                    {},
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            };
+            assert_eq!(attribute_form_size(form, version, Format::Dwarf32, 4), None);
+        }
+
+        match parse_attribute_value(&buf, form, 4, 4, Format::Dwarf32, LittleEndian) {
+            IResult::Done(_, _) => {},
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+    }
+}
+
+#[test]
+fn test_parse_attribute_value_big_endian() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+    let tests = [
+        (AttributeForm::Addr, 4, Format::Dwarf32, AttributeValue::Addr(0x01020304)),
+        (AttributeForm::Addr, 8, Format::Dwarf32, AttributeValue::Addr(0x0102030405060708)),
+        (AttributeForm::Data2, 4, Format::Dwarf32, AttributeValue::Data2(0x0102)),
+        (AttributeForm::Data4, 4, Format::Dwarf32, AttributeValue::Data4(0x01020304)),
+        (AttributeForm::Data8, 4, Format::Dwarf32, AttributeValue::Data8(0x0102030405060708)),
+        (AttributeForm::Ref4, 4, Format::Dwarf32, AttributeValue::UnitRef(0x01020304)),
+        (AttributeForm::Strp, 4, Format::Dwarf32, AttributeValue::DebugStrRef(0x01020304)),
+        (AttributeForm::Strp, 4, Format::Dwarf64,
+         AttributeValue::DebugStrRef(0x0102030405060708)),
+    ];
+
+    for test in tests.iter() {
+        let (form, address_size, format, expect) = *test;
+        match parse_attribute_value(&buf[..], form, 4, address_size, format, BigEndian) {
+            IResult::Done(_, value) => assert_eq!(value, expect),
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        };
+    }
+}
+
+/// An attribute in a `DebuggingInformationEntry`, consisting of a name and its
+/// associated value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute<'input> {
+    name: AttributeName,
+    value: AttributeValue<'input>,
+}
+
+impl<'input> Attribute<'input> {
+    /// Get this attribute's name.
+    pub fn name(&self) -> AttributeName {
+        self.name
+    }
+
+    /// Get this attribute's value.
+    pub fn value(&self) -> AttributeValue<'input> {
+        self.value
+    }
+}
+
+/// Parse an attribute described by the given specification.
+fn parse_attribute<'input, Endian>(input: &'input [u8],
+                                   spec: AttributeSpecification,
+                                   version: u16,
+                                   format: Format,
+                                   address_size: u8,
+                                   endian: Endian)
+                                   -> ParseResult<&'input [u8], Attribute<'input>>
+    where Endian: Endianity
+{
+    if let Some(value) = spec.implicit_const_value() {
+        if version < 5 {
+            return IResult::Error(Err::Position(ErrorKind::Custom(Error::InvalidAttributeForm),
+                                                input));
+        }
+
+        return IResult::Done(input,
+                             Attribute {
+                                 name: spec.name(),
+                                 value: AttributeValue::Sdata(value),
+                             });
+    }
+
+    let (rest, value) = try_parse!(input,
+                                   call!(parse_attribute_value,
+                                         spec.form(),
+                                         version,
+                                         address_size,
+                                         format,
+                                         endian));
+    IResult::Done(rest,
+                  Attribute {
+                      name: spec.name(),
+                      value,
+                  })
+}
+
+/// Get the size of an attribute value of the given form, if that size is
+/// fixed rather than determined by the value itself.
+///
+/// Returns `None` for forms that are not valid in the given DWARF version, so
+/// that they are rejected when they are parsed.
+fn attribute_form_size(form: AttributeForm,
+                       version: u16,
+                       format: Format,
+                       address_size: u8)
+                       -> Option<u64> {
+    let valid_address_size = match address_size {
+        1 | 2 | 4 | 8 => Some(address_size as u64),
+        _ => None,
+    };
+
+    match form {
+        AttributeForm::FlagPresent | AttributeForm::SecOffset | AttributeForm::RefSig8
+            if version < 4 => None,
+        _ if version < 5 && is_dwarf5_form(form) => None,
+        AttributeForm::Addr => valid_address_size,
+        AttributeForm::RefAddr if version == 2 => valid_address_size,
+        AttributeForm::Data1 |
+        AttributeForm::Flag |
+        AttributeForm::Ref1 |
+        AttributeForm::Strx1 |
+        AttributeForm::Addrx1 => Some(1),
+        AttributeForm::Data2 |
+        AttributeForm::Ref2 |
+        AttributeForm::Strx2 |
+        AttributeForm::Addrx2 => Some(2),
+        AttributeForm::Strx3 | AttributeForm::Addrx3 => Some(3),
+        AttributeForm::Data4 |
+        AttributeForm::Ref4 |
+        AttributeForm::Strx4 |
+        AttributeForm::Addrx4 |
+        AttributeForm::RefSup4 => Some(4),
+        AttributeForm::Data8 |
+        AttributeForm::Ref8 |
+        AttributeForm::RefSig8 |
+        AttributeForm::RefSup8 => Some(8),
+        AttributeForm::Data16 => Some(16),
+        AttributeForm::Strp |
+        AttributeForm::RefAddr |
+        AttributeForm::SecOffset |
+        AttributeForm::LineStrp |
+        AttributeForm::StrpSup |
+        AttributeForm::GnuRefAlt |
+        AttributeForm::GnuStrpAlt => Some(format.offset_size() as u64),
+        AttributeForm::FlagPresent | AttributeForm::ImplicitConst => Some(0),
+        _ => None,
+    }
+}
+
+/// Skip over the values of the given attributes, without decoding the ones
+/// whose size is fixed by their form.
+fn skip_attributes<'input, Endian>(mut input: &'input [u8],
+                                   specs: &[AttributeSpecification],
+                                   version: u16,
+                                   format: Format,
+                                   address_size: u8,
+                                   endian: Endian)
+                                   -> ParseResult<&'input [u8], ()>
+    where Endian: Endianity
+{
+    let mut skip = 0;
+    for spec in specs {
+        match attribute_form_size(spec.form(), version, format, address_size) {
+            Some(size) => skip += size,
+            None => {
+                let (rest, _) = try_parse!(input, call!(take, skip));
+                let (rest, _) = try_parse!(rest,
+                                           call!(parse_attribute,
+                                                 *spec,
+                                                 version,
+                                                 format,
+                                                 address_size,
+                                                 endian));
+                input = rest;
+                skip = 0;
+            },
+        }
+    }
+
+    let (rest, _) = try_parse!(input, call!(take, skip));
+    IResult::Done(rest, ())
+}
+
+/// A Debugging Information Entry (DIE).
+///
+/// DIEs have a set of attributes and optionally have children DIEs as well.
+#[derive(Debug, Clone)]
+pub struct DebuggingInformationEntry<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    offset: u64,
+    attrs_slice: &'input [u8],
+    abbrev: Abbreviation<'abbrev>,
+    version: u16,
+    format: Format,
+    address_size: u8,
+    endian: Endian,
+}
+
+impl<'input, 'abbrev, Endian> DebuggingInformationEntry<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    /// Get this entry's offset from the start of its compilation unit's
+    /// header.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get this entry's abbreviation code.
+    pub fn code(&self) -> u64 {
+        self.abbrev.code()
+    }
+
+    /// Get this entry's `DW_TAG_whatever` tag.
+    pub fn tag(&self) -> AbbreviationTag {
+        self.abbrev.tag()
+    }
+
+    /// Return true if this entry has children, false otherwise.
+    pub fn has_children(&self) -> bool {
+        self.abbrev.has_children()
+    }
+
+    /// Iterate over this entry's attributes, in the order they were declared
+    /// in its abbreviation.
+    pub fn attrs(&self) -> AttrsIter<'input, 'abbrev, Endian> {
+        AttrsIter {
+            input: self.attrs_slice,
+            specs: self.abbrev.attributes(),
+            version: self.version,
+            format: self.format,
+            address_size: self.address_size,
+            endian: self.endian,
+        }
+    }
+
+    /// Find the first attribute in this entry with the given name, if any.
+    pub fn attr(&self, name: AttributeName) -> Option<Attribute<'input>> {
+        self.attrs().find(|attr| attr.name() == name)
+    }
+
+    /// Find the value of the first attribute in this entry with the given
+    /// name, if any.
+    pub fn attr_value(&self, name: AttributeName) -> Option<AttributeValue<'input>> {
+        self.attr(name).map(|attr| attr.value())
+    }
+}
+
+/// An iterator over a particular entry's attributes.
+///
+/// The attributes were already validated when the entry was read by its
+/// `EntriesCursor`, so iterating over them cannot fail.
+#[derive(Debug, Clone)]
+pub struct AttrsIter<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    specs: &'abbrev [AttributeSpecification],
+    version: u16,
+    format: Format,
+    address_size: u8,
+    endian: Endian,
+}
+
+impl<'input, 'abbrev, Endian> Iterator for AttrsIter<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    type Item = Attribute<'input>;
+
+    fn next(&mut self) -> Option<Attribute<'input>> {
+        let (spec, specs) = self.specs.split_first()?;
+
+        match parse_attribute(self.input,
+                              *spec,
+                              self.version,
+                              self.format,
+                              self.address_size,
+                              self.endian) {
+            IResult::Done(rest, attr) => {
+                self.input = rest;
+                self.specs = specs;
+                Some(attr)
+            },
+            _ => {
+                self.specs = &[];
+                None
+            },
+        }
+    }
+}
+
+/// A cursor into the debugging information entries of a single compilation
+/// unit, which walks the entries in depth-first order.
+#[derive(Debug, Clone)]
+pub struct EntriesCursor<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    entries: &'input [u8],
+    input: &'input [u8],
+    entries_offset: u64,
+    abbrevs: &'abbrev Abbreviations,
+    version: u16,
+    format: Format,
+    address_size: u8,
+    endian: Endian,
+    cached_current: Option<DebuggingInformationEntry<'input, 'abbrev, Endian>>,
+    delta_depth: isize,
+}
+
+impl<'input, 'abbrev, Endian> EntriesCursor<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    fn new(entries: &'input [u8],
+           entries_offset: u64,
+           abbrevs: &'abbrev Abbreviations,
+           version: u16,
+           format: Format,
+           address_size: u8,
+           endian: Endian)
+           -> EntriesCursor<'input, 'abbrev, Endian> {
+        EntriesCursor {
+            entries,
+            input: entries,
+            entries_offset,
+            abbrevs,
+            version,
+            format,
+            address_size,
+            endian,
+            cached_current: None,
+            delta_depth: 0,
+        }
+    }
+
+    /// Get a reference to the entry that the cursor is currently pointing to.
+    ///
+    /// Returns `None` before the first call to `next_entry` or `next_dfs`, at
+    /// a null entry, and once the end of the unit has been reached.
+    pub fn current(&self) -> Option<&DebuggingInformationEntry<'input, 'abbrev, Endian>> {
+        self.cached_current.as_ref()
+    }
+
+    /// Move the cursor to the next entry in the unit, whether or not it is a
+    /// null entry.
+    ///
+    /// Returns `Ok(Some(()))` if the cursor moved to another entry, in which
+    /// case `current` is `None` if that entry is a null entry that ends a chain
+    /// of siblings. Returns `Ok(None)` once the end of the unit is reached.
+    pub fn next_entry(&mut self) -> Result<Option<()>, Error> {
+        if self.input.is_empty() {
+            self.cached_current = None;
+            self.delta_depth = 0;
+            return Ok(None);
+        }
+
+        let offset = self.entries_offset + (self.entries.len() - self.input.len()) as u64;
+        let (rest, code) = into_result(parse_unsigned_leb(self.input))?;
+        if code == 0 {
+            self.input = rest;
+            self.cached_current = None;
+            self.delta_depth = -1;
+            return Ok(Some(()));
+        }
+
+        let abbrev = match self.abbrevs.get(code) {
+            Some(abbrev) => abbrev,
+            None => return Err(Error::UnknownAbbreviation),
+        };
+
+        let (after_attrs, _) = into_result(skip_attributes(rest,
+                                                           abbrev.attributes(),
+                                                           self.version,
+                                                           self.format,
+                                                           self.address_size,
+                                                           self.endian))?;
+
+        self.input = after_attrs;
+        self.cached_current = Some(DebuggingInformationEntry {
+            offset,
+            attrs_slice: &rest[..rest.len() - after_attrs.len()],
+            abbrev,
+            version: self.version,
+            format: self.format,
+            address_size: self.address_size,
+            endian: self.endian,
+        });
+        self.delta_depth = if abbrev.has_children() { 1 } else { 0 };
+        Ok(Some(()))
+    }
+
+    /// Move the cursor to the next sibling of the current entry, skipping over
+    /// all of the current entry's children.
+    ///
+    /// If the current entry has a `DW_AT_sibling` attribute, then the children
+    /// are skipped without being parsed at all. Otherwise, the children are
+    /// skipped entry by entry, without decoding their attributes.
+    ///
+    /// Returns `Ok(None)` if the current entry has no next sibling, in which
+    /// case the cursor is left at the null entry that ends the sibling chain
+    /// (or at the end of the unit).
+    pub fn next_sibling(&mut self)
+                        -> Result<Option<&DebuggingInformationEntry<'input, 'abbrev, Endian>>,
+                                  Error> {
+        let (has_children, sibling) = match self.cached_current {
+            Some(ref current) => {
+                let sibling = match current.attr_value(AttributeName::Sibling) {
+                    Some(AttributeValue::UnitRef(offset)) if offset > current.offset => Some(offset),
+                    _ => None,
+                };
+                (current.has_children(), sibling)
+            },
+            None => return Ok(None),
+        };
+
+        if has_children {
+            match sibling {
+                Some(offset) if self.contains_offset(offset) => {
+                    self.input = &self.entries[(offset - self.entries_offset) as usize..];
+                },
+                _ => {
+                    let mut depth = 1;
+                    while depth > 0 {
+                        if self.next_entry()?.is_none() {
+                            return Ok(None);
+                        }
+                        depth += self.delta_depth;
+                    }
+                },
+            }
+        }
+
+        self.next_entry()?;
+        Ok(self.cached_current.as_ref())
+    }
+
+    /// Return true if the given unit offset is within this cursor's entries.
+    fn contains_offset(&self, offset: u64) -> bool {
+        self.entries_offset <= offset && offset - self.entries_offset <= self.entries.len() as u64
+    }
+
+    /// Move the cursor to the entry at the given offset from the start of the
+    /// unit's header.
+    fn seek(&mut self, offset: u64) -> Result<(), Error> {
+        if !self.contains_offset(offset) {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        self.input = &self.entries[(offset - self.entries_offset) as usize..];
+        self.next_entry()?;
+        Ok(())
+    }
+
+    /// Move the cursor to the next entry in depth-first order, skipping over
+    /// null entries.
+    ///
+    /// Returns the change in depth relative to the previous entry along with
+    /// the new entry, or `Ok(None)` once the end of the unit is reached. A
+    /// delta of `1` means the new entry is the first child of the previous
+    /// entry, `0` means it is the previous entry's next sibling, and negative
+    /// deltas mean that the previous entry's sibling chain (and perhaps its
+    /// parents' chains) have ended.
+    pub fn next_dfs(&mut self)
+                    -> Result<Option<(isize, &DebuggingInformationEntry<'input, 'abbrev, Endian>)>,
+                              Error> {
+        let mut delta_depth = self.delta_depth;
+        loop {
+            if self.next_entry()?.is_none() {
+                return Ok(None);
+            }
+
+            if self.cached_current.is_some() {
+                break;
+            }
+
+            delta_depth += self.delta_depth;
+        }
+
+        Ok(self.cached_current.as_ref().map(|entry| (delta_depth, entry)))
+    }
+}
+
+/// A cursor for navigating a compilation unit's debugging information entries
+/// as a tree, rather than in a flat depth-first order.
+///
+/// The cursor starts at the unit's root entry. Moving to the next sibling uses
+/// the current entry's `DW_AT_sibling` attribute to skip its children when
+/// possible.
+#[derive(Debug, Clone)]
+pub struct EntriesTreeCursor<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    cursor: EntriesCursor<'input, 'abbrev, Endian>,
+    parents: Vec<u64>,
+}
+
+impl<'input, 'abbrev, Endian> EntriesTreeCursor<'input, 'abbrev, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new tree cursor from a cursor that has not been moved yet,
+    /// positioning it at the unit's root entry.
+    fn new(mut cursor: EntriesCursor<'input, 'abbrev, Endian>)
+           -> Result<EntriesTreeCursor<'input, 'abbrev, Endian>, Error> {
+        cursor.next_entry()?;
+        Ok(EntriesTreeCursor {
+            cursor,
+            parents: Vec::new(),
+        })
+    }
+
+    /// Get the entry that the cursor is currently pointing to, or `None` if
+    /// the unit has no entries.
+    pub fn current(&self) -> Option<&DebuggingInformationEntry<'input, 'abbrev, Endian>> {
+        self.cursor.current()
+    }
+
+    /// Get the depth of the current entry, where the root entry is at depth 0.
+    pub fn depth(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Move the cursor to the first child of the current entry.
+    ///
+    /// Returns `Ok(false)`, and leaves the cursor where it is, if the current
+    /// entry has no children.
+    pub fn move_to_first_child(&mut self) -> Result<bool, Error> {
+        let offset = match self.cursor.current() {
+            Some(current) if current.has_children() => current.offset(),
+            _ => return Ok(false),
+        };
+
+        let mut cursor = self.cursor.clone();
+        cursor.next_entry()?;
+        if cursor.current().is_none() {
+            return Ok(false);
+        }
+
+        self.cursor = cursor;
+        self.parents.push(offset);
+        Ok(true)
+    }
+
+    /// Move the cursor to the next sibling of the current entry, skipping over
+    /// the current entry's children.
+    ///
+    /// Returns `Ok(false)`, and leaves the cursor where it is, if the current
+    /// entry is the last of its siblings.
+    pub fn move_to_next_sibling(&mut self) -> Result<bool, Error> {
+        if self.cursor.current().is_none() {
+            return Ok(false);
+        }
+
+        let mut cursor = self.cursor.clone();
+        if cursor.next_sibling()?.is_none() {
+            return Ok(false);
+        }
+
+        self.cursor = cursor;
+        Ok(true)
+    }
+
+    /// Move the cursor back up to the parent of the current entry.
+    ///
+    /// Returns `Ok(false)`, and leaves the cursor where it is, if the current
+    /// entry is the root.
+    pub fn move_to_parent(&mut self) -> Result<bool, Error> {
+        let offset = match self.parents.last() {
+            Some(&offset) => offset,
+            None => return Ok(false),
+        };
+
+        self.cursor.seek(offset)?;
+        self.parents.pop();
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+fn test_abbrevs() -> Abbreviations {
+    let buf = [
+        // Code 1: DW_TAG_compile_unit, has children, DW_AT_name as DW_FORM_string.
+        0x01, 0x11, 0x01,
+        0x03, 0x08,
+        0x00, 0x00,
+
+        // Code 2: DW_TAG_subprogram, has children, DW_AT_name as
+        // DW_FORM_string and DW_AT_low_pc as DW_FORM_addr.
+        0x02, 0x2e, 0x01,
+        0x03, 0x08,
+        0x11, 0x01,
+        0x00, 0x00,
+
+        // Code 3: DW_TAG_variable, no children, DW_AT_name as DW_FORM_indirect.
+        0x03, 0x34, 0x00,
+        0x03, 0x16,
+        0x00, 0x00,
+
+        // Code 4: DW_TAG_lexical_block, has children, DW_AT_sibling as
+        // DW_FORM_ref4.
+        0x04, 0x0b, 0x01,
+        0x01, 0x13,
+        0x00, 0x00,
+
+        // Null abbreviation.
+        0x00
+    ];
+
+    match parse_abbreviations(&buf) {
+        IResult::Done(_, abbrevs) => abbrevs,
+        _ => panic!("Failed to parse the test abbreviations"),
+    }
+}
+
+#[cfg(test)]
+const TEST_DEBUG_INFO: [u8; 43] = [
+    0x1c, 0x00, 0x00, 0x00, // 32-bit unit length
+    0x04, 0x00,             // version 4
+    0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+    0x04,                   // address size
+
+    // Offset 11: compile unit named "cu".
+    0x01, b'c', b'u', 0x00,
+
+    // Offset 15: subprogram named "f" at 0x04030201.
+    0x02, b'f', 0x00, 0x01, 0x02, 0x03, 0x04,
+
+    // Offset 22: variable named "x", with an indirect DW_FORM_string.
+    0x03, 0x08, b'x', 0x00,
+
+    // Offset 26: end of the subprogram's children.
+    0x00,
+
+    // Offset 27: variable named "y", with an indirect DW_FORM_string.
+    0x03, 0x08, b'y', 0x00,
+
+    // Offset 31: end of the compile unit's children.
+    0x00,
+
+    // The next unit, which should not be visited.
+    0x01, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+];
+
+#[test]
+fn test_entries_cursor_next_dfs() {
+    let abbrevs = test_abbrevs();
+    let input = DebugInfoInput::new(&TEST_DEBUG_INFO[..32], LittleEndian);
+
+    let (rest, header) = match parse_compilation_unit_header(input) {
+        IResult::Done(rest, header) => (rest, header),
+        _ => panic!("Failed to parse the compilation unit header"),
+    };
+    let mut cursor = rest.entries(&header, &abbrevs).expect("Should bound the entries");
+
+    let expected = [(0, 11, AbbreviationTag::CompileUnit, &b"cu"[..]),
+                    (1, 15, AbbreviationTag::Subprogram, &b"f"[..]),
+                    (1, 22, AbbreviationTag::Variable, &b"x"[..]),
+                    (-1, 27, AbbreviationTag::Variable, &b"y"[..])];
+
+    for &(expected_delta, offset, tag, name) in expected.iter() {
+        let (delta, entry) = cursor.next_dfs()
+            .expect("Should parse the next entry")
+            .expect("Should have another entry");
+        assert_eq!(delta, expected_delta);
+        assert_eq!(entry.offset(), offset);
+        assert_eq!(entry.tag(), tag);
+
+        let attr = entry.attr(AttributeName::Name).expect("Should have a name");
+        assert_eq!(attr.value(), AttributeValue::String(name));
+    }
+
+    assert!(cursor.next_dfs().expect("Should parse the end of the unit").is_none());
+    assert!(cursor.current().is_none());
+}
+
+#[test]
+fn test_entries_cursor_next_entry_null_entries() {
+    let abbrevs = test_abbrevs();
+    let input = DebugInfoInput::new(&TEST_DEBUG_INFO, LittleEndian);
+
+    let (rest, header) = match parse_compilation_unit_header(input) {
+        IResult::Done(rest, header) => (rest, header),
+        _ => panic!("Failed to parse the compilation unit header"),
+    };
+    let mut cursor = rest.entries(&header, &abbrevs).expect("Should bound the entries");
+
+    let mut offsets = Vec::new();
+    while cursor.next_entry().expect("Should parse the next entry").is_some() {
+        offsets.push(cursor.current().map(|entry| entry.offset()));
+    }
+
+    assert_eq!(offsets, vec![Some(11), Some(15), Some(22), None, Some(27), None]);
+
+    let subprogram_attrs: Vec<_> = {
+        let mut cursor = rest.entries(&header, &abbrevs).expect("Should bound the entries");
+        cursor.next_dfs().unwrap();
+        let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+        entry.attrs().map(|attr| (attr.name(), attr.value())).collect()
+    };
+    assert_eq!(subprogram_attrs,
+               vec![(AttributeName::Name, AttributeValue::String(b"f")),
+                    (AttributeName::LowPc, AttributeValue::Addr(0x04030201))]);
+}
+
+#[test]
+fn test_entries_cursor_unknown_abbreviation() {
+    let buf = [
+        0x08, 0x00, 0x00, 0x00, // 32-bit unit length
+        0x04, 0x00,             // version 4
+        0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x04,                   // address size
+        0x2a,                   // unknown abbreviation code
+    ];
+    let abbrevs = test_abbrevs();
+
+    let input = DebugInfoInput::new(&buf, LittleEndian);
+    let (rest, header) = match parse_compilation_unit_header(input) {
+        IResult::Done(rest, header) => (rest, header),
+        _ => panic!("Failed to parse the compilation unit header"),
+    };
+
+    match rest.entries(&header, &abbrevs).expect("Should bound the entries").next_dfs() {
+        Err(Error::UnknownAbbreviation) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_debug_info_input_entries_truncated() {
+    let abbrevs = test_abbrevs();
+    let input = DebugInfoInput::new(&TEST_DEBUG_INFO[..31], LittleEndian);
+
+    let (rest, header) = match parse_compilation_unit_header(input) {
+        IResult::Done(rest, header) => (rest, header),
+        _ => panic!("Failed to parse the compilation unit header"),
+    };
+    assert!(matches!(rest.entries(&header, &abbrevs), Err(Error::UnexpectedEof)));
+}
+
+#[cfg(test)]
+const TEST_DEBUG_INFO_TREE: [u8; 50] = [
+    0x2e, 0x00, 0x00, 0x00, // 32-bit unit length
+    0x04, 0x00,             // version 4
+    0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+    0x04,                   // address size
+
+    // Offset 11: compile unit named "cu".
+    0x01, b'c', b'u', 0x00,
+
+    // Offset 15: lexical block whose sibling is at offset 37.
+    0x04, 0x25, 0x00, 0x00, 0x00,
+
+    // Offset 20: variable named "a".
+    0x03, 0x08, b'a', 0x00,
+
+    // Offset 24: subprogram named "g".
+    0x02, b'g', 0x00, 0x01, 0x02, 0x03, 0x04,
+
+    // Offset 31: variable named "b".
+    0x03, 0x08, b'b', 0x00,
+
+    // Offset 35: end of g's children.
+    0x00,
+
+    // Offset 36: end of the lexical block's children.
+    0x00,
+
+    // Offset 37: subprogram named "h", with no children despite its
+    // abbreviation.
+    0x02, b'h', 0x00, 0x01, 0x02, 0x03, 0x04,
+    0x00,
+
+    // Offset 45: variable named "c".
+    0x03, 0x08, b'c', 0x00,
+
+    // Offset 49: end of the compile unit's children.
+    0x00,
+];
+
+#[test]
+fn test_entries_cursor_next_sibling() {
+    let abbrevs = test_abbrevs();
+    let unit = CompilationUnitsIter::new(&TEST_DEBUG_INFO_TREE, LittleEndian)
+        .next()
+        .unwrap()
+        .unwrap();
+    let mut cursor = unit.entries(&abbrevs);
+
+    cursor.next_dfs().unwrap();
+    let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+    assert_eq!(entry.offset(), 15);
+
+    let offsets = [37, 45];
+    for &offset in offsets.iter() {
+        let entry = cursor.next_sibling().unwrap().expect("Should have a sibling");
+        assert_eq!(entry.offset(), offset);
+    }
+    assert!(cursor.next_sibling().unwrap().is_none());
+    assert!(cursor.next_dfs().unwrap().is_none());
+}
+
+#[test]
+fn test_entries_cursor_next_sibling_uses_sibling_attribute() {
+    // Corrupt the lexical block's children, which should never be parsed
+    // because the block has a DW_AT_sibling attribute.
+    let mut buf = TEST_DEBUG_INFO_TREE;
+    for byte in &mut buf[20..37] {
+        *byte = 0x7f;
+    }
+
+    let abbrevs = test_abbrevs();
+    let unit = CompilationUnitsIter::new(&buf, LittleEndian).next().unwrap().unwrap();
+    let mut cursor = unit.entries(&abbrevs);
+
+    cursor.next_dfs().unwrap();
+    cursor.next_dfs().unwrap();
+    let entry = cursor.next_sibling().unwrap().expect("Should have a sibling");
+    assert_eq!(entry.offset(), 37);
+    assert_eq!(entry.attr_value(AttributeName::Name), Some(AttributeValue::String(b"h")));
+
+    // Whereas walking into the lexical block's children fails.
+    let mut cursor = unit.entries(&abbrevs);
+    cursor.next_dfs().unwrap();
+    cursor.next_dfs().unwrap();
+    assert!(cursor.next_dfs().is_err());
+}
+
+#[test]
+fn test_entries_tree_cursor() {
+    let abbrevs = test_abbrevs();
+    let unit = CompilationUnitsIter::new(&TEST_DEBUG_INFO_TREE, LittleEndian)
+        .next()
+        .unwrap()
+        .unwrap();
+    let mut tree = unit.entries_tree(&abbrevs).expect("Should parse the root");
+
+    let assert_current = |tree: &EntriesTreeCursor<LittleEndian>, offset, depth| {
+        assert_eq!(tree.current().map(|entry| entry.offset()), Some(offset));
+        assert_eq!(tree.depth(), depth);
+    };
+
+    assert_current(&tree, 11, 0);
+    assert!(!tree.move_to_next_sibling().unwrap());
+    assert!(!tree.move_to_parent().unwrap());
+
+    assert!(tree.move_to_first_child().unwrap());
+    assert_current(&tree, 15, 1);
+
+    assert!(tree.move_to_first_child().unwrap());
+    assert_current(&tree, 20, 2);
+    assert!(!tree.move_to_first_child().unwrap());
+
+    assert!(tree.move_to_next_sibling().unwrap());
+    assert_current(&tree, 24, 2);
+
+    assert!(tree.move_to_first_child().unwrap());
+    assert_current(&tree, 31, 3);
+    assert!(!tree.move_to_next_sibling().unwrap());
+    assert_current(&tree, 31, 3);
+
+    assert!(tree.move_to_parent().unwrap());
+    assert_current(&tree, 24, 2);
+    assert!(!tree.move_to_next_sibling().unwrap());
+
+    assert!(tree.move_to_parent().unwrap());
+    assert_current(&tree, 15, 1);
+    assert!(tree.move_to_next_sibling().unwrap());
+    assert_current(&tree, 37, 1);
+
+    assert!(!tree.move_to_first_child().unwrap());
+    assert!(tree.move_to_next_sibling().unwrap());
+    assert_current(&tree, 45, 1);
+    assert!(!tree.move_to_next_sibling().unwrap());
+
+    assert!(tree.move_to_parent().unwrap());
+    assert_current(&tree, 11, 0);
+    assert!(!tree.move_to_parent().unwrap());
+}
+
+#[test]
+fn test_unknown_tags_names_and_forms() {
+    let debug_abbrev = [
+        // Code 1: an unknown tag, no children, and an unknown attribute name
+        // as DW_FORM_string.
+        0x01, 0xff, 0x9f, 0x01, 0x00,
+        0xff, 0x5f, 0x08,
+        0x00, 0x00,
+
+        // Code 2: DW_TAG_variable, no children, DW_AT_name as an unknown form.
+        0x02, 0x34, 0x00,
+        0x03, 0x7f,
+        0x00, 0x00,
+
+        0x00,
+    ];
+    let abbrevs = match parse_abbreviations(&debug_abbrev) {
+        IResult::Done(_, abbrevs) => abbrevs,
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let abbrev = abbrevs.get(2).unwrap();
+    assert_eq!(abbrev.attributes()[0].form(), AttributeForm(0x7f));
+
+    let debug_info = [
+        0x0c, 0x00, 0x00, 0x00, // 32-bit unit length
+        0x04, 0x00,             // version 4
+        0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x04,                   // address size
+        0x01, b'x', 0x00,
+        0x02, 0x00,
+    ];
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian).next().unwrap().unwrap();
+    let mut cursor = unit.entries(&abbrevs);
+
+    {
+        let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+        assert_eq!(entry.tag(), AbbreviationTag(0x4fff));
+        assert!(entry.tag().is_user_defined());
+
+        let attr = entry.attrs().next().expect("Should have an attribute");
+        assert_eq!(attr.name(), AttributeName(0x2fff));
+        assert!(attr.name().is_user_defined());
+        assert_eq!(attr.value(), AttributeValue::String(b"x"));
+    }
+
+    match cursor.next_dfs() {
+        Err(Error::InvalidAttributeForm) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
+
+#[test]
+fn test_parse_abbreviations_vendor_extensions() {
+    let buf = [
+        // Code 1: DW_TAG_GNU_call_site, no children, DW_AT_abstract_origin as
+        // DW_FORM_GNU_ref_alt and DW_AT_GNU_tail_call as DW_FORM_flag_present.
+        0x01, 0x89, 0x82, 0x01, 0x00,
+        0x31, 0xa0, 0x3e,
+        0x95, 0x42, 0x19,
+        0x00, 0x00,
+
+        // Code 2: DW_TAG_subprogram, no children, DW_AT_MIPS_linkage_name as
+        // DW_FORM_GNU_strp_alt and DW_AT_APPLE_optimized as DW_FORM_flag.
+        0x02, 0x2e, 0x00,
+        0x87, 0x40, 0xa1, 0x3e,
+        0xe1, 0x7f, 0x0c,
+        0x00, 0x00,
+
+        0x00,
+    ];
+
+    let abbrevs = match parse_abbreviations(&buf) {
+        IResult::Done(rest, abbrevs) => {
+            assert_eq!(rest.len(), 0);
+            abbrevs
+        },
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let call_site = abbrevs.get(1).unwrap();
+    assert_eq!(call_site.tag(), AbbreviationTag::GnuCallSite);
+    assert_eq!(call_site.attributes(),
+               &[AttributeSpecification::new(AttributeName::AbstractOrigin,
+                                             AttributeForm::GnuRefAlt),
+                 AttributeSpecification::new(AttributeName::GnuTailCall,
+                                             AttributeForm::FlagPresent)]);
+
+    let subprogram = abbrevs.get(2).unwrap();
+    assert_eq!(subprogram.attributes(),
+               &[AttributeSpecification::new(AttributeName::MipsLinkageName,
+                                             AttributeForm::GnuStrpAlt),
+                 AttributeSpecification::new(AttributeName::AppleOptimized,
+                                             AttributeForm::Flag)]);
+}
+
+#[test]
+fn test_entries_cursor_dwarf2() {
+    let debug_abbrev = [
+        // Code 1: DW_TAG_variable, no children, DW_AT_type as DW_FORM_ref_addr
+        // and DW_AT_external as DW_FORM_flag_present.
+        0x01, 0x34, 0x00,
+        0x49, 0x10,
+        0x3f, 0x19,
+        0x00, 0x00,
+
+        // Code 2: DW_TAG_variable, no children, DW_AT_type as DW_FORM_ref_addr
+        // and DW_AT_name as DW_FORM_string.
+        0x02, 0x34, 0x00,
+        0x49, 0x10,
+        0x03, 0x08,
+        0x00, 0x00,
+
+        0x00,
+    ];
+    let abbrevs = match parse_abbreviations(&debug_abbrev) {
+        IResult::Done(_, abbrevs) => abbrevs,
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let debug_info = [
+        0x1b, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x02, 0x00,                                     // version 2
+        0x00, 0x00, 0x00, 0x00,                         // debug_abbrev_offset
+        0x08,                                           // address size
+        0x02,                                           // abbreviation code
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // address sized ref_addr
+        b'a', 0x00,
+        0x01,                                           // abbreviation code
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // address sized ref_addr
+    ];
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian).next().unwrap().unwrap();
+    let mut cursor = unit.entries(&abbrevs);
+
+    {
+        let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+        assert_eq!(entry.attr_value(AttributeName::Type),
+                   Some(AttributeValue::DebugInfoRef(0x0807060504030201)));
+        assert_eq!(entry.attr_value(AttributeName::Name), Some(AttributeValue::String(b"a")));
+    }
+
+    match cursor.next_dfs() {
+        Err(Error::InvalidAttributeForm) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise.map(|_| ())),
+    };
+}
+
+#[test]
+fn test_entries_cursor_implicit_const() {
+    let debug_abbrev = [
+        // Code 1: DW_TAG_variable, no children, DW_AT_decl_file as
+        // DW_FORM_implicit_const with value 2, DW_AT_name as DW_FORM_strx1 and
+        // DW_AT_decl_line as DW_FORM_data1.
+        0x01, 0x34, 0x00,
+        0x3a, 0x21, 0x02,
+        0x03, 0x25,
+        0x3b, 0x0b,
+        0x00, 0x00,
+
+        0x00,
+    ];
+    let abbrevs = match parse_abbreviations(&debug_abbrev) {
+        IResult::Done(_, abbrevs) => abbrevs,
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let debug_info = [
+        0x0e, 0x00, 0x00, 0x00, // 32-bit unit length
+        0x05, 0x00,             // version 5
+        0x01,                   // DW_UT_compile
+        0x04,                   // address size
+        0x00, 0x00, 0x00, 0x00, // debug_abbrev_offset
+        0x01, 0x07, 0x2a,
+        0x01, 0x08, 0x2b,
+    ];
+    let unit = CompilationUnitsIter::new(&debug_info, LittleEndian).next().unwrap().unwrap();
+    let mut cursor = unit.entries(&abbrevs);
+
+    for &(offset, index, line) in [(12, 7, 42), (15, 8, 43)].iter() {
+        let (_, entry) = cursor.next_dfs().unwrap().expect("Should have an entry");
+        assert_eq!(entry.offset(), offset);
+        let attrs: Vec<_> = entry.attrs().map(|attr| attr.value()).collect();
+        assert_eq!(attrs,
+                   vec![AttributeValue::Sdata(2),
+                        AttributeValue::DebugStrOffsetsIndex(index),
+                        AttributeValue::Data1(line)]);
+    }
+
+    assert!(cursor.next_dfs().unwrap().is_none());
 }
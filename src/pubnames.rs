@@ -0,0 +1,436 @@
+//! Functions for parsing the `.debug_pubnames` and `.debug_pubtypes` sections,
+//! and their `.debug_gnu_pubnames` and `.debug_gnu_pubtypes` variants.
+
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use nom::{Err, ErrorKind, IResult};
+use parser::{Error, Format, into_result, parse_initial_length, parse_null_terminated_string,
+             parse_offset, parse_u16, parse_u8, ParseResult, take};
+use types::GdbIndexSymbolKind;
+
+/// The `.debug_pubnames` section, which maps the names of global objects and
+/// functions to their debugging information entries.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugPubNames<'input, Endian>
+    where Endian: Endianity
+{
+    debug_pubnames_section: &'input [u8],
+    gnu: bool,
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugPubNames<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugPubNames` instance from the data in the
+    /// `.debug_pubnames` section, which is encoded in the given byte order.
+    pub fn new(debug_pubnames_section: &'input [u8],
+               endian: Endian)
+               -> DebugPubNames<'input, Endian> {
+        DebugPubNames {
+            debug_pubnames_section,
+            gnu: false,
+            endian,
+        }
+    }
+
+    /// Construct a new `DebugPubNames` instance from the data in the
+    /// `.debug_gnu_pubnames` section, whose entries also have GDB index
+    /// attributes.
+    pub fn new_gnu(debug_gnu_pubnames_section: &'input [u8],
+                   endian: Endian)
+                   -> DebugPubNames<'input, Endian> {
+        DebugPubNames {
+            debug_pubnames_section: debug_gnu_pubnames_section,
+            gnu: true,
+            endian,
+        }
+    }
+
+    /// Iterate over the sets of names in this section, one for each
+    /// compilation unit that has any.
+    pub fn sets(&self) -> PubSetsIter<'input, Endian> {
+        PubSetsIter::new(self.debug_pubnames_section, self.gnu, self.endian)
+    }
+}
+
+/// The `.debug_pubtypes` section, which maps the names of global types to their
+/// debugging information entries.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugPubTypes<'input, Endian>
+    where Endian: Endianity
+{
+    debug_pubtypes_section: &'input [u8],
+    gnu: bool,
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugPubTypes<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugPubTypes` instance from the data in the
+    /// `.debug_pubtypes` section, which is encoded in the given byte order.
+    pub fn new(debug_pubtypes_section: &'input [u8],
+               endian: Endian)
+               -> DebugPubTypes<'input, Endian> {
+        DebugPubTypes {
+            debug_pubtypes_section,
+            gnu: false,
+            endian,
+        }
+    }
+
+    /// Construct a new `DebugPubTypes` instance from the data in the
+    /// `.debug_gnu_pubtypes` section, whose entries also have GDB index
+    /// attributes.
+    pub fn new_gnu(debug_gnu_pubtypes_section: &'input [u8],
+                   endian: Endian)
+                   -> DebugPubTypes<'input, Endian> {
+        DebugPubTypes {
+            debug_pubtypes_section: debug_gnu_pubtypes_section,
+            gnu: true,
+            endian,
+        }
+    }
+
+    /// Iterate over the sets of names in this section, one for each
+    /// compilation unit that has any.
+    pub fn sets(&self) -> PubSetsIter<'input, Endian> {
+        PubSetsIter::new(self.debug_pubtypes_section, self.gnu, self.endian)
+    }
+}
+
+/// The header of a set of names in the `.debug_pubnames` or `.debug_pubtypes`
+/// sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PubSetHeader {
+    offset: u64,
+    unit_length: u64,
+    format: Format,
+    version: u16,
+    debug_info_offset: u64,
+    debug_info_length: u64,
+}
+
+impl PubSetHeader {
+    /// Get the offset of this header from the start of its section.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the length of this set, not including its initial length field.
+    pub fn unit_length(&self) -> u64 {
+        self.unit_length
+    }
+
+    /// Return true if this set uses the 64-bit DWARF format, false if it uses
+    /// the 32-bit format.
+    pub fn is_dwarf64(&self) -> bool {
+        self.format == Format::Dwarf64
+    }
+
+    /// Get the version of this set's encoding.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get the offset of the compilation unit's header in the `.debug_info`
+    /// section.
+    pub fn debug_info_offset(&self) -> u64 {
+        self.debug_info_offset
+    }
+
+    /// Get the size of the compilation unit in the `.debug_info` section,
+    /// including its header.
+    pub fn debug_info_length(&self) -> u64 {
+        self.debug_info_length
+    }
+}
+
+/// A name in a set of names, along with the debugging information entry that
+/// it names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PubEntry<'input> {
+    debug_info_offset: u64,
+    die_offset: u64,
+    gdb_index_attributes: Option<u8>,
+    name: &'input [u8],
+}
+
+impl<'input> PubEntry<'input> {
+    /// Get the offset of the compilation unit's header in the `.debug_info`
+    /// section, which is where `CompilationUnitsIter` and
+    /// `parse_compilation_unit_header` start to parse the unit.
+    pub fn debug_info_offset(&self) -> u64 {
+        self.debug_info_offset
+    }
+
+    /// Get the offset of the debugging information entry from the start of
+    /// its compilation unit's header, as returned by
+    /// `DebuggingInformationEntry::offset`.
+    pub fn die_offset(&self) -> u64 {
+        self.die_offset
+    }
+
+    /// Get the name, without its null terminator.
+    pub fn name(&self) -> &'input [u8] {
+        self.name
+    }
+
+    /// Get the kind of symbol that this entry names, if it is from a
+    /// `.debug_gnu_pubnames` or `.debug_gnu_pubtypes` section.
+    pub fn kind(&self) -> Option<GdbIndexSymbolKind> {
+        self.gdb_index_attributes.map(|attributes| GdbIndexSymbolKind((attributes >> 4) & 0x7))
+    }
+
+    /// Return whether the symbol that this entry names is static rather than
+    /// global, if it is from a `.debug_gnu_pubnames` or `.debug_gnu_pubtypes`
+    /// section.
+    pub fn is_static(&self) -> Option<bool> {
+        self.gdb_index_attributes.map(|attributes| attributes & 0x80 != 0)
+    }
+}
+
+/// A set of names that belong to a single compilation unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PubSet<'input> {
+    header: PubSetHeader,
+    entries: Vec<PubEntry<'input>>,
+}
+
+impl<'input> PubSet<'input> {
+    /// Get this set's header.
+    pub fn header(&self) -> &PubSetHeader {
+        &self.header
+    }
+
+    /// Get this set's names, not including the terminating entry.
+    pub fn entries(&self) -> &[PubEntry<'input>] {
+        &self.entries[..]
+    }
+}
+
+/// Parse a set of names, which is at the given offset in its section.
+fn parse_pub_set<'input, Endian>(input: &'input [u8],
+                                 offset: u64,
+                                 gnu: bool,
+                                 endian: Endian)
+                                 -> ParseResult<&'input [u8], PubSet<'input>>
+    where Endian: Endianity
+{
+    let (rest, (unit_length, format)) = try_parse!(input, call!(parse_initial_length, endian));
+    let (after_unit, unit) = try_parse!(rest, call!(take, unit_length));
+
+    let (rest, version) = try_parse!(unit, call!(parse_u16, endian));
+    if version != 2 {
+        return IResult::Error(Err::Position(ErrorKind::Custom(Error::UnknownDwarfVersion),
+                                            input));
+    }
+
+    let (rest, debug_info_offset) = try_parse!(rest, call!(parse_offset, format, endian));
+    let (mut rest, debug_info_length) = try_parse!(rest, call!(parse_offset, format, endian));
+
+    let mut entries = Vec::new();
+    while !rest.is_empty() {
+        let (rest1, die_offset) = try_parse!(rest, call!(parse_offset, format, endian));
+        if die_offset == 0 {
+            break;
+        }
+
+        let (rest1, gdb_index_attributes) = if gnu {
+            let (rest1, attributes) = try_parse!(rest1, parse_u8);
+            (rest1, Some(attributes))
+        } else {
+            (rest1, None)
+        };
+        let (rest1, name) = try_parse!(rest1, parse_null_terminated_string);
+        rest = rest1;
+
+        entries.push(PubEntry {
+            debug_info_offset,
+            die_offset,
+            gdb_index_attributes,
+            name,
+        });
+    }
+
+    IResult::Done(after_unit,
+                  PubSet {
+                      header: PubSetHeader {
+                          offset,
+                          unit_length,
+                          format,
+                          version,
+                          debug_info_offset,
+                          debug_info_length,
+                      },
+                      entries,
+                  })
+}
+
+/// An iterator over the sets of names in a `.debug_pubnames` or
+/// `.debug_pubtypes` section, or in one of their GNU variants.
+#[derive(Debug, Clone)]
+pub struct PubSetsIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    offset: u64,
+    gnu: bool,
+    endian: Endian,
+}
+
+impl<'input, Endian> PubSetsIter<'input, Endian>
+    where Endian: Endianity
+{
+    fn new(input: &'input [u8], gnu: bool, endian: Endian) -> PubSetsIter<'input, Endian> {
+        PubSetsIter {
+            input,
+            offset: 0,
+            gnu,
+            endian,
+        }
+    }
+}
+
+impl<'input, Endian> Iterator for PubSetsIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<PubSet<'input>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match into_result(parse_pub_set(self.input, self.offset, self.gnu, self.endian)) {
+            Ok((rest, set)) => {
+                self.offset += (self.input.len() - rest.len()) as u64;
+                self.input = rest;
+                Some(Ok(set))
+            },
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+#[test]
+fn test_parse_pubnames() {
+    let buf = [
+        // A 32-bit set.
+        0x1f, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x02, 0x00,                                     // version 2
+        0x10, 0x00, 0x00, 0x00,                         // debug_info_offset
+        0x40, 0x00, 0x00, 0x00,                         // debug_info_length
+        0x0b, 0x00, 0x00, 0x00, b'm', b'a', b'i', b'n', 0x00,
+        0x20, 0x00, 0x00, 0x00, b'f', b'o', b'o', 0x00,
+        0x00, 0x00, 0x00, 0x00,                         // terminator
+
+        // A 64-bit set.
+        0xff, 0xff, 0xff, 0xff,                         // enable 64-bit
+        0x1c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // unit length
+        0x02, 0x00,                                     // version 2
+        0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // debug_info_offset
+        0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // debug_info_length
+        0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'x', 0x00,
+    ];
+
+    let debug_pubnames = DebugPubNames::new(&buf, LittleEndian);
+    let sets = debug_pubnames.sets()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the sets");
+    assert_eq!(sets.len(), 2);
+
+    let header = sets[0].header();
+    assert_eq!(header.offset(), 0);
+    assert_eq!(header.unit_length(), 0x1f);
+    assert!(!header.is_dwarf64());
+    assert_eq!(header.version(), 2);
+    assert_eq!(header.debug_info_offset(), 0x10);
+    assert_eq!(header.debug_info_length(), 0x40);
+    assert_eq!(sets[0].entries()
+                   .iter()
+                   .map(|entry| (entry.debug_info_offset(), entry.die_offset(), entry.name()))
+                   .collect::<Vec<_>>(),
+               vec![(0x10, 0x0b, &b"main"[..]), (0x10, 0x20, &b"foo"[..])]);
+    assert_eq!(sets[0].entries()[0].kind(), None);
+    assert_eq!(sets[0].entries()[0].is_static(), None);
+
+    let header = sets[1].header();
+    assert_eq!(header.offset(), 0x23);
+    assert!(header.is_dwarf64());
+    assert_eq!(header.debug_info_offset(), 0x50);
+    assert_eq!(header.debug_info_length(), 0x30);
+    assert_eq!(sets[1].entries().len(), 1);
+    assert_eq!(sets[1].entries()[0].die_offset(), 0x17);
+    assert_eq!(sets[1].entries()[0].name(), b"x");
+}
+
+#[test]
+fn test_parse_gnu_pubtypes() {
+    let buf = [
+        0x00, 0x00, 0x00, 0x1e,                         // 32-bit unit length
+        0x00, 0x02,                                     // version 2
+        0x00, 0x00, 0x00, 0x00,                         // debug_info_offset
+        0x00, 0x00, 0x00, 0x40,                         // debug_info_length
+        0x00, 0x00, 0x00, 0x0b, 0x10, b'i', b'n', b't', 0x00,
+        0x00, 0x00, 0x00, 0x20, 0x90, b's', 0x00,
+        0x00, 0x00, 0x00, 0x00,                         // terminator
+    ];
+
+    let debug_pubtypes = DebugPubTypes::new_gnu(&buf, BigEndian);
+    let mut sets = debug_pubtypes.sets();
+    let set = sets.next().expect("Should have a set").expect("Should parse the set");
+    assert!(sets.next().is_none());
+
+    let entries = set.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].name(), b"int");
+    assert_eq!(entries[0].kind(), Some(GdbIndexSymbolKind::Type));
+    assert_eq!(entries[0].is_static(), Some(false));
+    assert_eq!(entries[1].die_offset(), 0x20);
+    assert_eq!(entries[1].kind(), Some(GdbIndexSymbolKind::Type));
+    assert_eq!(entries[1].is_static(), Some(true));
+
+    // The same data in a `.debug_pubtypes` section doesn't have the GDB index
+    // attributes, so the names would include them.
+    let set = DebugPubTypes::new(&buf, BigEndian)
+        .sets()
+        .next()
+        .expect("Should have a set")
+        .expect("Should parse the set");
+    assert_eq!(set.entries()[0].name(), b"\x10int");
+}
+
+#[test]
+fn test_parse_pubnames_errors() {
+    let buf = [
+        0x0e, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x03, 0x00,                                     // version 3
+        0x00, 0x00, 0x00, 0x00,                         // debug_info_offset
+        0x00, 0x00, 0x00, 0x00,                         // debug_info_length
+        0x00, 0x00, 0x00, 0x00,                         // terminator
+    ];
+    match DebugPubNames::new(&buf, LittleEndian).sets().next() {
+        Some(Err(Error::UnknownDwarfVersion)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let buf = [
+        0x0f, 0x00, 0x00, 0x00,                         // 32-bit unit length
+        0x02, 0x00,                                     // version 2
+        0x00, 0x00, 0x00, 0x00,                         // debug_info_offset
+        0x00, 0x00, 0x00, 0x00,                         // debug_info_length
+        0x0b, 0x00, 0x00, 0x00, b'x',                   // unterminated name
+    ];
+    let mut sets = DebugPubNames::new(&buf, LittleEndian).sets();
+    match sets.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(sets.next().is_none());
+}
@@ -0,0 +1,230 @@
+//! Functions for parsing the `.debug_ranges` section.
+
+use endianity::Endianity;
+#[cfg(test)]
+use endianity::{BigEndian, LittleEndian};
+use parser::{Error, into_result, parse_address};
+use types::CompilationUnitHeader;
+
+/// The `.debug_ranges` section, which holds the non-contiguous address ranges
+/// that `DW_AT_ranges` attributes refer to.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugRanges<'input, Endian>
+    where Endian: Endianity
+{
+    debug_ranges_section: &'input [u8],
+    endian: Endian,
+}
+
+impl<'input, Endian> DebugRanges<'input, Endian>
+    where Endian: Endianity
+{
+    /// Construct a new `DebugRanges` instance from the data in the
+    /// `.debug_ranges` section, which is encoded in the given byte order.
+    pub fn new(debug_ranges_section: &'input [u8], endian: Endian) -> DebugRanges<'input, Endian> {
+        DebugRanges {
+            debug_ranges_section,
+            endian,
+        }
+    }
+
+    /// Iterate over the range list at the given offset, which is usually the
+    /// value of a `DW_AT_ranges` attribute in the given compilation unit.
+    ///
+    /// The `base_address` is the unit's base address, as returned by
+    /// `CompilationUnit::base_address`. Base address selection entries in the
+    /// list replace it.
+    pub fn ranges(&self,
+                  offset: u64,
+                  header: &CompilationUnitHeader,
+                  base_address: u64)
+                  -> Result<RangesIter<'input, Endian>, Error> {
+        if offset >= self.debug_ranges_section.len() as u64 {
+            return Err(Error::OffsetOutOfBounds);
+        }
+
+        Ok(RangesIter {
+            input: &self.debug_ranges_section[offset as usize..],
+            address_size: header.address_size(),
+            base_address,
+            endian: self.endian,
+        })
+    }
+}
+
+/// An absolute address range, from a range list or elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    begin: u64,
+    end: u64,
+}
+
+impl Range {
+    /// Construct a new `Range`.
+    pub fn new(begin: u64, end: u64) -> Range {
+        Range {
+            begin,
+            end,
+        }
+    }
+
+    /// Get the address of the first byte in this range.
+    pub fn begin(&self) -> u64 {
+        self.begin
+    }
+
+    /// Get the address of the first byte after the end of this range.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Return true if the given address is within this range.
+    pub fn contains(&self, address: u64) -> bool {
+        self.begin <= address && address < self.end
+    }
+}
+
+/// Get the largest address that fits in the given address size, which marks
+/// base address selection entries in range and location lists.
+pub(crate) fn max_address(address_size: u8) -> u64 {
+    if address_size >= 8 {
+        !0
+    } else {
+        (1 << (8 * address_size as u64)) - 1
+    }
+}
+
+/// An iterator over the ranges of a range list in the `.debug_ranges` section.
+///
+/// Base address selection entries are applied rather than yielded, and empty
+/// ranges are skipped, so every range yielded is absolute and non-empty.
+#[derive(Debug, Clone)]
+pub struct RangesIter<'input, Endian>
+    where Endian: Endianity
+{
+    input: &'input [u8],
+    address_size: u8,
+    base_address: u64,
+    endian: Endian,
+}
+
+impl<'input, Endian> RangesIter<'input, Endian>
+    where Endian: Endianity
+{
+    fn next_range(&mut self) -> Result<Option<Range>, Error> {
+        loop {
+            let (rest, begin) = into_result(parse_address(self.input,
+                                                          self.address_size,
+                                                          self.endian))?;
+            let (rest, end) = into_result(parse_address(rest, self.address_size, self.endian))?;
+            self.input = rest;
+
+            if begin == 0 && end == 0 {
+                self.input = &[];
+                return Ok(None);
+            }
+
+            if begin == max_address(self.address_size) {
+                self.base_address = end;
+                continue;
+            }
+
+            if begin != end {
+                return Ok(Some(Range::new(self.base_address.wrapping_add(begin),
+                                          self.base_address.wrapping_add(end))));
+            }
+        }
+    }
+}
+
+impl<'input, Endian> Iterator for RangesIter<'input, Endian>
+    where Endian: Endianity
+{
+    type Item = Result<Range, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        match self.next_range() {
+            Ok(range) => range.map(Ok),
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+#[test]
+fn test_ranges() {
+    let buf = [
+        0xaa, 0xbb,                                     // another list's data
+        0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // 0x10..0x20
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x10, 0x00, 0x00, // base address 0x1000
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // 0x0..0x8
+        0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, // empty
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // end of list
+        0x30, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, // the next list
+    ];
+
+    let debug_ranges = DebugRanges::new(&buf, LittleEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 4);
+    let ranges = debug_ranges.ranges(2, &header, 0x100)
+        .expect("Should find the list")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Should parse the list");
+    assert_eq!(ranges, vec![Range::new(0x110, 0x120), Range::new(0x1000, 0x1008)]);
+    assert!(ranges[0].contains(0x11f));
+    assert!(!ranges[0].contains(0x120));
+}
+
+#[test]
+fn test_ranges_64() {
+    let buf = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // base address 0x12345678_00000000
+        0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, // 0x10..0x20
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // end of list
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let debug_ranges = DebugRanges::new(&buf, BigEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 8);
+    let mut ranges = debug_ranges.ranges(0, &header, 0).expect("Should find the list");
+    assert_eq!(ranges.next().map(|range| range.expect("Should parse the range")),
+               Some(Range::new(0x12345678_00000010, 0x12345678_00000020)));
+    assert!(ranges.next().is_none());
+}
+
+#[test]
+fn test_ranges_errors() {
+    let buf = [
+        0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, // 0x10..0x20
+        0x00, 0x00, 0x00, 0x00,                         // truncated
+    ];
+
+    let debug_ranges = DebugRanges::new(&buf, LittleEndian);
+    let header = CompilationUnitHeader::new(0, 4, 0, 4);
+    match debug_ranges.ranges(buf.len() as u64, &header, 0) {
+        Err(Error::OffsetOutOfBounds) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+
+    let mut ranges = debug_ranges.ranges(0, &header, 0).expect("Should find the list");
+    assert_eq!(ranges.next().map(|range| range.expect("Should parse the range")),
+               Some(Range::new(0x10, 0x20)));
+    match ranges.next() {
+        Some(Err(Error::UnexpectedEof)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+    assert!(ranges.next().is_none());
+
+    let header = CompilationUnitHeader::new(0, 4, 0, 3);
+    match debug_ranges.ranges(0, &header, 0).expect("Should find the list").next() {
+        Some(Err(Error::UnsupportedAddressSize)) => {},
+        otherwise => panic!("Unexpected result: {:?}", otherwise),
+    };
+}
@@ -1,73 +1,209 @@
-use std::collections::hash_map;
+use std::collections::btree_map;
+use std::fmt;
+use std::slice;
 
-/// Abbreviation tag types, aka `DW_TAG_whatever` in the standard.
+/// Define a newtype over an integer for a family of DWARF constants, along with
+/// a named associated constant for each of the values that we know about.
 ///
-/// DWARF standard 4, section 7.5.4, page 154
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(missing_docs)]
-pub enum AbbreviationTag {
-    ArrayType = 0x01,
-    ClassType = 0x02,
-    EntryPoint = 0x03,
-    EnumerationType = 0x04,
-    FormalParameter = 0x05,
-    ImportedDeclaration = 0x08,
-    Label = 0x0a,
-    LexicalBlock = 0x0b,
-    Member = 0x0d,
-    PointerType = 0x0f,
-    ReferenceType = 0x10,
-    CompileUnit = 0x11,
-    StringType = 0x12,
-    StructureType = 0x13,
-    SubroutineType = 0x15,
-    Typedef = 0x16,
-    UnionType = 0x17,
-    UnspecifiedParameters = 0x18,
-    Variant = 0x19,
-    CommonBlock = 0x1a,
-    CommonInclusion = 0x1b,
-    Inheritance = 0x1c,
-    InlinedSubroutine = 0x1d,
-    Module = 0x1e,
-    PtrToMemberType = 0x1f,
-    SetType = 0x20,
-    SubrangeType = 0x21,
-    WithStmt = 0x22,
-    AccessDeclaration = 0x23,
-    BaseType = 0x24,
-    CatchBlock = 0x25,
-    ConstType = 0x26,
-    Constant = 0x27,
-    Enumerator = 0x28,
-    FileType = 0x29,
-    Friend = 0x2a,
-    Namelist = 0x2b,
-    NamelistItem = 0x2c,
-    PackedType = 0x2d,
-    Subprogram = 0x2e,
-    TemplateTypeParameter = 0x2f,
-    TemplateValueParameter = 0x30,
-    ThrownType = 0x31,
-    TryBlock = 0x32,
-    VariantPart = 0x33,
-    Variable = 0x34,
-    VolatileType = 0x35,
-    DwarfProcedure = 0x36,
-    RestrictType = 0x37,
-    InterfaceType = 0x38,
-    Namespace = 0x39,
-    ImportedModule = 0x3a,
-    UnspecifiedType = 0x3b,
-    PartialUnit = 0x3c,
-    ImportedUnit = 0x3d,
-    Condition = 0x3f,
-    SharedType = 0x40,
-    TypeUnit = 0x41,
-    RvalueReferenceType = 0x42,
-    TemplateAlias = 0x43,
-    LoUser = 0x4080,
-    HiUser = 0xffff,
+/// Unlike an enum, the newtype can carry any value, so that values we don't
+/// know about (such as new vendor extensions) can flow through as data rather
+/// than being parse errors.
+macro_rules! dwarf_constants {
+    (
+        $(#[$meta:meta])*
+        pub struct $struct_name:ident($struct_type:ty) {
+            $( $name:ident = $value:expr, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $struct_name(pub $struct_type);
+
+        #[allow(missing_docs, non_upper_case_globals)]
+        impl $struct_name {
+            $( pub const $name: $struct_name = $struct_name($value); )+
+        }
+
+        impl $struct_name {
+            /// Get the name of this value's associated constant, or `None` if
+            /// this is not a value that we know about. If several constants
+            /// share this value, the first one defined is returned.
+            pub fn name(&self) -> Option<&'static str> {
+                $(
+                    if self.0 == $value {
+                        return Some(stringify!($name));
+                    }
+                )+
+                None
+            }
+        }
+
+        impl fmt::Debug for $struct_name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self.name() {
+                    Some(name) => write!(f, "{}::{}", stringify!($struct_name), name),
+                    None => write!(f, "{}({:#x})", stringify!($struct_name), self.0),
+                }
+            }
+        }
+    };
+}
+
+dwarf_constants! {
+    /// Abbreviation tag types, aka `DW_TAG_whatever` in the standard.
+    ///
+    /// DWARF standard 4, section 7.5.4, page 154
+    ///
+    /// This can hold any value, not just the ones with named constants, so that
+    /// vendor extensions and tags from future versions of the standard are
+    /// preserved rather than rejected.
+    pub struct AbbreviationTag(u64) {
+        ArrayType = 0x01,
+        ClassType = 0x02,
+        EntryPoint = 0x03,
+        EnumerationType = 0x04,
+        FormalParameter = 0x05,
+        ImportedDeclaration = 0x08,
+        Label = 0x0a,
+        LexicalBlock = 0x0b,
+        Member = 0x0d,
+        PointerType = 0x0f,
+        ReferenceType = 0x10,
+        CompileUnit = 0x11,
+        StringType = 0x12,
+        StructureType = 0x13,
+        SubroutineType = 0x15,
+        Typedef = 0x16,
+        UnionType = 0x17,
+        UnspecifiedParameters = 0x18,
+        Variant = 0x19,
+        CommonBlock = 0x1a,
+        CommonInclusion = 0x1b,
+        Inheritance = 0x1c,
+        InlinedSubroutine = 0x1d,
+        Module = 0x1e,
+        PtrToMemberType = 0x1f,
+        SetType = 0x20,
+        SubrangeType = 0x21,
+        WithStmt = 0x22,
+        AccessDeclaration = 0x23,
+        BaseType = 0x24,
+        CatchBlock = 0x25,
+        ConstType = 0x26,
+        Constant = 0x27,
+        Enumerator = 0x28,
+        FileType = 0x29,
+        Friend = 0x2a,
+        Namelist = 0x2b,
+        NamelistItem = 0x2c,
+        PackedType = 0x2d,
+        Subprogram = 0x2e,
+        TemplateTypeParameter = 0x2f,
+        TemplateValueParameter = 0x30,
+        ThrownType = 0x31,
+        TryBlock = 0x32,
+        VariantPart = 0x33,
+        Variable = 0x34,
+        VolatileType = 0x35,
+        DwarfProcedure = 0x36,
+        RestrictType = 0x37,
+        InterfaceType = 0x38,
+        Namespace = 0x39,
+        ImportedModule = 0x3a,
+        UnspecifiedType = 0x3b,
+        PartialUnit = 0x3c,
+        ImportedUnit = 0x3d,
+        Condition = 0x3f,
+        SharedType = 0x40,
+        TypeUnit = 0x41,
+        RvalueReferenceType = 0x42,
+        TemplateAlias = 0x43,
+
+        // DWARF 5.
+        CoarrayType = 0x44,
+        GenericSubrange = 0x45,
+        DynamicType = 0x46,
+        AtomicType = 0x47,
+        CallSite = 0x48,
+        CallSiteParameter = 0x49,
+        SkeletonUnit = 0x4a,
+        ImmutableType = 0x4b,
+
+        LoUser = 0x4080,
+        HiUser = 0xffff,
+
+        // MIPS extensions.
+        MipsLoop = 0x4081,
+
+        // HP extensions.
+        HpArrayDescriptor = 0x4090,
+        HpBlissField = 0x4091,
+        HpBlissFieldSet = 0x4092,
+
+        // GNU extensions.
+        FormatLabel = 0x4101,
+        FunctionTemplate = 0x4102,
+        ClassTemplate = 0x4103,
+        GnuBincl = 0x4104,
+        GnuEincl = 0x4105,
+        GnuTemplateTemplateParam = 0x4106,
+        GnuTemplateParameterPack = 0x4107,
+        GnuFormalParameterPack = 0x4108,
+        GnuCallSite = 0x4109,
+        GnuCallSiteParameter = 0x410a,
+
+        // Apple extensions.
+        AppleProperty = 0x4200,
+
+        // ALTIUM extensions.
+        AltiumCircType = 0x5101,
+        AltiumMwaCircType = 0x5102,
+        AltiumRevCarryType = 0x5103,
+        AltiumRom = 0x5111,
+
+        // LLVM extensions.
+        LlvmAnnotation = 0x6000,
+
+        // UPC extensions.
+        UpcSharedType = 0x8765,
+        UpcStrictType = 0x8766,
+        UpcRelaxedType = 0x8767,
+
+        // PGI extensions.
+        PgiKanjiType = 0xa000,
+        PgiInterfaceBlock = 0xa020,
+
+        // Borland extensions.
+        BorlandProperty = 0xb000,
+        BorlandDelphiString = 0xb001,
+        BorlandDelphiDynamicArray = 0xb002,
+        BorlandDelphiSet = 0xb003,
+        BorlandDelphiVariant = 0xb004,
+    }
+}
+
+impl AbbreviationTag {
+    /// Return true if this tag is in the range reserved for user (typically
+    /// vendor) extensions, `DW_TAG_lo_user` through `DW_TAG_hi_user`.
+    pub fn is_user_defined(&self) -> bool {
+        AbbreviationTag::LoUser.0 <= self.0 && self.0 <= AbbreviationTag::HiUser.0
+    }
+}
+
+#[test]
+fn test_dwarf_constants_debug() {
+    assert_eq!(format!("{:?}", AbbreviationTag::CompileUnit), "AbbreviationTag::CompileUnit");
+    assert_eq!(format!("{:?}", AbbreviationTag(0x4fff)), "AbbreviationTag(0x4fff)");
+    assert_eq!(AttributeName::Name.name(), Some("Name"));
+    assert_eq!(AttributeName::GnuAllCallSites.name(), Some("GnuAllCallSites"));
+    assert_eq!(AttributeName(0x2001).name(), Some("MipsFde"));
+    assert_eq!(AttributeForm::GnuStrpAlt.name(), Some("GnuStrpAlt"));
+    assert_eq!(AttributeForm(0x7f).name(), None);
+
+    assert!(AbbreviationTag::LoUser.is_user_defined());
+    assert!(AbbreviationTag(0x4fff).is_user_defined());
+    assert!(AbbreviationTag::HiUser.is_user_defined());
+    assert!(!AbbreviationTag::CompileUnit.is_user_defined());
 }
 
 /// Whether an abbreviation's type has children or not, aka
@@ -76,163 +212,384 @@ pub enum AbbreviationTag {
 /// DWARF standard 4, section 7.5.4, page 154
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AbbreviationHasChildren {
+    /// The type does not have children.
+    No = 0x0,
+
     /// The type has children.
-    Yes = 0x0,
+    Yes = 0x1,
+}
 
-    /// The type does not have children.
-    No = 0x1,
+dwarf_constants! {
+    /// The set of possible attribute names, aka `DW_AT_whatever` in the standard.
+    ///
+    /// DWARF standard 4, section 7.5.4, page 155
+    ///
+    /// This can hold any value, not just the ones with named constants, so that
+    /// vendor extensions and attributes from future versions of the standard are
+    /// preserved rather than rejected.
+    pub struct AttributeName(u64) {
+        Sibling = 0x1,
+        Location = 0x2,
+        Name = 0x3,
+        Ordering = 0x9,
+        ByteSize = 0xb,
+        BitOffset = 0xc,
+        BitSize = 0x0d,
+        StmtList = 0x10,
+        LowPc = 0x11,
+        HighPc = 0x12,
+        Language = 0x13,
+        Discr = 0x15,
+        DiscrValue = 0x16,
+        Visibility = 0x17,
+        Import = 0x18,
+        StringLength = 0x19,
+        CommonReference = 0x1a,
+        CompDir = 0x1b,
+        ConstValue = 0x1c,
+        ContainingType = 0x1d,
+        DefaultValue = 0x1e,
+        Inline = 0x20,
+        IsOptional = 0x21,
+        LowerBound = 0x22,
+        Producer = 0x25,
+        Prototyped = 0x27,
+        ReturnAddr = 0x2a,
+        StartScope = 0x2c,
+        BitStride = 0x2e,
+        UpperBound = 0x2f,
+        AbstractOrigin = 0x31,
+        Accessibility = 0x32,
+        AddressClass = 0x33,
+        Artificial = 0x34,
+        BaseTypes = 0x35,
+        CallingConvention = 0x36,
+        Count = 0x37,
+        DataMemberLocation = 0x38,
+        DeclColumn = 0x39,
+        DeclFile = 0x3a,
+        DeclLine = 0x3b,
+        Declaration = 0x3c,
+        DiscrList = 0x3d,
+        Encoding = 0x3e,
+        External = 0x3f,
+        FrameBase = 0x40,
+        Friend = 0x41,
+        IdentifierCase = 0x42,
+        MacroInfo = 0x43,
+        NamelistItem = 0x44,
+        Priority = 0x45,
+        Segment = 0x46,
+        Specification = 0x47,
+        StaticLink = 0x48,
+        Type = 0x49,
+        UseLocation = 0x4a,
+        VariableParameter = 0x4b,
+        Virtuality = 0x4c,
+        VtableElemLocation = 0x4d,
+        Allocated = 0x4e,
+        Associated = 0x4f,
+        DataLocation = 0x50,
+        ByteStride = 0x51,
+        EntryPc = 0x52,
+        UseUtf8 = 0x53,
+        Extension = 0x54,
+        Ranges = 0x55,
+        Trampoline = 0x56,
+        CallColumn = 0x57,
+        CallFile = 0x58,
+        CallLine = 0x59,
+        Description = 0x5a,
+        BinaryScale = 0x5b,
+        DecimalScale = 0x5c,
+        Small = 0x5d,
+        DecimalSign = 0x5e,
+        DigitCount = 0x5f,
+        PictureString = 0x60,
+        Mutable = 0x61,
+        ThreadsScaled = 0x62,
+        Explicit = 0x63,
+        ObjectPointer = 0x64,
+        Endianity = 0x65,
+        Elemental = 0x66,
+        Pure = 0x67,
+        Recursive = 0x68,
+        Signature = 0x69,
+        MainSubprogram = 0x6a,
+        DataBitOffset = 0x6b,
+        ConstExpr = 0x6c,
+        EnumClass = 0x6d,
+        LinkageName = 0x6e,
+
+        // DWARF 5.
+        StringLengthBitSize = 0x6f,
+        StringLengthByteSize = 0x70,
+        Rank = 0x71,
+        StrOffsetsBase = 0x72,
+        AddrBase = 0x73,
+        RnglistsBase = 0x74,
+        DwoName = 0x76,
+        Reference = 0x77,
+        RvalueReference = 0x78,
+        Macros = 0x79,
+        CallAllCalls = 0x7a,
+        CallAllSourceCalls = 0x7b,
+        CallAllTailCalls = 0x7c,
+        CallReturnPc = 0x7d,
+        CallValue = 0x7e,
+        CallOrigin = 0x7f,
+        CallParameter = 0x80,
+        CallPc = 0x81,
+        CallTailCall = 0x82,
+        CallTarget = 0x83,
+        CallTargetClobbered = 0x84,
+        CallDataLocation = 0x85,
+        CallDataValue = 0x86,
+        Noreturn = 0x87,
+        Alignment = 0x88,
+        ExportSymbols = 0x89,
+        Deleted = 0x8a,
+        Defaulted = 0x8b,
+        LoclistsBase = 0x8c,
+
+        LoUser = 0x2000,
+        HiUser = 0x3fff,
+
+        // MIPS extensions.
+        MipsFde = 0x2001,
+        MipsLoopBegin = 0x2002,
+        MipsTailLoopBegin = 0x2003,
+        MipsEpilogBegin = 0x2004,
+        MipsLoopUnrollFactor = 0x2005,
+        MipsSoftwarePipelineDepth = 0x2006,
+        MipsLinkageName = 0x2007,
+        MipsStride = 0x2008,
+        MipsAbstractName = 0x2009,
+        MipsCloneOrigin = 0x200a,
+        MipsHasInlines = 0x200b,
+        MipsStrideByte = 0x200c,
+        MipsStrideElem = 0x200d,
+        MipsPtrDopetype = 0x200e,
+        MipsAllocatableDopetype = 0x200f,
+        MipsAssumedShapeDopetype = 0x2010,
+        MipsAssumedSize = 0x2011,
+
+        // HP extensions. Several of these share their values with the MIPS
+        // extensions above.
+        HpBlockIndex = 0x2000,
+        HpUnmodifiable = 0x2001,
+        HpPrologue = 0x2005,
+        HpEpilogue = 0x2008,
+        HpActualsStmtList = 0x2010,
+        HpProcPerSection = 0x2011,
+        HpRawDataPtr = 0x2012,
+        HpPassByReference = 0x2013,
+        HpOptLevel = 0x2014,
+        HpProfVersionId = 0x2015,
+        HpOptFlags = 0x2016,
+        HpColdRegionLowPc = 0x2017,
+        HpColdRegionHighPc = 0x2018,
+        HpAllVariablesModifiable = 0x2019,
+        HpLinkageName = 0x201a,
+        HpProfFlags = 0x201b,
+        HpUnitName = 0x201f,
+        HpUnitSize = 0x2020,
+        HpWidenedByteSize = 0x2021,
+        HpDefinitionPoints = 0x2022,
+        HpDefaultLocation = 0x2023,
+        HpIsResultParam = 0x2029,
+
+        // GNU extensions.
+        SfNames = 0x2101,
+        SrcInfo = 0x2102,
+        MacInfo = 0x2103,
+        SrcCoords = 0x2104,
+        BodyBegin = 0x2105,
+        BodyEnd = 0x2106,
+        GnuVector = 0x2107,
+        GnuGuardedBy = 0x2108,
+        GnuPtGuardedBy = 0x2109,
+        GnuGuarded = 0x210a,
+        GnuPtGuarded = 0x210b,
+        GnuLocksExcluded = 0x210c,
+        GnuExclusiveLocksRequired = 0x210d,
+        GnuSharedLocksRequired = 0x210e,
+        GnuOdrSignature = 0x210f,
+        GnuTemplateName = 0x2110,
+        GnuCallSiteValue = 0x2111,
+        GnuCallSiteDataValue = 0x2112,
+        GnuCallSiteTarget = 0x2113,
+        GnuCallSiteTargetClobbered = 0x2114,
+        GnuTailCall = 0x2115,
+        GnuAllTailCallSites = 0x2116,
+        GnuAllCallSites = 0x2117,
+        GnuAllSourceCallSites = 0x2118,
+        GnuMacros = 0x2119,
+        GnuDeleted = 0x211a,
+        GnuDwoName = 0x2130,
+        GnuDwoId = 0x2131,
+        GnuRangesBase = 0x2132,
+        GnuAddrBase = 0x2133,
+        GnuPubnames = 0x2134,
+        GnuPubtypes = 0x2135,
+        GnuDiscriminator = 0x2136,
+        GnuLocviews = 0x2137,
+        GnuEntryView = 0x2138,
+
+        // VMS extensions.
+        VmsRtnbegPdAddress = 0x2201,
+
+        // UPC extensions.
+        UpcThreadsScaled = 0x3210,
+
+        // PGI extensions.
+        PgiLbase = 0x3a00,
+        PgiSoffset = 0x3a01,
+        PgiLstride = 0x3a02,
+
+        // Borland extensions.
+        BorlandPropertyRead = 0x3b11,
+        BorlandPropertyWrite = 0x3b12,
+        BorlandPropertyImplements = 0x3b13,
+        BorlandPropertyIndex = 0x3b14,
+        BorlandPropertyDefault = 0x3b15,
+        BorlandDelphiUnit = 0x3b20,
+        BorlandDelphiClass = 0x3b21,
+        BorlandDelphiRecord = 0x3b22,
+        BorlandDelphiMetaclass = 0x3b23,
+        BorlandDelphiConstructor = 0x3b24,
+        BorlandDelphiDestructor = 0x3b25,
+        BorlandDelphiAnonymousMethod = 0x3b26,
+        BorlandDelphiInterface = 0x3b27,
+        BorlandDelphiAbi = 0x3b28,
+        BorlandDelphiReturn = 0x3b29,
+        BorlandDelphiFrameptr = 0x3b30,
+        BorlandClosure = 0x3b31,
+
+        // LLVM extensions.
+        LlvmIncludePath = 0x3e00,
+        LlvmConfigMacros = 0x3e01,
+        LlvmSysroot = 0x3e02,
+        LlvmTagOffset = 0x3e03,
+        LlvmApinotes = 0x3e07,
+
+        // Apple extensions.
+        AppleOptimized = 0x3fe1,
+        AppleFlags = 0x3fe2,
+        AppleIsa = 0x3fe3,
+        AppleBlock = 0x3fe4,
+        AppleMajorRuntimeVers = 0x3fe5,
+        AppleRuntimeClass = 0x3fe6,
+        AppleOmitFramePtr = 0x3fe7,
+        ApplePropertyName = 0x3fe8,
+        ApplePropertyGetter = 0x3fe9,
+        ApplePropertySetter = 0x3fea,
+        ApplePropertyAttribute = 0x3feb,
+        AppleObjcCompleteType = 0x3fec,
+        AppleProperty = 0x3fed,
+    }
 }
 
-/// The set of possible attribute names, aka `DW_AT_whatever` in the standard.
-///
-/// DWARF standard 4, section 7.5.4, page 155
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(missing_docs)]
-pub enum AttributeName {
-    Sibling = 0x1,
-    Location = 0x2,
-    Name = 0x3,
-    Ordering = 0x9,
-    ByteSize = 0xb,
-    BitOffset = 0xc,
-    BitSize = 0x0d,
-    StmtList = 0x10,
-    LowPc = 0x11,
-    HighPc = 0x12,
-    Language = 0x13,
-    Discr = 0x15,
-    DiscrValue = 0x16,
-    Visibility = 0x17,
-    Import = 0x18,
-    StringLength = 0x19,
-    CommonReference = 0x1a,
-    CompDir = 0x1b,
-    ConstValue = 0x1c,
-    ContainingType = 0x1d,
-    DefaultValue = 0x1e,
-    Inline = 0x20,
-    IsOptional = 0x21,
-    LowerBound = 0x22,
-    Producer = 0x25,
-    Prototyped = 0x27,
-    ReturnAddr = 0x2a,
-    StartScope = 0x2c,
-    BitStride = 0x2e,
-    UpperBound = 0x2f,
-    AbstractOrigin = 0x31,
-    Accessibility = 0x32,
-    AddressClass = 0x33,
-    Artificial = 0x34,
-    BaseTypes = 0x35,
-    CallingConvention = 0x36,
-    Count = 0x37,
-    DataMemberLocation = 0x38,
-    DeclColumn = 0x39,
-    DeclFile = 0x3a,
-    DeclLine = 0x3b,
-    Declaration = 0x3c,
-    DiscrList = 0x3d,
-    Encoding = 0x3e,
-    External = 0x3f,
-    FrameBase = 0x40,
-    Friend = 0x41,
-    IdentifierCase = 0x42,
-    MacroInfo = 0x43,
-    NamelistItem = 0x44,
-    Priority = 0x45,
-    Segment = 0x46,
-    Specification = 0x47,
-    StaticLink = 0x48,
-    Type = 0x49,
-    UseLocation = 0x4a,
-    VariableParameter = 0x4b,
-    Virtuality = 0x4c,
-    VtableElemLocation = 0x4d,
-    Allocated = 0x4e,
-    Associated = 0x4f,
-    DataLocation = 0x50,
-    ByteStride = 0x51,
-    EntryPc = 0x52,
-    UseUtf8 = 0x53,
-    Extension = 0x54,
-    Ranges = 0x55,
-    Trampoline = 0x56,
-    CallColumn = 0x57,
-    CallFile = 0x58,
-    CallLine = 0x59,
-    Description = 0x5a,
-    BinaryScale = 0x5b,
-    DecimalScale = 0x5c,
-    Small = 0x5d,
-    DecimalSign = 0x5e,
-    DigitCount = 0x5f,
-    PictureString = 0x60,
-    Mutable = 0x61,
-    ThreadsScaled = 0x62,
-    Explicit = 0x63,
-    ObjectPointer = 0x64,
-    Endianity = 0x65,
-    Elemental = 0x66,
-    Pure = 0x67,
-    Recursive = 0x68,
-    Signature = 0x69,
-    MainSubprogram = 0x6a,
-    DataBitOffset = 0x6b,
-    ConstExpr = 0x6c,
-    EnumClass = 0x6d,
-    LinkageName = 0x6e,
-    LoUser = 0x2000,
-    HiUser = 0x3fff,
+impl AttributeName {
+    /// Return true if this attribute name is in the range reserved for user
+    /// (typically vendor) extensions, `DW_AT_lo_user` through `DW_AT_hi_user`.
+    pub fn is_user_defined(&self) -> bool {
+        AttributeName::LoUser.0 <= self.0 && self.0 <= AttributeName::HiUser.0
+    }
 }
 
-/// The type and encoding of an attribute, aka `DW_FORM_whatever` in the
-/// standard.
-///
-/// DWARF standard 4, section 7.5.4, page 160
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(missing_docs)]
-pub enum AttributeForm {
-    Addr = 0x01,
-    Block2 = 0x03,
-    Block4 = 0x04,
-    Data2 = 0x05,
-    Data4 = 0x06,
-    Data8 = 0x07,
-    String = 0x08,
-    Block = 0x09,
-    Block1 = 0x0a,
-    Data1 = 0x0b,
-    Flag = 0x0c,
-    Sdata = 0x0d,
-    Strp = 0x0e,
-    Udata = 0x0f,
-    RefAddr = 0x10,
-    Ref1 = 0x11,
-    Ref2 = 0x12,
-    Ref4 = 0x13,
-    Ref8 = 0x14,
-    RefUdata = 0x15,
-    Indirect = 0x16,
-    SecOffset = 0x17,
-    Exprloc = 0x18,
-    FlagPresent = 0x19,
-    RefSig8 = 0x20,
+dwarf_constants! {
+    /// The type and encoding of an attribute, aka `DW_FORM_whatever` in the
+    /// standard.
+    ///
+    /// DWARF standard 4, section 7.5.4, page 160
+    ///
+    /// This can hold any value, not just the ones with named constants. Values
+    /// that we don't know how to decode are only an error when an entry actually
+    /// has an attribute of that form.
+    pub struct AttributeForm(u64) {
+        Addr = 0x01,
+        Block2 = 0x03,
+        Block4 = 0x04,
+        Data2 = 0x05,
+        Data4 = 0x06,
+        Data8 = 0x07,
+        String = 0x08,
+        Block = 0x09,
+        Block1 = 0x0a,
+        Data1 = 0x0b,
+        Flag = 0x0c,
+        Sdata = 0x0d,
+        Strp = 0x0e,
+        Udata = 0x0f,
+        RefAddr = 0x10,
+        Ref1 = 0x11,
+        Ref2 = 0x12,
+        Ref4 = 0x13,
+        Ref8 = 0x14,
+        RefUdata = 0x15,
+        Indirect = 0x16,
+        SecOffset = 0x17,
+        Exprloc = 0x18,
+        FlagPresent = 0x19,
+        RefSig8 = 0x20,
+
+        // DWARF 5.
+        Strx = 0x1a,
+        Addrx = 0x1b,
+        RefSup4 = 0x1c,
+        StrpSup = 0x1d,
+        Data16 = 0x1e,
+        LineStrp = 0x1f,
+        ImplicitConst = 0x21,
+        Loclistx = 0x22,
+        Rnglistx = 0x23,
+        RefSup8 = 0x24,
+        Strx1 = 0x25,
+        Strx2 = 0x26,
+        Strx3 = 0x27,
+        Strx4 = 0x28,
+        Addrx1 = 0x29,
+        Addrx2 = 0x2a,
+        Addrx3 = 0x2b,
+        Addrx4 = 0x2c,
+
+        // GNU extensions, for split DWARF and for `dwz` supplementary object
+        // files.
+        GnuAddrIndex = 0x1f01,
+        GnuStrIndex = 0x1f02,
+        GnuRefAlt = 0x1f20,
+        GnuStrpAlt = 0x1f21,
+    }
 }
 
 /// The description of an attribute in an abbreviated type. It is a pair of name
-/// and form.
+/// and form, along with the attribute's value for `DW_FORM_implicit_const`,
+/// which is stored in the abbreviation rather than in each entry.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AttributeSpecification {
     name: AttributeName,
     form: AttributeForm,
+    implicit_const_value: i64,
 }
 
 impl AttributeSpecification {
     /// Construct a new `AttributeSpecification` from the given name and form.
     pub fn new(name: AttributeName, form: AttributeForm) -> AttributeSpecification {
         AttributeSpecification {
-            name: name,
-            form: form,
+            name,
+            form,
+            implicit_const_value: 0,
+        }
+    }
+
+    /// Construct a new `DW_FORM_implicit_const` `AttributeSpecification` with
+    /// the given name and value.
+    pub fn new_implicit_const(name: AttributeName, value: i64) -> AttributeSpecification {
+        AttributeSpecification {
+            name,
+            form: AttributeForm::ImplicitConst,
+            implicit_const_value: value,
         }
     }
 
@@ -245,19 +602,32 @@ impl AttributeSpecification {
     pub fn form(&self) -> AttributeForm {
         self.form
     }
+
+    /// Get the attribute's value, if its form is `DW_FORM_implicit_const`.
+    pub fn implicit_const_value(&self) -> Option<i64> {
+        if self.form == AttributeForm::ImplicitConst {
+            Some(self.implicit_const_value)
+        } else {
+            None
+        }
+    }
 }
 
 /// An abbreviation describes the shape of a DIE type: its code, tag type,
 /// whether it has children, and its set of attributes.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Abbreviation {
+///
+/// The attribute specifications are borrowed, since `Abbreviations` stores the
+/// attributes of all of its abbreviations together in a single contiguous
+/// buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Abbreviation<'a> {
     code: u64,
     tag: AbbreviationTag,
     has_children: AbbreviationHasChildren,
-    attributes: Vec<AttributeSpecification>,
+    attributes: &'a [AttributeSpecification],
 }
 
-impl Abbreviation {
+impl<'a> Abbreviation<'a> {
     /// Construct a new `Abbreviation`.
     ///
     /// ### Panics
@@ -266,13 +636,13 @@ impl Abbreviation {
     pub fn new(code: u64,
                tag: AbbreviationTag,
                has_children: AbbreviationHasChildren,
-               attributes: Vec<AttributeSpecification>) -> Abbreviation {
+               attributes: &'a [AttributeSpecification]) -> Abbreviation<'a> {
         assert!(code != 0);
         Abbreviation {
-            code: code,
-            tag: tag,
-            has_children: has_children,
-            attributes: attributes,
+            code,
+            tag,
+            has_children,
+            attributes,
         }
     }
 
@@ -295,22 +665,43 @@ impl Abbreviation {
     }
 
     /// Get this abbreviation's attributes.
-    pub fn attributes(&self) -> &[AttributeSpecification] {
-        &self.attributes[..]
+    pub fn attributes(&self) -> &'a [AttributeSpecification] {
+        self.attributes
     }
 }
 
+/// An abbreviation as it is stored within `Abbreviations`, with its attributes
+/// given as a range of the shared attributes buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AbbreviationEntry {
+    code: u64,
+    tag: AbbreviationTag,
+    has_children: AbbreviationHasChildren,
+    attributes_start: usize,
+    attributes_end: usize,
+}
+
 /// A set of type abbreviations.
+///
+/// Compilers almost always number their abbreviations consecutively starting
+/// from 1, so those abbreviations are kept in a vector indexed by code, and
+/// only abbreviations with codes that don't fit that pattern fall back to a
+/// map. The attributes of every abbreviation are stored contiguously in one
+/// buffer, rather than each abbreviation allocating its own.
 #[derive(Debug, Clone)]
 pub struct Abbreviations {
-    abbrevs: hash_map::HashMap<u64, Abbreviation>,
+    attributes: Vec<AttributeSpecification>,
+    dense: Vec<AbbreviationEntry>,
+    sparse: btree_map::BTreeMap<u64, AbbreviationEntry>,
 }
 
 impl Abbreviations {
     /// Construct a new, empty set of abbreviations.
     pub fn new() -> Abbreviations {
         Abbreviations {
-            abbrevs: hash_map::HashMap::new(),
+            attributes: Vec::new(),
+            dense: Vec::new(),
+            sparse: btree_map::BTreeMap::new(),
         }
     }
 
@@ -319,41 +710,334 @@ impl Abbreviations {
     /// Returns `Ok` if it is the first abbreviation in the set with its code,
     /// `Err` if the code is a duplicate and there already exists an
     /// abbreviation in the set with the given abbreviation's code.
+    #[allow(clippy::result_unit_err)]
     pub fn insert(&mut self, abbrev: Abbreviation) -> Result<(), ()> {
-        match self.abbrevs.entry(abbrev.code) {
-            hash_map::Entry::Occupied(_) =>
-                Err(()),
-            hash_map::Entry::Vacant(entry) => {
-                entry.insert(abbrev);
-                Ok(())
-            },
+        let next_dense_code = self.dense.len() as u64 + 1;
+        if abbrev.code < next_dense_code || self.sparse.contains_key(&abbrev.code) {
+            return Err(());
+        }
+
+        let attributes_start = self.attributes.len();
+        self.attributes.extend_from_slice(abbrev.attributes);
+        let entry = AbbreviationEntry {
+            code: abbrev.code,
+            tag: abbrev.tag,
+            has_children: abbrev.has_children,
+            attributes_start,
+            attributes_end: self.attributes.len(),
+        };
+
+        if abbrev.code != next_dense_code {
+            self.sparse.insert(abbrev.code, entry);
+            return Ok(());
+        }
+
+        // Keep the dense vector as long as possible by pulling in any
+        // abbreviations with the following codes that were inserted out of
+        // order.
+        self.dense.push(entry);
+        loop {
+            let next_dense_code = self.dense.len() as u64 + 1;
+            match self.sparse.remove(&next_dense_code) {
+                Some(entry) => self.dense.push(entry),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn abbreviation<'a>(&'a self, entry: &AbbreviationEntry) -> Abbreviation<'a> {
+        Abbreviation {
+            code: entry.code,
+            tag: entry.tag,
+            has_children: entry.has_children,
+            attributes: &self.attributes[entry.attributes_start..entry.attributes_end],
+        }
+    }
+
+    /// Get the abbreviation associated with the given code.
+    pub fn get<'a>(&'a self, code: u64) -> Option<Abbreviation<'a>> {
+        let entry = if code != 0 && code <= self.dense.len() as u64 {
+            Some(&self.dense[code as usize - 1])
+        } else {
+            self.sparse.get(&code)
+        };
+
+        entry.map(|entry| self.abbreviation(entry))
+    }
+
+    /// Get the number of abbreviations in the set.
+    pub fn len(&self) -> usize {
+        self.dense.len() + self.sparse.len()
+    }
+
+    /// Return true if the set contains no abbreviations, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the abbreviations in the set, in order of their codes.
+    pub fn iter<'a>(&'a self) -> AbbreviationsIter<'a> {
+        AbbreviationsIter {
+            abbrevs: self,
+            dense: self.dense.iter(),
+            sparse: self.sparse.values(),
         }
     }
 }
 
+impl Default for Abbreviations {
+    fn default() -> Abbreviations {
+        Abbreviations::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a Abbreviations {
+    type Item = Abbreviation<'a>;
+    type IntoIter = AbbreviationsIter<'a>;
+
+    fn into_iter(self) -> AbbreviationsIter<'a> {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_abbreviations_insert_and_get() {
+    let attrs = [AttributeSpecification::new(AttributeName::Name, AttributeForm::String),
+                 AttributeSpecification::new(AttributeName::Type, AttributeForm::Ref4)];
+    let abbrev = |code, attrs| {
+        Abbreviation::new(code, AbbreviationTag::Variable, AbbreviationHasChildren::No, attrs)
+    };
+
+    let mut abbrevs = Abbreviations::new();
+    assert!(abbrevs.is_empty());
+    assert_eq!(abbrevs.insert(abbrev(1, &attrs[..1])), Ok(()));
+    assert_eq!(abbrevs.insert(abbrev(2, &attrs[..])), Ok(()));
+    assert_eq!(abbrevs.insert(abbrev(5, &attrs[1..])), Ok(()));
+    assert_eq!(abbrevs.insert(abbrev(100, &[])), Ok(()));
+    assert_eq!(abbrevs.dense.len(), 2);
+    assert_eq!(abbrevs.sparse.len(), 2);
+
+    // Filling in the gap moves the out of order abbreviations into the dense
+    // vector.
+    assert_eq!(abbrevs.insert(abbrev(4, &attrs[..1])), Ok(()));
+    assert_eq!(abbrevs.insert(abbrev(3, &attrs[..])), Ok(()));
+    assert_eq!(abbrevs.dense.len(), 5);
+    assert_eq!(abbrevs.sparse.len(), 1);
+
+    assert_eq!(abbrevs.insert(abbrev(2, &[])), Err(()));
+    assert_eq!(abbrevs.insert(abbrev(5, &[])), Err(()));
+    assert_eq!(abbrevs.insert(abbrev(100, &[])), Err(()));
+    assert_eq!(abbrevs.len(), 6);
+
+    assert_eq!(abbrevs.get(0), None);
+    assert_eq!(abbrevs.get(1), Some(abbrev(1, &attrs[..1])));
+    assert_eq!(abbrevs.get(2), Some(abbrev(2, &attrs[..])));
+    assert_eq!(abbrevs.get(3), Some(abbrev(3, &attrs[..])));
+    assert_eq!(abbrevs.get(5), Some(abbrev(5, &attrs[1..])));
+    assert_eq!(abbrevs.get(6), None);
+    assert_eq!(abbrevs.get(100), Some(abbrev(100, &[])));
+
+    let codes: Vec<_> = abbrevs.iter().map(|abbrev| abbrev.code()).collect();
+    assert_eq!(codes, vec![1, 2, 3, 4, 5, 100]);
+}
+
+/// An iterator over a set of abbreviations, in order of their codes.
+#[derive(Debug, Clone)]
+pub struct AbbreviationsIter<'a> {
+    abbrevs: &'a Abbreviations,
+    dense: slice::Iter<'a, AbbreviationEntry>,
+    sparse: btree_map::Values<'a, u64, AbbreviationEntry>,
+}
+
+impl<'a> Iterator for AbbreviationsIter<'a> {
+    type Item = Abbreviation<'a>;
+
+    fn next(&mut self) -> Option<Abbreviation<'a>> {
+        let abbrevs = self.abbrevs;
+        self.dense
+            .next()
+            .or_else(|| self.sparse.next())
+            .map(|entry| abbrevs.abbreviation(entry))
+    }
+}
+
+dwarf_constants! {
+    /// The `DW_UT_*` unit type of a DWARF 5 unit header, which determines the
+    /// extra fields found in the header.
+    pub struct UnitType(u8) {
+        Compile = 0x01,
+        Type = 0x02,
+        Partial = 0x03,
+        Skeleton = 0x04,
+        SplitCompile = 0x05,
+        SplitType = 0x06,
+        LoUser = 0x80,
+        HiUser = 0xff,
+    }
+}
+
+dwarf_constants! {
+    /// The `DW_MACINFO_*` type of an entry in the `.debug_macinfo` section.
+    pub struct MacinfoType(u8) {
+        Define = 0x01,
+        Undef = 0x02,
+        StartFile = 0x03,
+        EndFile = 0x04,
+        VendorExt = 0xff,
+    }
+}
+
+dwarf_constants! {
+    /// The `DW_MACRO_*` type of an entry in the `.debug_macro` section.
+    pub struct MacroType(u8) {
+        Define = 0x01,
+        Undef = 0x02,
+        StartFile = 0x03,
+        EndFile = 0x04,
+        DefineStrp = 0x05,
+        UndefStrp = 0x06,
+        Import = 0x07,
+        DefineSup = 0x08,
+        UndefSup = 0x09,
+        ImportSup = 0x0a,
+        DefineStrx = 0x0b,
+        UndefStrx = 0x0c,
+        LoUser = 0xe0,
+        HiUser = 0xff,
+
+        // GNU extensions, which were standardized by DWARF 5.
+        GnuDefineIndirect = 0x05,
+        GnuUndefIndirect = 0x06,
+        GnuTransparentInclude = 0x07,
+        GnuDefineIndirectAlt = 0x08,
+        GnuUndefIndirectAlt = 0x09,
+        GnuTransparentIncludeAlt = 0x0a,
+    }
+}
+
+dwarf_constants! {
+    /// The kind of symbol that an entry in a `.debug_gnu_pubnames` or
+    /// `.debug_gnu_pubtypes` section names, as used by GDB's index.
+    pub struct GdbIndexSymbolKind(u8) {
+        None = 0,
+        Type = 1,
+        Variable = 2,
+        Function = 3,
+        Other = 4,
+    }
+}
+
+dwarf_constants! {
+    /// The `DW_LLE_GNU_*` kind of an entry in a pre-standard split DWARF
+    /// location list, as found in the `.debug_loc.dwo` section.
+    pub struct LocationListEntryKind(u8) {
+        GnuEndOfListEntry = 0x00,
+        GnuBaseAddressSelectionEntry = 0x01,
+        GnuStartEndEntry = 0x02,
+        GnuStartLengthEntry = 0x03,
+    }
+}
+
+dwarf_constants! {
+    /// A `DW_LNS_*` standard opcode of a line number program.
+    pub struct StandardOpcode(u8) {
+        Copy = 0x01,
+        AdvancePc = 0x02,
+        AdvanceLine = 0x03,
+        SetFile = 0x04,
+        SetColumn = 0x05,
+        NegateStmt = 0x06,
+        SetBasicBlock = 0x07,
+        ConstAddPc = 0x08,
+        FixedAdvancePc = 0x09,
+        SetPrologueEnd = 0x0a,
+        SetEpilogueBegin = 0x0b,
+        SetIsa = 0x0c,
+    }
+}
+
+dwarf_constants! {
+    /// A `DW_LNE_*` extended opcode of a line number program.
+    pub struct ExtendedOpcode(u8) {
+        EndSequence = 0x01,
+        SetAddress = 0x02,
+        DefineFile = 0x03,
+        SetDiscriminator = 0x04,
+        LoUser = 0x80,
+        HiUser = 0xff,
+    }
+}
+
+dwarf_constants! {
+    /// The `DW_LNCT_*` content type of an entry in a DWARF 5 line number
+    /// program header's directory or file name table.
+    pub struct LineNumberContentType(u64) {
+        Path = 0x1,
+        DirectoryIndex = 0x2,
+        Timestamp = 0x3,
+        Size = 0x4,
+        Md5 = 0x5,
+        LoUser = 0x2000,
+        HiUser = 0x3fff,
+    }
+}
+
 /// The header of a compilation unit's debugging information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CompilationUnitHeader {
     unit_length: u64,
     version: u16,
+    unit_type: UnitType,
     debug_abbrev_offset: u64,
     address_size: u8,
+    type_signature: Option<u64>,
+    type_offset: Option<u64>,
+    dwo_id: Option<u64>,
 }
 
 impl CompilationUnitHeader {
-    /// Construct a new `CompilationUnitHeader`.
+    /// Construct a new `CompilationUnitHeader` for a `DW_UT_compile` unit.
     pub fn new(unit_length: u64,
                version: u16,
                debug_abbrev_offset: u64,
                address_size: u8) -> CompilationUnitHeader {
         CompilationUnitHeader {
-            unit_length: unit_length,
-            version: version,
-            debug_abbrev_offset: debug_abbrev_offset,
-            address_size: address_size
+            unit_length,
+            version,
+            unit_type: UnitType::Compile,
+            debug_abbrev_offset,
+            address_size,
+            type_signature: None,
+            type_offset: None,
+            dwo_id: None,
         }
     }
 
+    /// Replace this header's unit type.
+    pub fn with_unit_type(mut self, unit_type: UnitType) -> CompilationUnitHeader {
+        self.unit_type = unit_type;
+        self
+    }
+
+    /// Set the type signature and type offset of a `DW_UT_type` or
+    /// `DW_UT_split_type` unit.
+    pub fn with_type_signature(mut self,
+                               type_signature: u64,
+                               type_offset: u64)
+                               -> CompilationUnitHeader {
+        self.type_signature = Some(type_signature);
+        self.type_offset = Some(type_offset);
+        self
+    }
+
+    /// Set the split DWARF id of a `DW_UT_skeleton` or `DW_UT_split_compile`
+    /// unit.
+    pub fn with_dwo_id(mut self, dwo_id: u64) -> CompilationUnitHeader {
+        self.dwo_id = Some(dwo_id);
+        self
+    }
+
     /// Get the length of the debugging info for this compilation unit.
     pub fn unit_length(&self) -> u64 {
         self.unit_length
@@ -374,4 +1058,28 @@ impl CompilationUnitHeader {
     pub fn address_size(&self) -> u8 {
         self.address_size
     }
+
+    /// Get the type of this unit. Units from before DWARF 5 have no unit type
+    /// in their header, and are `UnitType::Compile`.
+    pub fn unit_type(&self) -> UnitType {
+        self.unit_type
+    }
+
+    /// Get the signature of the type defined by this unit, if this is a type
+    /// unit.
+    pub fn type_signature(&self) -> Option<u64> {
+        self.type_signature
+    }
+
+    /// Get the offset of the type defined by this unit's entry from the start
+    /// of the unit's header, if this is a type unit.
+    pub fn type_offset(&self) -> Option<u64> {
+        self.type_offset
+    }
+
+    /// Get the id that pairs a skeleton unit with its split compilation unit,
+    /// if this is either one of them.
+    pub fn dwo_id(&self) -> Option<u64> {
+        self.dwo_id
+    }
 }